@@ -0,0 +1,239 @@
+use crate::cassandra::error::*;
+use crate::cassandra::inet::Inet;
+use crate::cassandra::util::Protected;
+
+use crate::cassandra_sys::cass_authenticator_address;
+use crate::cassandra_sys::cass_authenticator_class_name;
+use crate::cassandra_sys::cass_authenticator_exchange_data;
+use crate::cassandra_sys::cass_authenticator_hostname;
+use crate::cassandra_sys::cass_authenticator_set_error_n;
+use crate::cassandra_sys::cass_authenticator_set_exchange_data;
+use crate::cassandra_sys::cass_authenticator_set_response;
+use crate::cassandra_sys::CassAuthenticator as _Authenticator;
+use crate::cassandra_sys::CassAuthenticatorCallbacks;
+use crate::cassandra_sys::CassInet;
+
+use std::any::Any;
+use std::os::raw::{c_char, c_void};
+use std::panic::{self, AssertUnwindSafe};
+use std::{ptr, slice, str};
+
+/// A custom SASL authenticator, for authentication schemes beyond the plain
+/// text username and password of `Cluster::set_credentials`, such as
+/// short-lived tokens or Kerberos. Install it with `Cluster::set_authenticator`.
+///
+/// The driver performs an exchange with a host each time it opens a
+/// connection: it sends the initial response, answers any challenges from the
+/// host, and is finally told of success. One authenticator is shared by every
+/// connection, and is called from the driver's IO threads, so state belonging
+/// to a single exchange should be kept with `AuthExchange::set_state`.
+///
+/// An error returned from any method fails the connection attempt, with the
+/// error's message.
+///
+/// ```
+/// use cassandra_cpp::*;
+///
+/// /// The `PLAIN` mechanism, as used by Cassandra's `PasswordAuthenticator`.
+/// struct Plain {
+///     username: String,
+///     password: String,
+/// }
+///
+/// impl Authenticator for Plain {
+///     fn initial_response(&self, _exchange: &mut AuthExchange) -> Result<Vec<u8>> {
+///         Ok(format!("\0{}\0{}", self.username, self.password).into_bytes())
+///     }
+/// }
+/// ```
+pub trait Authenticator: Send + Sync {
+    /// Returns the token to send to the host when the exchange starts.
+    fn initial_response(&self, exchange: &mut AuthExchange) -> Result<Vec<u8>>;
+
+    /// Returns the response to a challenge token sent by the host. By
+    /// default, challenges are not expected, and fail the exchange.
+    fn evaluate_challenge(&self, exchange: &mut AuthExchange, challenge: &[u8]) -> Result<Vec<u8>> {
+        let _ = challenge;
+        Err(ErrorKind::AuthenticationFailed(format!(
+            "unexpected challenge from {} ({})",
+            exchange.address().to_string(),
+            exchange.class_name()
+        ))
+        .into())
+    }
+
+    /// Called with the final token sent by the host, which may be empty, once
+    /// the host has accepted the exchange. Does nothing by default.
+    fn on_success(&self, exchange: &mut AuthExchange, token: &[u8]) -> Result<()> {
+        let _ = (exchange, token);
+        Ok(())
+    }
+}
+
+/// A SASL exchange with a single host, passed to each `Authenticator` call.
+pub struct AuthExchange {
+    auth: *mut _Authenticator,
+    state: Option<Box<dyn Any + Send>>,
+}
+
+impl std::fmt::Debug for AuthExchange {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("AuthExchange")
+            .field("address", &self.address())
+            .field("hostname", &self.hostname())
+            .field("class_name", &self.class_name())
+            .finish()
+    }
+}
+
+impl AuthExchange {
+    /// The address of the host being authenticated with.
+    pub fn address(&self) -> Inet {
+        let mut address = CassInet {
+            address: [0; 16],
+            address_length: 0,
+        };
+        unsafe { cass_authenticator_address(self.auth, &mut address) };
+        Inet::build(address)
+    }
+
+    /// The hostname of the host being authenticated with, which is empty
+    /// unless the driver resolves hostnames.
+    pub fn hostname(&self) -> &str {
+        let mut length = 0;
+        unsafe {
+            let hostname = cass_authenticator_hostname(self.auth, &mut length);
+            str_from_raw(hostname, length)
+        }
+    }
+
+    /// The class name of the host's authenticator, e.g.
+    /// `org.apache.cassandra.auth.PasswordAuthenticator`.
+    pub fn class_name(&self) -> &str {
+        let mut length = 0;
+        unsafe {
+            let class_name = cass_authenticator_class_name(self.auth, &mut length);
+            str_from_raw(class_name, length)
+        }
+    }
+
+    /// Stores state for the rest of this exchange, replacing any existing
+    /// state.
+    pub fn set_state<T: Any + Send>(&mut self, state: T) {
+        self.state = Some(Box::new(state));
+    }
+
+    /// The state stored for this exchange, if it has the given type.
+    pub fn state<T: Any + Send>(&self) -> Option<&T> {
+        self.state.as_ref().and_then(|state| state.downcast_ref())
+    }
+
+    /// The state stored for this exchange, if it has the given type.
+    pub fn state_mut<T: Any + Send>(&mut self) -> Option<&mut T> {
+        self.state.as_mut().and_then(|state| state.downcast_mut())
+    }
+}
+
+/// Borrow a string owned by the driver, which is empty if it is not UTF-8.
+unsafe fn str_from_raw<'a>(s: *const c_char, length: usize) -> &'a str {
+    if s.is_null() {
+        return "";
+    }
+    str::from_utf8(slice::from_raw_parts(s as *const u8, length)).unwrap_or_default()
+}
+
+/// Borrow a token passed by the driver, which may be null.
+unsafe fn token<'a>(token: *const c_char, size: usize) -> &'a [u8] {
+    if token.is_null() {
+        &[]
+    } else {
+        slice::from_raw_parts(token as *const u8, size)
+    }
+}
+
+/// The callbacks installed by `Cluster::set_authenticator`.
+pub(crate) static CALLBACKS: CassAuthenticatorCallbacks = CassAuthenticatorCallbacks {
+    initial_callback: Some(initial_callback),
+    challenge_callback: Some(challenge_callback),
+    success_callback: Some(success_callback),
+    cleanup_callback: Some(cleanup_callback),
+};
+
+/// Box an authenticator as the data passed to the callbacks, to be freed by
+/// `free_data`.
+pub(crate) fn into_data(authenticator: Box<dyn Authenticator>) -> *mut c_void {
+    Box::into_raw(Box::new(authenticator)) as *mut c_void
+}
+
+/// Frees the authenticator. Called by the C++ driver once it is no longer
+/// used by the cluster or any of its sessions.
+pub(crate) unsafe extern "C" fn free_data(data: *mut c_void) {
+    drop(Box::from_raw(data as *mut Box<dyn Authenticator>));
+}
+
+/// Run an authenticator method, passing its error to the driver. A panic
+/// must not unwind into C, so it is reported as an error too.
+unsafe fn run<T>(auth: *mut _Authenticator, f: impl FnOnce() -> Result<T>, on_ok: impl FnOnce(T)) {
+    let message = match panic::catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(value)) => return on_ok(value),
+        Ok(Err(e)) => e.to_string(),
+        Err(_) => "Authenticator panicked".to_string(),
+    };
+    cass_authenticator_set_error_n(auth, message.as_ptr() as *const c_char, message.len());
+}
+
+unsafe fn respond(auth: *mut _Authenticator, f: impl FnOnce() -> Result<Vec<u8>>) {
+    run(auth, f, |response| {
+        cass_authenticator_set_response(auth, response.as_ptr() as *const c_char, response.len())
+    })
+}
+
+/// The exchange stored with the driver by `initial_callback`.
+unsafe fn exchange<'a>(auth: *mut _Authenticator) -> &'a mut AuthExchange {
+    &mut *(cass_authenticator_exchange_data(auth) as *mut AuthExchange)
+}
+
+unsafe extern "C" fn initial_callback(auth: *mut _Authenticator, data: *mut c_void) {
+    let authenticator = &*(data as *const Box<dyn Authenticator>);
+    let exchange = Box::into_raw(Box::new(AuthExchange { auth, state: None }));
+    cass_authenticator_set_exchange_data(auth, exchange as *mut c_void);
+    respond(auth, || authenticator.initial_response(&mut *exchange));
+}
+
+unsafe extern "C" fn challenge_callback(
+    auth: *mut _Authenticator,
+    data: *mut c_void,
+    challenge: *const c_char,
+    challenge_size: usize,
+) {
+    let authenticator = &*(data as *const Box<dyn Authenticator>);
+    let challenge = token(challenge, challenge_size);
+    respond(auth, || {
+        authenticator.evaluate_challenge(exchange(auth), challenge)
+    });
+}
+
+unsafe extern "C" fn success_callback(
+    auth: *mut _Authenticator,
+    data: *mut c_void,
+    token_data: *const c_char,
+    token_size: usize,
+) {
+    let authenticator = &*(data as *const Box<dyn Authenticator>);
+    let token = token(token_data, token_size);
+    run(
+        auth,
+        || authenticator.on_success(exchange(auth), token),
+        |()| (),
+    );
+}
+
+/// Frees the exchange, if it was started. Called by the C++ driver when the
+/// connection's authentication is finished with.
+unsafe extern "C" fn cleanup_callback(auth: *mut _Authenticator, _data: *mut c_void) {
+    let exchange = cass_authenticator_exchange_data(auth) as *mut AuthExchange;
+    if !exchange.is_null() {
+        cass_authenticator_set_exchange_data(auth, ptr::null_mut());
+        drop(Box::from_raw(exchange));
+    }
+}
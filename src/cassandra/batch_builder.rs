@@ -0,0 +1,199 @@
+use crate::cassandra::batch::{Batch, BatchType};
+use crate::cassandra::consistency::Consistency;
+use crate::cassandra::error::*;
+use crate::cassandra::statement::Statement;
+use crate::cassandra::token::{partition_key_bytes, KeyComponent};
+use crate::Session;
+
+use futures::stream::{self, TryStreamExt};
+
+use std::collections::HashMap;
+
+/// The statements of one batch under construction.
+#[derive(Debug, Default)]
+struct Chunk {
+    statements: Vec<Statement>,
+    size: usize,
+}
+
+/// Builds unlogged batches from statements, grouping them by partition and
+/// splitting them to stay within size and count limits, and executes the
+/// batches concurrently.
+///
+/// Batches spanning many partitions put load on the coordinator and trigger
+/// Cassandra's `batch_size_warn_threshold`; single-partition unlogged batches
+/// are applied atomically by one replica set. Each statement is added with its
+/// partition key, and statements for the same partition are batched together
+/// in the order they were added. Statements for different partitions are never
+/// in the same batch, and are not applied in any particular order.
+///
+/// The size of each statement is estimated from its query text and bound
+/// values. By default a batch holds at most 100 statements and 5KiB, matching
+/// Cassandra's default warning threshold. A single statement larger than the
+/// limit is put in a batch by itself.
+///
+/// ```no_run
+/// # use cassandra_cpp::*;
+/// # async fn test(session: &Session) -> Result<()> {
+/// let insert = session
+///     .prepare("INSERT INTO ks.readings (sensor, at, value) VALUES (?, ?, ?)")
+///     .await?;
+/// let mut builder = BatchBuilder::new(session);
+/// for i in 0..1000 {
+///     let sensor = format!("sensor-{}", i % 10);
+///     let mut statement = insert.bind();
+///     statement.bind(0, sensor.as_str())?;
+///     statement.bind(1, i as i64)?;
+///     statement.bind(2, 0.5f64)?;
+///     builder.add(statement, &[&sensor])?;
+/// }
+/// builder.execute().await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct BatchBuilder {
+    session: Session,
+    max_statements: usize,
+    max_size: usize,
+    concurrency: usize,
+    consistency: Option<Consistency>,
+    /// The partitions in the order they were first seen.
+    partitions: Vec<Vec<Chunk>>,
+    /// The index into `partitions` of each serialized partition key.
+    index: HashMap<Vec<u8>, usize>,
+}
+
+impl BatchBuilder {
+    /// Create a builder for batches on the given session.
+    pub fn new(session: &Session) -> Self {
+        BatchBuilder {
+            session: session.clone(),
+            max_statements: 100,
+            max_size: 5 * 1024,
+            concurrency: 8,
+            consistency: None,
+            partitions: vec![],
+            index: HashMap::new(),
+        }
+    }
+
+    /// Sets the most statements in one batch.
+    pub fn max_statements(&mut self, max_statements: usize) -> &mut Self {
+        self.max_statements = max_statements.max(1);
+        self
+    }
+
+    /// Sets the largest estimated size of one batch, in bytes.
+    pub fn max_size(&mut self, max_size: usize) -> &mut Self {
+        self.max_size = max_size;
+        self
+    }
+
+    /// Sets the most batches executed at once.
+    pub fn concurrency(&mut self, concurrency: usize) -> &mut Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Sets the consistency of every batch.
+    pub fn consistency(&mut self, consistency: Consistency) -> &mut Self {
+        self.consistency = Some(consistency);
+        self
+    }
+
+    /// Add a statement writing to the partition with the given key. The key
+    /// components must be given in the order of the table's partition key.
+    pub fn add(
+        &mut self,
+        statement: Statement,
+        partition_key: &[&dyn KeyComponent],
+    ) -> Result<&mut Self> {
+        if &self.session != statement.session() {
+            return Err(ErrorKind::BatchSessionMismatch(
+                self.session.clone(),
+                statement.session().clone(),
+            )
+            .into());
+        }
+        let key = partition_key_bytes(partition_key);
+        let partitions = &mut self.partitions;
+        let index = *self.index.entry(key).or_insert_with(|| {
+            partitions.push(vec![]);
+            partitions.len() - 1
+        });
+        let chunks = &mut self.partitions[index];
+
+        let size = statement.estimated_size();
+        let full = match chunks.last() {
+            Some(chunk) => {
+                chunk.statements.len() >= self.max_statements || chunk.size + size > self.max_size
+            }
+            None => true,
+        };
+        if full {
+            chunks.push(Chunk::default());
+        }
+        let chunk = chunks.last_mut().expect("chunk");
+        chunk.statements.push(statement);
+        chunk.size += size;
+        Ok(self)
+    }
+
+    /// The number of statements added.
+    pub fn len(&self) -> usize {
+        self.chunks().map(|chunk| chunk.statements.len()).sum()
+    }
+
+    /// Whether no statements have been added.
+    pub fn is_empty(&self) -> bool {
+        self.partitions.is_empty()
+    }
+
+    /// The number of batches the statements will be split into.
+    pub fn batch_count(&self) -> usize {
+        self.chunks().count()
+    }
+
+    fn chunks(&self) -> impl Iterator<Item = &Chunk> {
+        self.partitions.iter().flatten()
+    }
+
+    /// Build the batches, grouped by partition in the order partitions were
+    /// first added.
+    pub fn build(self) -> Result<Vec<Batch>> {
+        let session = self.session;
+        let consistency = self.consistency;
+        self.partitions
+            .into_iter()
+            .flatten()
+            .map(|chunk| {
+                let mut batch = session.batch(BatchType::UNLOGGED);
+                if let Some(consistency) = consistency {
+                    batch.set_consistency(consistency)?;
+                }
+                for statement in chunk.statements {
+                    batch.add_statement(statement)?;
+                }
+                Ok(batch)
+            })
+            .collect()
+    }
+
+    /// Build and execute the batches, with at most the configured number in
+    /// flight at once, returning the number of batches executed.
+    ///
+    /// Fails with the first error encountered. Batches which had already been
+    /// applied are not rolled back, and those in flight may still be applied.
+    pub async fn execute(self) -> Result<usize> {
+        let concurrency = self.concurrency;
+        let batches = self.build()?;
+        let count = batches.len();
+        stream::iter(batches.into_iter().map(Ok))
+            .try_for_each_concurrent(concurrency, |batch| async move {
+                batch.execute().await.map(|_| ())
+            })
+            .await?;
+        Ok(count)
+    }
+}
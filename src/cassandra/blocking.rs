@@ -0,0 +1,166 @@
+use crate::cassandra::batch::Batch;
+use crate::cassandra::cluster::Cluster;
+use crate::cassandra::error::*;
+use crate::cassandra::future::{CassFuture, Completable};
+use crate::cassandra::prepared::PreparedStatement;
+use crate::cassandra::result::CassResult;
+use crate::cassandra::statement::Statement;
+use crate::Session;
+
+use std::ops::{Deref, DerefMut};
+use std::time::Duration;
+
+/// Block on a driver future, for at most `timeout` if given.
+fn wait<T: Completable>(future: CassFuture<T>, timeout: Option<Duration>) -> Result<T> {
+    match timeout {
+        Some(timeout) => future.wait_timeout(timeout),
+        None => future.wait(),
+    }
+}
+
+/// A session whose operations block the calling thread until they complete,
+/// for programs without an async runtime.
+///
+/// Each operation blocks on the driver's own future, so no runtime is needed.
+/// If a timeout is set, an operation which takes longer fails with
+/// `ErrorKind::Timeout`; the request is abandoned, but may still be executed
+/// by the cluster.
+///
+/// ```no_run
+/// use cassandra_cpp::*;
+/// use std::time::Duration;
+/// # fn test() -> Result<()> {
+/// let mut cluster = Cluster::default();
+/// cluster.set_contact_points("127.0.0.1")?;
+/// let session = BlockingSession::connect(&mut cluster)?.with_timeout(Duration::from_secs(5));
+/// let mut statement = session.statement("SELECT release_version FROM system.local WHERE key = ?");
+/// statement.bind(0, "local")?;
+/// let result = statement.execute()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct BlockingSession {
+    session: Session,
+    timeout: Option<Duration>,
+}
+
+impl BlockingSession {
+    /// Connects to the cluster, blocking until connected.
+    pub fn connect(cluster: &mut Cluster) -> Result<Self> {
+        Ok(cluster.connect_future().wait()?.into())
+    }
+
+    /// Connects to the cluster, setting the keyspace of the session, and
+    /// blocking until connected.
+    pub fn connect_keyspace(cluster: &mut Cluster, keyspace: &str) -> Result<Self> {
+        Ok(cluster.connect_keyspace_future(keyspace).wait()?.into())
+    }
+
+    /// Sets how long each operation may block for. By default, there is no
+    /// limit beyond the driver's request timeout.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// The timeout of each operation, if any.
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    /// The underlying session.
+    pub fn session(&self) -> &Session {
+        &self.session
+    }
+
+    /// Returns the underlying session.
+    pub fn into_session(self) -> Session {
+        self.session
+    }
+
+    /// Creates a prepared statement with the given query.
+    pub fn prepare(&self, query: impl AsRef<str>) -> Result<PreparedStatement> {
+        let query = query.as_ref();
+        let prepared = wait(self.session.prepare_future(query), self.timeout)?;
+        Ok(prepared.with_query(query))
+    }
+
+    /// Creates a statement with the given query.
+    pub fn statement(&self, query: impl AsRef<str>) -> BlockingStatement {
+        self.wrap(self.session.statement(query))
+    }
+
+    /// Creates a statement bound from a prepared statement.
+    pub fn bind(&self, prepared: &PreparedStatement) -> BlockingStatement {
+        self.wrap(prepared.bind())
+    }
+
+    /// Wraps a statement so it executes with this session's timeout.
+    pub fn wrap(&self, statement: Statement) -> BlockingStatement {
+        BlockingStatement {
+            statement,
+            timeout: self.timeout,
+        }
+    }
+
+    /// Executes a given query.
+    pub fn execute(&self, query: impl AsRef<str>) -> Result<CassResult> {
+        self.statement(query).execute()
+    }
+
+    /// Executes a batch statement.
+    pub fn execute_batch(&self, batch: &Batch) -> Result<CassResult> {
+        wait(self.session.execute_batch(batch), self.timeout)
+    }
+}
+
+impl From<Session> for BlockingSession {
+    fn from(session: Session) -> Self {
+        BlockingSession {
+            session,
+            timeout: None,
+        }
+    }
+}
+
+/// A statement whose execution blocks the calling thread until it completes.
+/// Created by `BlockingSession`. Values are bound through the underlying
+/// `Statement`, to which this dereferences.
+#[derive(Debug)]
+pub struct BlockingStatement {
+    statement: Statement,
+    timeout: Option<Duration>,
+}
+
+impl BlockingStatement {
+    /// Sets how long execution may block for.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Executes the statement, blocking until it completes.
+    pub fn execute(self) -> Result<CassResult> {
+        wait(self.statement.execute_future(), self.timeout)
+    }
+
+    /// Returns the underlying statement.
+    pub fn into_statement(self) -> Statement {
+        self.statement
+    }
+}
+
+impl Deref for BlockingStatement {
+    type Target = Statement;
+
+    fn deref(&self) -> &Statement {
+        &self.statement
+    }
+}
+
+impl DerefMut for BlockingStatement {
+    fn deref_mut(&mut self) -> &mut Statement {
+        &mut self.statement
+    }
+}
@@ -0,0 +1,571 @@
+use crate::cassandra::error::*;
+use crate::cassandra::metrics::SessionMetrics;
+use crate::cassandra::prepared::PreparedStatement;
+use crate::cassandra::result::CassResult;
+use crate::cassandra::statement::Statement;
+use crate::cassandra::util;
+
+use futures::channel::mpsc::UnboundedSender;
+use futures::future::{self, BoxFuture};
+use futures::stream::{FuturesUnordered, Stream, StreamExt};
+use futures::FutureExt;
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::task::Poll;
+use std::time::{Duration, Instant};
+
+/// A record which can be bound to the prepared statement of a `BulkLoader`.
+///
+/// ```
+/// # use cassandra_cpp::*;
+/// struct Reading {
+///     sensor: String,
+///     value: f64,
+/// }
+///
+/// impl BindRecord for Reading {
+///     fn bind_to(&self, statement: &mut Statement) -> Result<()> {
+///         statement.bind(0, self.sensor.as_str())?;
+///         statement.bind(1, self.value)?;
+///         Ok(())
+///     }
+/// }
+/// ```
+pub trait BindRecord {
+    /// Bind the record's values to `statement`.
+    fn bind_to(&self, statement: &mut Statement) -> Result<()>;
+}
+
+/// Where a `BulkLoader` sends records which could not be written.
+pub trait DeadLetterSink<R> {
+    /// Accept a record which failed to be written, with its final error.
+    fn write(&mut self, record: R, error: Error);
+}
+
+impl<R> DeadLetterSink<R> for Vec<(R, Error)> {
+    fn write(&mut self, record: R, error: Error) {
+        self.push((record, error));
+    }
+}
+
+impl<R> DeadLetterSink<R> for UnboundedSender<(R, Error)> {
+    fn write(&mut self, record: R, error: Error) {
+        // If the receiver has gone, there is nowhere left to report the record.
+        let _ = self.unbounded_send((record, error));
+    }
+}
+
+impl<R, S: DeadLetterSink<R> + ?Sized> DeadLetterSink<R> for &mut S {
+    fn write(&mut self, record: R, error: Error) {
+        (**self).write(record, error)
+    }
+}
+
+/// Whether an error indicates that the cluster or driver is overloaded, so
+/// concurrency should be reduced.
+fn is_overload(error: &Error) -> bool {
+    let code = match error.kind() {
+        ErrorKind::CassError(code, _) => code,
+        ErrorKind::CassErrorResult(code, ..) => code,
+        _ => return false,
+    };
+    matches!(
+        code,
+        CassErrorCode::LIB_REQUEST_TIMED_OUT
+            | CassErrorCode::LIB_REQUEST_QUEUE_FULL
+            | CassErrorCode::SERVER_OVERLOADED
+            | CassErrorCode::SERVER_WRITE_TIMEOUT
+    )
+}
+
+/// A short name for the kind of an error, used to count errors by kind.
+fn error_name(error: &Error) -> String {
+    match error.kind() {
+        ErrorKind::CassError(code, _) => format!("{:?}", code),
+        ErrorKind::CassErrorResult(code, ..) => format!("{:?}", code),
+        kind => {
+            let description = format!("{:?}", kind);
+            match description.find(|c: char| !c.is_alphanumeric()) {
+                Some(end) => description[..end].to_string(),
+                None => description,
+            }
+        }
+    }
+}
+
+/// How a `BulkLoader` retries records which fail.
+#[derive(Clone)]
+pub struct BulkRetryPolicy {
+    max_retries: u32,
+    initial_delay: Duration,
+    max_delay: Duration,
+    retryable: fn(&Error) -> bool,
+}
+
+impl fmt::Debug for BulkRetryPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("BulkRetryPolicy")
+            .field("max_retries", &self.max_retries)
+            .field("initial_delay", &self.initial_delay)
+            .field("max_delay", &self.max_delay)
+            .finish()
+    }
+}
+
+impl Default for BulkRetryPolicy {
+    /// Retry transient errors (timeouts, unavailable replicas, and overload) 3
+    /// times, starting with a delay of 100ms.
+    fn default() -> Self {
+        BulkRetryPolicy::new(3, Duration::from_millis(100))
+    }
+}
+
+impl BulkRetryPolicy {
+    /// Retry transient errors up to `max_retries` times, waiting `delay` before
+    /// the first retry. The delay doubles on each subsequent retry, up to 10s.
+    pub fn new(max_retries: u32, delay: Duration) -> Self {
+        BulkRetryPolicy {
+            max_retries,
+            initial_delay: delay,
+            max_delay: Duration::from_secs(10),
+            retryable: is_transient,
+        }
+    }
+
+    /// Never retry.
+    pub fn none() -> Self {
+        BulkRetryPolicy::new(0, Duration::from_millis(0))
+    }
+
+    /// Sets the longest delay between retries.
+    pub fn max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+
+    /// Sets which errors are retried. By default only transient errors are.
+    pub fn retry_if(mut self, retryable: fn(&Error) -> bool) -> Self {
+        self.retryable = retryable;
+        self
+    }
+
+    /// The delay before the given retry (1 for the first), if the error should
+    /// be retried.
+    fn delay(&self, retry: u32, error: &Error) -> Option<Duration> {
+        if retry > self.max_retries || !(self.retryable)(error) {
+            return None;
+        }
+        let factor = 1u32.checked_shl(retry - 1).unwrap_or(u32::MAX);
+        Some(
+            self.initial_delay
+                .checked_mul(factor)
+                .unwrap_or(self.max_delay)
+                .min(self.max_delay),
+        )
+    }
+}
+
+/// The outcome of a `BulkLoader` run.
+#[derive(Debug, Clone, Default)]
+pub struct BulkLoadReport {
+    /// The number of records written successfully.
+    pub written: u64,
+    /// The number of records which could not be written, and were sent to the
+    /// dead-letter sink if there is one.
+    pub failed: u64,
+    /// The number of retries made.
+    pub retries: u64,
+    /// The number of times concurrency was reduced because of overload.
+    pub backoffs: u64,
+    /// The number of errors of each kind, including those later retried
+    /// successfully, keyed by error code (e.g., `"SERVER_WRITE_TIMEOUT"`).
+    pub errors: BTreeMap<String, u64>,
+    /// The concurrency in use when the load finished.
+    pub final_concurrency: usize,
+    /// The highest concurrency used.
+    pub peak_concurrency: usize,
+    /// The duration of the load.
+    pub elapsed: Duration,
+}
+
+impl BulkLoadReport {
+    /// The number of records written per second.
+    pub fn throughput(&self) -> f64 {
+        let seconds = self.elapsed.as_secs_f64();
+        if seconds > 0.0 {
+            self.written as f64 / seconds
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Additive-increase, multiplicative-decrease control of the number of
+/// requests in flight.
+#[derive(Debug)]
+struct Concurrency {
+    current: usize,
+    min: usize,
+    max: usize,
+    /// Successes since the last change.
+    successes: usize,
+    /// Completions since the last decrease; decreases are limited to one per
+    /// window of in-flight requests, so one burst of timeouts counts once.
+    since_decrease: usize,
+    peak: usize,
+    backoffs: u64,
+}
+
+impl Concurrency {
+    /// Brings the starting concurrency within the limits, which may have been
+    /// set in any order. The maximum wins over the minimum.
+    fn start(&mut self) {
+        self.min = self.min.min(self.max);
+        self.current = self.current.clamp(self.min, self.max);
+        self.peak = self.current;
+    }
+
+    fn on_success(&mut self) {
+        self.since_decrease += 1;
+        self.successes += 1;
+        if self.successes >= self.current && self.current < self.max {
+            self.current += 1;
+            self.peak = self.peak.max(self.current);
+            self.successes = 0;
+        }
+    }
+
+    fn on_overload(&mut self) {
+        if self.since_decrease >= self.current {
+            self.current = (self.current / 2).max(self.min);
+            self.successes = 0;
+            self.since_decrease = 0;
+            self.backoffs += 1;
+        }
+    }
+}
+
+/// The counters of `SessionMetrics` which indicate overload.
+fn overload_count(metrics: &SessionMetrics) -> u64 {
+    metrics.exceeded_pending_requests_water_mark
+        + metrics.exceeded_write_bytes_water_mark
+        + metrics.pending_request_timeouts
+        + metrics.request_timeouts
+}
+
+type Attempt<R> = BoxFuture<'static, (R, u32, Result<CassResult>)>;
+
+/// Loads a stream of records by executing a prepared statement for each, with
+/// adaptive concurrency, retries, and a dead-letter sink.
+///
+/// Concurrency starts at 32 requests in flight. It grows by one after each
+/// window of successful requests, and halves when requests time out or are
+/// rejected as overloaded, or when the session's metrics show that the
+/// pending-request or write-bytes water marks were exceeded or requests timed
+/// out. Note that the metrics cover the whole session, so other requests on
+/// the same session also cause the loader to back off.
+///
+/// ```no_run
+/// # use cassandra_cpp::*;
+/// # use futures::stream;
+/// # struct Reading;
+/// # impl BindRecord for Reading {
+/// #     fn bind_to(&self, _: &mut Statement) -> Result<()> { Ok(()) }
+/// # }
+/// # async fn test(session: &Session, readings: Vec<Reading>) -> Result<()> {
+/// let insert = session.prepare("INSERT INTO ks.readings (sensor, value) VALUES (?, ?)").await?;
+/// let mut dead_letters = vec![];
+/// let report = BulkLoader::new(insert)
+///     .max_concurrency(512)
+///     .retry_policy(BulkRetryPolicy::new(5, std::time::Duration::from_millis(50)))
+///     .dead_letter(&mut dead_letters)
+///     .load(stream::iter(readings))
+///     .await;
+/// println!("{:.0} rows/s, {} failed", report.throughput(), report.failed);
+/// # Ok(())
+/// # }
+/// ```
+pub struct BulkLoader<'a, R> {
+    prepared: PreparedStatement,
+    concurrency: Concurrency,
+    retry_policy: BulkRetryPolicy,
+    metrics_interval: Duration,
+    dead_letter: Option<Box<dyn DeadLetterSink<R> + 'a>>,
+}
+
+impl<R> fmt::Debug for BulkLoader<'_, R> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("BulkLoader")
+            .field("prepared", &self.prepared)
+            .field("concurrency", &self.concurrency)
+            .field("retry_policy", &self.retry_policy)
+            .field("metrics_interval", &self.metrics_interval)
+            .finish()
+    }
+}
+
+impl<'a, R: BindRecord + Send + 'static> BulkLoader<'a, R> {
+    /// Create a loader which executes `prepared` for each record.
+    pub fn new(prepared: PreparedStatement) -> Self {
+        BulkLoader {
+            prepared,
+            concurrency: Concurrency {
+                current: 32,
+                min: 1,
+                max: 1024,
+                successes: 0,
+                since_decrease: 0,
+                peak: 32,
+                backoffs: 0,
+            },
+            retry_policy: BulkRetryPolicy::default(),
+            metrics_interval: Duration::from_secs(1),
+            dead_letter: None,
+        }
+    }
+
+    /// Sets the number of requests in flight at the start of the load. It is
+    /// kept within `min_concurrency` and `max_concurrency`.
+    pub fn initial_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency.current = concurrency.max(1);
+        self.concurrency.peak = self.concurrency.current;
+        self
+    }
+
+    /// Sets the fewest requests in flight that backing off may reduce to.
+    pub fn min_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency.min = concurrency.max(1);
+        self
+    }
+
+    /// Sets the most requests in flight.
+    pub fn max_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency.max = concurrency.max(1);
+        self
+    }
+
+    /// Sets how failed records are retried.
+    pub fn retry_policy(mut self, retry_policy: BulkRetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Sets how often the session's metrics are checked for signs of overload.
+    pub fn metrics_interval(mut self, interval: Duration) -> Self {
+        self.metrics_interval = interval;
+        self
+    }
+
+    /// Send records which still fail after retrying to `sink`. Otherwise they
+    /// are only counted in the report.
+    pub fn dead_letter(mut self, sink: impl DeadLetterSink<R> + 'a) -> Self {
+        self.dead_letter = Some(Box::new(sink));
+        self
+    }
+
+    /// Bind and execute a record, after waiting `delay` if it is a retry. The
+    /// wait is timed from now by the timer shared by all delays, so a retry
+    /// holds no thread while it waits.
+    fn attempt(
+        &self,
+        record: R,
+        retry: u32,
+        delay: Duration,
+    ) -> std::result::Result<Attempt<R>, (R, Error)> {
+        let mut statement = self.prepared.bind();
+        if let Err(err) = record.bind_to(&mut statement) {
+            return Err((record, err));
+        }
+        let wait = if retry > 0 {
+            Some(util::delay(delay))
+        } else {
+            None
+        };
+        Ok(async move {
+            if let Some(wait) = wait {
+                wait.await;
+            }
+            let result = statement.execute().await;
+            (record, retry, result)
+        }
+        .boxed())
+    }
+
+    fn dead_letter_record(&mut self, report: &mut BulkLoadReport, record: R, error: Error) {
+        report.failed += 1;
+        if let Some(sink) = &mut self.dead_letter {
+            sink.write(record, error);
+        }
+    }
+
+    /// Load every record from `records`, returning a report once all have been
+    /// written or sent to the dead-letter sink.
+    pub async fn load<S>(mut self, records: S) -> BulkLoadReport
+    where
+        S: Stream<Item = R>,
+    {
+        let start = Instant::now();
+        let mut records = Box::pin(records);
+        let mut input_done = false;
+        let mut in_flight: FuturesUnordered<Attempt<R>> = FuturesUnordered::new();
+        let mut report = BulkLoadReport::default();
+        let session = self.prepared.session().clone();
+        let mut last_check = Instant::now();
+        let mut last_overloads = overload_count(&session.get_metrics());
+        self.concurrency.start();
+
+        enum Event<R> {
+            Record(Option<R>),
+            Completed((R, u32, Result<CassResult>)),
+            Finished,
+        }
+
+        loop {
+            let limit = self.concurrency.current;
+            let event = future::poll_fn(|cx| {
+                if !input_done && in_flight.len() < limit {
+                    if let Poll::Ready(record) = records.as_mut().poll_next(cx) {
+                        return Poll::Ready(Event::Record(record));
+                    }
+                }
+                match in_flight.poll_next_unpin(cx) {
+                    Poll::Ready(Some(completed)) => Poll::Ready(Event::Completed(completed)),
+                    Poll::Ready(None) if input_done => Poll::Ready(Event::Finished),
+                    _ => Poll::Pending,
+                }
+            })
+            .await;
+
+            match event {
+                Event::Record(Some(record)) => match self.attempt(record, 0, Duration::default()) {
+                    Ok(attempt) => in_flight.push(attempt),
+                    Err((record, err)) => {
+                        *report.errors.entry(error_name(&err)).or_default() += 1;
+                        self.dead_letter_record(&mut report, record, err);
+                    }
+                },
+                Event::Record(None) => input_done = true,
+                Event::Completed((_, _, Ok(_))) => {
+                    report.written += 1;
+                    self.concurrency.on_success();
+                }
+                Event::Completed((record, retry, Err(err))) => {
+                    *report.errors.entry(error_name(&err)).or_default() += 1;
+                    if is_overload(&err) {
+                        self.concurrency.on_overload();
+                    }
+                    match self.retry_policy.delay(retry + 1, &err) {
+                        Some(delay) => {
+                            report.retries += 1;
+                            match self.attempt(record, retry + 1, delay) {
+                                Ok(attempt) => in_flight.push(attempt),
+                                Err((record, err)) => {
+                                    self.dead_letter_record(&mut report, record, err)
+                                }
+                            }
+                        }
+                        None => self.dead_letter_record(&mut report, record, err),
+                    }
+                }
+                Event::Finished => break,
+            }
+
+            if last_check.elapsed() >= self.metrics_interval {
+                let overloads = overload_count(&session.get_metrics());
+                if overloads > last_overloads {
+                    self.concurrency.on_overload();
+                }
+                last_overloads = overloads;
+                last_check = Instant::now();
+            }
+        }
+
+        report.final_concurrency = self.concurrency.current;
+        report.peak_concurrency = self.concurrency.peak;
+        report.backoffs = self.concurrency.backoffs;
+        report.elapsed = start.elapsed();
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_concurrency() {
+        let mut concurrency = Concurrency {
+            current: 4,
+            min: 2,
+            max: 5,
+            successes: 0,
+            since_decrease: 0,
+            peak: 4,
+            backoffs: 0,
+        };
+        for _ in 0..4 {
+            concurrency.on_success();
+        }
+        assert_eq!(concurrency.current, 5);
+        for _ in 0..20 {
+            concurrency.on_success();
+        }
+        assert_eq!(concurrency.current, 5);
+
+        // A burst of overloads within one window halves concurrency once.
+        concurrency.on_overload();
+        concurrency.on_overload();
+        assert_eq!(concurrency.current, 2);
+        assert_eq!(concurrency.backoffs, 1);
+        for _ in 0..2 {
+            concurrency.on_success();
+        }
+        assert_eq!(concurrency.current, 3);
+        concurrency.on_overload();
+        assert_eq!(concurrency.current, 3);
+        assert_eq!(concurrency.peak, 5);
+    }
+
+    #[test]
+    fn test_concurrency_start() {
+        let mut concurrency = Concurrency {
+            current: 32,
+            min: 1,
+            max: 8,
+            successes: 0,
+            since_decrease: 0,
+            peak: 32,
+            backoffs: 0,
+        };
+        concurrency.start();
+        assert_eq!(concurrency.current, 8);
+        assert_eq!(concurrency.peak, 8);
+
+        concurrency.current = 1;
+        concurrency.min = 16;
+        concurrency.start();
+        assert_eq!(concurrency.min, 8);
+        assert_eq!(concurrency.current, 8);
+    }
+
+    #[test]
+    fn test_retry_policy() {
+        let timeout = Error::from(ErrorKind::CassError(
+            CassErrorCode::SERVER_WRITE_TIMEOUT,
+            String::new(),
+        ));
+        let invalid = Error::from(ErrorKind::CassError(
+            CassErrorCode::SERVER_INVALID_QUERY,
+            String::new(),
+        ));
+        let policy = BulkRetryPolicy::new(3, Duration::from_millis(100))
+            .max_delay(Duration::from_millis(300));
+        assert_eq!(policy.delay(1, &timeout), Some(Duration::from_millis(100)));
+        assert_eq!(policy.delay(2, &timeout), Some(Duration::from_millis(200)));
+        assert_eq!(policy.delay(3, &timeout), Some(Duration::from_millis(300)));
+        assert_eq!(policy.delay(4, &timeout), None);
+        assert_eq!(policy.delay(1, &invalid), None);
+        assert_eq!(BulkRetryPolicy::none().delay(1, &timeout), None);
+    }
+}
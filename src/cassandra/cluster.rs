@@ -1,5 +1,10 @@
+use crate::cassandra::authenticator::{self, Authenticator};
+use crate::cassandra::cluster_config::ClusterConfig;
+use crate::cassandra::consistency::Consistency;
+use crate::cassandra::credentials::{CredentialProvider, PlainAuthenticator};
 use crate::cassandra::error::*;
 use crate::cassandra::future::CassFuture;
+use crate::cassandra::host_events::{notify_host_listeners, HostEvent, HostEvents, HostListeners};
 use crate::cassandra::policy::retry::RetryPolicy;
 use crate::cassandra::session::Session;
 use crate::cassandra::ssl::Ssl;
@@ -8,15 +13,20 @@ use crate::cassandra::util::{Protected, ProtectedInner};
 
 use crate::cassandra_sys::cass_cluster_free;
 use crate::cassandra_sys::cass_cluster_new;
+use crate::cassandra_sys::cass_cluster_set_authenticator_callbacks;
+use crate::cassandra_sys::cass_cluster_set_blacklist_dc_filtering_n;
+use crate::cassandra_sys::cass_cluster_set_blacklist_filtering_n;
 use crate::cassandra_sys::cass_cluster_set_cloud_secure_connection_bundle_n;
 use crate::cassandra_sys::cass_cluster_set_cloud_secure_connection_bundle_no_ssl_lib_init_n;
 use crate::cassandra_sys::cass_cluster_set_connect_timeout;
 use crate::cassandra_sys::cass_cluster_set_connection_heartbeat_interval;
 use crate::cassandra_sys::cass_cluster_set_connection_idle_timeout;
+use crate::cassandra_sys::cass_cluster_set_consistency;
 use crate::cassandra_sys::cass_cluster_set_contact_points_n;
 use crate::cassandra_sys::cass_cluster_set_core_connections_per_host;
 use crate::cassandra_sys::cass_cluster_set_credentials_n;
 use crate::cassandra_sys::cass_cluster_set_exponential_reconnect;
+use crate::cassandra_sys::cass_cluster_set_host_listener_callback;
 use crate::cassandra_sys::cass_cluster_set_latency_aware_routing;
 use crate::cassandra_sys::cass_cluster_set_latency_aware_routing_settings;
 use crate::cassandra_sys::cass_cluster_set_load_balance_dc_aware_n;
@@ -37,6 +47,7 @@ use crate::cassandra_sys::cass_cluster_set_reconnect_wait_time;
 use crate::cassandra_sys::cass_cluster_set_request_timeout;
 use crate::cassandra_sys::cass_cluster_set_resolve_timeout;
 use crate::cassandra_sys::cass_cluster_set_retry_policy;
+use crate::cassandra_sys::cass_cluster_set_serial_consistency;
 use crate::cassandra_sys::cass_cluster_set_ssl;
 use crate::cassandra_sys::cass_cluster_set_tcp_keepalive;
 use crate::cassandra_sys::cass_cluster_set_tcp_nodelay;
@@ -44,7 +55,8 @@ use crate::cassandra_sys::cass_cluster_set_timestamp_gen;
 use crate::cassandra_sys::cass_cluster_set_token_aware_routing;
 use crate::cassandra_sys::cass_cluster_set_token_aware_routing_shuffle_replicas;
 use crate::cassandra_sys::cass_cluster_set_use_schema;
-use crate::cassandra_sys::cass_cluster_set_whitelist_filtering;
+use crate::cassandra_sys::cass_cluster_set_whitelist_dc_filtering_n;
+use crate::cassandra_sys::cass_cluster_set_whitelist_filtering_n;
 use crate::cassandra_sys::cass_cluster_set_write_bytes_high_water_mark;
 use crate::cassandra_sys::cass_cluster_set_write_bytes_low_water_mark;
 use crate::cassandra_sys::cass_false;
@@ -55,8 +67,9 @@ use crate::cassandra_sys::cass_session_connect_keyspace_n;
 use crate::cassandra_sys::cass_true;
 use crate::cassandra_sys::CassCluster as _Cluster;
 
+use std::net::IpAddr;
 use std::os::raw::c_char;
-
+use std::sync::Arc;
 use std::time::Duration;
 
 /// A CQL protocol version is just an integer.
@@ -77,7 +90,14 @@ pub type CqlProtocol = i32;
 /// # }
 /// ```
 #[derive(Debug)]
-pub struct Cluster(*mut _Cluster);
+pub struct Cluster {
+    inner: *mut _Cluster,
+    /// The keyspace which `connect` connects sessions to, if any.
+    keyspace: Option<String>,
+    /// The listeners for host events, once any are added, which sessions
+    /// connected from the cluster keep alive.
+    host_listeners: Option<Arc<HostListeners>>,
+}
 
 // The underlying C type has no thread-local state, and forbids only concurrent
 // mutation/free: https://datastax.github.io/cpp-driver/topics/#thread-safety
@@ -87,13 +107,13 @@ unsafe impl Sync for Cluster {}
 impl Drop for Cluster {
     /// Frees a cluster instance.
     fn drop(&mut self) {
-        unsafe { cass_cluster_free(self.0) }
+        unsafe { cass_cluster_free(self.inner) }
     }
 }
 
 impl ProtectedInner<*mut _Cluster> for Cluster {
     fn inner(&self) -> *mut _Cluster {
-        self.0
+        self.inner
     }
 }
 
@@ -102,18 +122,95 @@ impl Protected<*mut _Cluster> for Cluster {
         if inner.is_null() {
             panic!("Unexpected null pointer")
         };
-        Cluster(inner)
+        Cluster {
+            inner,
+            keyspace: None,
+            host_listeners: None,
+        }
     }
 }
 
 impl Default for Cluster {
     /// Creates a new cluster
     fn default() -> Cluster {
-        unsafe { Cluster(cass_cluster_new()) }
+        let inner = unsafe { cass_cluster_new() };
+        Cluster {
+            inner,
+            keyspace: None,
+            host_listeners: None,
+        }
     }
 }
 
 impl Cluster {
+    /// Creates a cluster from a configuration, after checking it is valid.
+    pub fn from_config(config: &ClusterConfig) -> Result<Cluster> {
+        config.validate()?;
+        let mut cluster = Cluster::default();
+        config.apply(&mut cluster)?;
+        Ok(cluster)
+    }
+
+    /// Creates a cluster from a connection URI, of the form
+    ///
+    /// `cassandra://[user[:password]@]host[:port][,host[:port]...][/keyspace][?option=value&...]`
+    ///
+    /// All hosts must use the same port; IPv6 addresses are given in brackets.
+    /// The user name, password and keyspace may be percent-encoded. If a
+    /// keyspace is given, `connect` connects to it. The options are:
+    ///
+    /// * `consistency` and `serial_consistency`: the default consistency of
+    ///   requests, by name, e.g. `LOCAL_QUORUM`.
+    /// * `dc`: use DC-aware load balancing with this local data center.
+    /// * `tls`: `true` to enable TLS. `tls_ca` adds a trusted certificate file,
+    ///   and `tls_verify` sets the comma-separated verification flags, e.g.
+    ///   `PEER_IDENTITY`; either also enables TLS.
+    /// * `connect_timeout` and `request_timeout`: a duration in `ms`, `s` or
+    ///   `m`, e.g. `5s`, or milliseconds if no unit is given.
+    ///
+    /// ```
+    /// # use cassandra_cpp::*;
+    /// # async fn test() -> Result<()> {
+    /// let mut cluster = Cluster::from_uri(
+    ///     "cassandra://user:pass@h1,h2:9042/keyspace?consistency=LOCAL_QUORUM&dc=eu-west",
+    /// )?;
+    /// let session = cluster.connect().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_uri(uri: &str) -> Result<Cluster> {
+        Cluster::from_config(&ClusterConfig::from_uri(uri)?)
+    }
+
+    /// Sets the keyspace which `connect` connects sessions to.
+    pub fn set_keyspace(&mut self, keyspace: &str) -> &mut Self {
+        self.keyspace = Some(keyspace.to_string());
+        self
+    }
+
+    /// The keyspace which `connect` connects sessions to, if any.
+    pub fn keyspace(&self) -> Option<&str> {
+        self.keyspace.as_deref()
+    }
+
+    /// Sets the default consistency level of requests.
+    ///
+    ///
+    /// Default: LOCAL_ONE
+    pub fn set_consistency(&mut self, consistency: Consistency) -> Result<&mut Self> {
+        unsafe { cass_cluster_set_consistency(self.inner, consistency.inner()).to_result(self) }
+    }
+
+    /// Sets the default serial consistency level of requests.
+    ///
+    ///
+    /// Default: ANY
+    pub fn set_serial_consistency(&mut self, consistency: Consistency) -> Result<&mut Self> {
+        unsafe {
+            cass_cluster_set_serial_consistency(self.inner, consistency.inner()).to_result(self)
+        }
+    }
+
     /// Sets/Appends contact points. This *MUST* be set. The first call sets
     /// the contact points and any subsequent calls appends additional contact
     /// points. Passing an empty string will clear the contact points. White space
@@ -126,7 +223,7 @@ impl Cluster {
     pub fn set_contact_points(&mut self, contact_points: &str) -> Result<&mut Self> {
         unsafe {
             let cp_ptr = contact_points.as_ptr() as *const c_char;
-            let err = cass_cluster_set_contact_points_n(self.0, cp_ptr, contact_points.len());
+            let err = cass_cluster_set_contact_points_n(self.inner, cp_ptr, contact_points.len());
             err.to_result(self)
         }
     }
@@ -138,7 +235,7 @@ impl Cluster {
     pub fn set_local_address(&mut self, name: &str) -> Result<&mut Self> {
         unsafe {
             let name_ptr = name.as_ptr() as *const c_char;
-            let err = cass_cluster_set_local_address_n(self.0, name_ptr, name.len());
+            let err = cass_cluster_set_local_address_n(self.inner, name_ptr, name.len());
             err.to_result(self)
         }
     }
@@ -149,13 +246,13 @@ impl Cluster {
     /// Default: 9042
     ///
     pub fn set_port(&mut self, port: u16) -> Result<&mut Self> {
-        unsafe { cass_cluster_set_port(self.0, port as i32).to_result(self) }
+        unsafe { cass_cluster_set_port(self.inner, port as i32).to_result(self) }
     }
 
     /// Sets the SSL context and enables SSL
     pub fn set_ssl(&mut self, ssl: Ssl) -> &Self {
         unsafe {
-            cass_cluster_set_ssl(self.0, ssl.inner());
+            cass_cluster_set_ssl(self.inner, ssl.inner());
             self
         }
     }
@@ -165,7 +262,7 @@ impl Cluster {
         unsafe {
             let path_ptr = path.as_ptr() as *const c_char;
             let err =
-                cass_cluster_set_cloud_secure_connection_bundle_n(self.0, path_ptr, path.len());
+                cass_cluster_set_cloud_secure_connection_bundle_n(self.inner, path_ptr, path.len());
             err.to_result(self)
         }
     }
@@ -181,7 +278,7 @@ impl Cluster {
         unsafe {
             let path_ptr = path.as_ptr() as *const c_char;
             let err = cass_cluster_set_cloud_secure_connection_bundle_no_ssl_lib_init_n(
-                self.0,
+                self.inner,
                 path_ptr,
                 path.len(),
             );
@@ -189,32 +286,41 @@ impl Cluster {
         }
     }
 
-    /// Connects to the cassandra cluster
+    /// Connects to the cassandra cluster, setting the keyspace of the session
+    /// if one was set with `set_keyspace`.
     pub async fn connect(&mut self) -> Result<Session> {
-        let session = Session::new();
-        let connect_future = {
-            let connect = unsafe { cass_session_connect(session.inner(), self.0) };
-            CassFuture::build(session, connect)
-        };
-        connect_future.await
+        self.connect_future().await
     }
 
     /// Connects to the cassandra cluster, setting the keyspace of the session.
     pub async fn connect_keyspace(&mut self, keyspace: &str) -> Result<Session> {
-        let session = Session::new();
-        let connect_future = {
-            let keyspace_ptr = keyspace.as_ptr() as *const c_char;
-            let connect_keyspace = unsafe {
-                cass_session_connect_keyspace_n(
-                    session.inner(),
-                    self.inner(),
-                    keyspace_ptr,
-                    keyspace.len(),
-                )
-            };
-            CassFuture::build(session, connect_keyspace)
+        self.connect_keyspace_future(keyspace).await
+    }
+
+    /// Starts connecting to the cluster, setting the keyspace of the session
+    /// if one was set with `set_keyspace`.
+    pub(crate) fn connect_future(&mut self) -> CassFuture<Session> {
+        if let Some(keyspace) = self.keyspace.clone() {
+            return self.connect_keyspace_future(&keyspace);
+        }
+        let session = Session::for_cluster(None, self.host_listeners.clone());
+        let connect = unsafe { cass_session_connect(session.inner(), self.inner) };
+        CassFuture::build_untracked(session, connect)
+    }
+
+    /// Starts connecting to the cluster, setting the keyspace of the session.
+    pub(crate) fn connect_keyspace_future(&mut self, keyspace: &str) -> CassFuture<Session> {
+        let session = Session::for_cluster(Some(keyspace), self.host_listeners.clone());
+        let keyspace_ptr = keyspace.as_ptr() as *const c_char;
+        let connect_keyspace = unsafe {
+            cass_session_connect_keyspace_n(
+                session.inner(),
+                self.inner(),
+                keyspace_ptr,
+                keyspace.len(),
+            )
         };
-        connect_future.await
+        CassFuture::build_untracked(session, connect_keyspace)
     }
 
     /// Sets the protocol version. This will automatically downgrade to the lowest
@@ -224,7 +330,7 @@ impl Cluster {
     /// Default: version 4
     ///
     pub fn set_protocol_version(&mut self, protocol_version: CqlProtocol) -> Result<&mut Self> {
-        unsafe { cass_cluster_set_protocol_version(self.0, protocol_version).to_result(self) }
+        unsafe { cass_cluster_set_protocol_version(self.inner, protocol_version).to_result(self) }
     }
 
     /// Sets the number of IO threads. This is the number of threads
@@ -234,7 +340,7 @@ impl Cluster {
     /// Default: 1
     ///
     pub fn set_num_threads_io(&mut self, num_threads: u32) -> Result<&mut Self> {
-        unsafe { cass_cluster_set_num_threads_io(self.0, num_threads).to_result(self) }
+        unsafe { cass_cluster_set_num_threads_io(self.inner, num_threads).to_result(self) }
     }
 
     /// Sets the size of the fixed size queue that stores pending requests.
@@ -243,7 +349,7 @@ impl Cluster {
     /// Default: 8192
     ///
     pub fn set_queue_size_io(&mut self, queue_size: u32) -> Result<&mut Self> {
-        unsafe { cass_cluster_set_queue_size_io(self.0, queue_size).to_result(self) }
+        unsafe { cass_cluster_set_queue_size_io(self.inner, queue_size).to_result(self) }
     }
 
     /// Sets the size of the fixed size queue that stores events.
@@ -252,7 +358,7 @@ impl Cluster {
     /// Default: 8192
     ///
     pub fn set_queue_size_event(&mut self, queue_size: u32) -> Result<&mut Self> {
-        unsafe { cass_cluster_set_queue_size_event(self.0, queue_size).to_result(self) }
+        unsafe { cass_cluster_set_queue_size_event(self.inner, queue_size).to_result(self) }
     }
 
     /// Sets the number of connections made to each server in each
@@ -263,7 +369,7 @@ impl Cluster {
     ///
     pub fn set_core_connections_per_host(&mut self, num_connections: u32) -> Result<&mut Self> {
         unsafe {
-            cass_cluster_set_core_connections_per_host(self.0, num_connections).to_result(self)
+            cass_cluster_set_core_connections_per_host(self.inner, num_connections).to_result(self)
         }
     }
 
@@ -275,7 +381,7 @@ impl Cluster {
     ///
     pub fn set_max_connections_per_host(&mut self, num_connections: u32) -> Result<&mut Self> {
         unsafe {
-            cass_cluster_set_max_connections_per_host(self.0, num_connections).to_result(self)
+            cass_cluster_set_max_connections_per_host(self.inner, num_connections).to_result(self)
         }
     }
 
@@ -286,7 +392,7 @@ impl Cluster {
     ///
     pub fn set_reconnect_wait_time(&mut self, wait_time: u32) -> &Self {
         unsafe {
-            cass_cluster_set_reconnect_wait_time(self.0, wait_time);
+            cass_cluster_set_reconnect_wait_time(self.inner, wait_time);
         }
         self
     }
@@ -298,7 +404,9 @@ impl Cluster {
     ///
     /// Default: 1
     pub fn set_max_concurrent_creation(&mut self, num_connections: u32) -> Result<&mut Self> {
-        unsafe { cass_cluster_set_max_concurrent_creation(self.0, num_connections).to_result(self) }
+        unsafe {
+            cass_cluster_set_max_concurrent_creation(self.inner, num_connections).to_result(self)
+        }
     }
 
     /// Sets the threshold for the maximum number of concurrent requests in-flight
@@ -312,7 +420,8 @@ impl Cluster {
         num_requests: u32,
     ) -> Result<&mut Self> {
         unsafe {
-            cass_cluster_set_max_concurrent_requests_threshold(self.0, num_requests).to_result(self)
+            cass_cluster_set_max_concurrent_requests_threshold(self.inner, num_requests)
+                .to_result(self)
         }
     }
 
@@ -322,7 +431,7 @@ impl Cluster {
     ///
     /// Default: 128
     pub fn set_max_requests_per_flush(&mut self, num_requests: u32) -> Result<&mut Self> {
-        unsafe { cass_cluster_set_max_requests_per_flush(self.0, num_requests).to_result(self) }
+        unsafe { cass_cluster_set_max_requests_per_flush(self.inner, num_requests).to_result(self) }
     }
 
     /// Sets the high water mark for the number of bytes outstanding
@@ -332,7 +441,9 @@ impl Cluster {
     ///
     /// Default: 64KB
     pub fn set_write_bytes_high_water_mark(&mut self, num_bytes: u32) -> Result<&mut Self> {
-        unsafe { cass_cluster_set_write_bytes_high_water_mark(self.0, num_bytes).to_result(self) }
+        unsafe {
+            cass_cluster_set_write_bytes_high_water_mark(self.inner, num_bytes).to_result(self)
+        }
     }
 
     /// Sets the low water mark for the number of bytes outstanding
@@ -342,7 +453,9 @@ impl Cluster {
     ///
     /// Default: 32KB
     pub fn set_write_bytes_low_water_mark(&mut self, num_bytes: u32) -> Result<&mut Self> {
-        unsafe { cass_cluster_set_write_bytes_low_water_mark(self.0, num_bytes).to_result(self) }
+        unsafe {
+            cass_cluster_set_write_bytes_low_water_mark(self.inner, num_bytes).to_result(self)
+        }
     }
 
     /// Sets the high water mark for the number of requests queued waiting
@@ -354,7 +467,8 @@ impl Cluster {
     /// Default: 256
     pub fn set_pending_requests_high_water_mark(&mut self, num_requests: u32) -> Result<&mut Self> {
         unsafe {
-            cass_cluster_set_pending_requests_high_water_mark(self.0, num_requests).to_result(self)
+            cass_cluster_set_pending_requests_high_water_mark(self.inner, num_requests)
+                .to_result(self)
         }
     }
 
@@ -367,7 +481,8 @@ impl Cluster {
     /// Default: 128
     pub fn set_pending_requests_low_water_mark(&mut self, num_requests: u32) -> Result<&mut Self> {
         unsafe {
-            cass_cluster_set_pending_requests_low_water_mark(self.0, num_requests).to_result(self)
+            cass_cluster_set_pending_requests_low_water_mark(self.inner, num_requests)
+                .to_result(self)
         }
     }
 
@@ -377,7 +492,7 @@ impl Cluster {
     /// Default: 5000ms
     pub fn set_connect_timeout(&mut self, timeout: Duration) -> &Self {
         unsafe {
-            cass_cluster_set_connect_timeout(self.0, timeout.as_millis() as u32);
+            cass_cluster_set_connect_timeout(self.inner, timeout.as_millis() as u32);
         }
         self
     }
@@ -388,7 +503,7 @@ impl Cluster {
     /// Default: 12000ms
     pub fn set_request_timeout(&mut self, timeout: Duration) -> &Self {
         unsafe {
-            cass_cluster_set_request_timeout(self.0, timeout.as_millis() as u32);
+            cass_cluster_set_request_timeout(self.inner, timeout.as_millis() as u32);
         }
         self
     }
@@ -402,7 +517,7 @@ impl Cluster {
     /// Default: 5000ms
     pub fn set_resolve_timeout(&mut self, timeout: Duration) -> &Self {
         unsafe {
-            cass_cluster_set_resolve_timeout(self.0, timeout.as_millis() as u32);
+            cass_cluster_set_resolve_timeout(self.inner, timeout.as_millis() as u32);
         }
         self
     }
@@ -430,7 +545,7 @@ impl Cluster {
     ) -> &Self {
         unsafe {
             cass_cluster_set_exponential_reconnect(
-                self.0,
+                self.inner,
                 base_delay_ms.as_millis() as u64,
                 max_delay_ms.as_millis() as u64,
             );
@@ -444,7 +559,7 @@ impl Cluster {
             let username_ptr = username.as_ptr() as *const c_char;
             let password_ptr = password.as_ptr() as *const c_char;
             cass_cluster_set_credentials_n(
-                self.0,
+                self.inner,
                 username_ptr,
                 username.len(),
                 password_ptr,
@@ -454,13 +569,42 @@ impl Cluster {
         Ok(self)
     }
 
+    /// Sets a custom SASL authenticator, which is used by every connection
+    /// made by sessions of this cluster. This replaces any credentials set by
+    /// `set_credentials` or `set_credential_provider`, and vice versa.
+    pub fn set_authenticator(
+        &mut self,
+        authenticator: impl Authenticator + 'static,
+    ) -> Result<&mut Self> {
+        unsafe {
+            cass_cluster_set_authenticator_callbacks(
+                self.inner,
+                &authenticator::CALLBACKS,
+                Some(authenticator::free_data),
+                authenticator::into_data(Box::new(authenticator)),
+            )
+            .to_result(self)
+        }
+    }
+
+    /// Sets a provider of credentials for plain text authentication, which is
+    /// queried each time a connection authenticates, rather than fixing the
+    /// credentials as `set_credentials` does. This replaces any credentials or
+    /// authenticator set previously.
+    pub fn set_credential_provider(
+        &mut self,
+        provider: impl CredentialProvider + 'static,
+    ) -> Result<&mut Self> {
+        self.set_authenticator(PlainAuthenticator(provider))
+    }
+
     /// Configures the cluster to use round-robin load balancing.
     ///
     /// The driver discovers all nodes in a cluster and cycles through
     /// them per request. All are considered 'local'.
     pub fn set_load_balance_round_robin(&mut self) -> &Self {
         unsafe {
-            cass_cluster_set_load_balance_round_robin(self.0);
+            cass_cluster_set_load_balance_round_robin(self.inner);
             self
         }
     }
@@ -485,7 +629,7 @@ impl Cluster {
             {
                 let local_dc_ptr = local_dc.as_ptr() as *const c_char;
                 cass_cluster_set_load_balance_dc_aware_n(
-                    self.0,
+                    self.inner,
                     local_dc_ptr,
                     local_dc.len(),
                     used_hosts_per_remote_dc,
@@ -516,7 +660,7 @@ impl Cluster {
     pub fn set_token_aware_routing(&mut self, enabled: bool) -> &Self {
         unsafe {
             cass_cluster_set_token_aware_routing(
-                self.0,
+                self.inner,
                 if enabled { cass_true } else { cass_false },
             );
         }
@@ -536,7 +680,7 @@ impl Cluster {
     pub fn set_token_aware_routing_shuffle_replicas(&mut self, enabled: bool) -> &Self {
         unsafe {
             cass_cluster_set_token_aware_routing_shuffle_replicas(
-                self.0,
+                self.inner,
                 if enabled { cass_true } else { cass_false },
             );
         }
@@ -555,7 +699,7 @@ impl Cluster {
     pub fn set_latency_aware_routing(&mut self, enabled: bool) -> &Self {
         unsafe {
             cass_cluster_set_latency_aware_routing(
-                self.0,
+                self.inner,
                 if enabled { cass_true } else { cass_false },
             );
         }
@@ -584,7 +728,7 @@ impl Cluster {
     ) -> &Self {
         unsafe {
             cass_cluster_set_latency_aware_routing_settings(
-                self.0,
+                self.inner,
                 exclusion_threshold,
                 scale.as_millis() as u64,
                 retry_period.as_millis() as u64,
@@ -595,9 +739,10 @@ impl Cluster {
         self
     }
 
-    /// /Sets/Appends whitelist hosts. The first call sets the whitelist hosts and
-    /// any subsequent calls appends additional hosts. Passing an empty string will
-    /// clear and disable the whitelist. White space is striped from the hosts.
+    /// Sets/Appends whitelist hosts. The first call sets the whitelist hosts and
+    /// any subsequent calls appends additional hosts. Passing no hosts will
+    /// clear and disable the whitelist. White space is striped from the hosts,
+    /// each of which must be an IP address or a hostname.
     ///
     /// This policy filters requests to all other policies, only allowing requests
     /// to the hosts contained in the whitelist. Any host not in the whitelist will
@@ -605,12 +750,90 @@ impl Cluster {
     /// for ensuring that the driver will only connect to a predefined set of hosts.
     ///
     ///
-    /// Examples: "127.0.0.1" "127.0.0.1,127.0.0.2", "server1.domain.com"
-    pub fn set_whitelist_filtering(&mut self, hosts: Vec<String>) -> &Self {
+    /// Examples: "127.0.0.1" "127.0.0.2", "server1.domain.com"
+    pub fn set_whitelist_filtering(
+        &mut self,
+        hosts: impl IntoIterator<Item = impl AsRef<str>>,
+    ) -> Result<&mut Self> {
+        let hosts = filter_list(hosts, check_host)?;
         unsafe {
-            cass_cluster_set_whitelist_filtering(self.0, hosts.join(",").as_ptr() as *const c_char);
+            cass_cluster_set_whitelist_filtering_n(
+                self.inner,
+                hosts.as_ptr() as *const c_char,
+                hosts.len(),
+            );
         }
-        self
+        Ok(self)
+    }
+
+    /// Sets/Appends blacklist hosts. The first call sets the blacklist hosts and
+    /// any subsequent calls appends additional hosts. Passing no hosts will
+    /// clear and disable the blacklist. White space is striped from the hosts,
+    /// each of which must be an IP address or a hostname.
+    ///
+    /// This policy filters requests to all other policies, only allowing requests
+    /// to the hosts not contained in the blacklist. Any host in the blacklist will
+    /// be ignored and a connection will not be established. This policy is useful
+    /// for ensuring that the driver will not connect to a predefined set of hosts,
+    /// e.g. during maintenance.
+    pub fn set_blacklist_filtering(
+        &mut self,
+        hosts: impl IntoIterator<Item = impl AsRef<str>>,
+    ) -> Result<&mut Self> {
+        let hosts = filter_list(hosts, check_host)?;
+        unsafe {
+            cass_cluster_set_blacklist_filtering_n(
+                self.inner,
+                hosts.as_ptr() as *const c_char,
+                hosts.len(),
+            );
+        }
+        Ok(self)
+    }
+
+    /// Sets/Appends whitelist data centers. The first call sets the whitelist
+    /// data centers and any subsequent calls appends additional data centers.
+    /// Passing no data centers will clear and disable the whitelist. White
+    /// space is striped from the data centers.
+    ///
+    /// This policy filters requests to all other policies, only allowing
+    /// requests to the hosts in the whitelisted data centers.
+    pub fn set_whitelist_dc_filtering(
+        &mut self,
+        dcs: impl IntoIterator<Item = impl AsRef<str>>,
+    ) -> Result<&mut Self> {
+        let dcs = filter_list(dcs, check_dc)?;
+        unsafe {
+            cass_cluster_set_whitelist_dc_filtering_n(
+                self.inner,
+                dcs.as_ptr() as *const c_char,
+                dcs.len(),
+            );
+        }
+        Ok(self)
+    }
+
+    /// Sets/Appends blacklist data centers. The first call sets the blacklist
+    /// data centers and any subsequent calls appends additional data centers.
+    /// Passing no data centers will clear and disable the blacklist. White
+    /// space is striped from the data centers.
+    ///
+    /// This policy filters requests to all other policies, only allowing
+    /// requests to the hosts not in the blacklisted data centers, e.g. to keep
+    /// an analytics data center free of application traffic.
+    pub fn set_blacklist_dc_filtering(
+        &mut self,
+        dcs: impl IntoIterator<Item = impl AsRef<str>>,
+    ) -> Result<&mut Self> {
+        let dcs = filter_list(dcs, check_dc)?;
+        unsafe {
+            cass_cluster_set_blacklist_dc_filtering_n(
+                self.inner,
+                dcs.as_ptr() as *const c_char,
+                dcs.len(),
+            );
+        }
+        Ok(self)
     }
 
     /// Enable/Disable Nagel's algorithm on connections.
@@ -619,7 +842,7 @@ impl Cluster {
     /// <b>Default:</b> true (disables Nagel's algorithm).
     pub fn set_tcp_nodelay(&mut self, enable: bool) -> &Self {
         unsafe {
-            cass_cluster_set_tcp_nodelay(self.0, if enable { cass_true } else { cass_false });
+            cass_cluster_set_tcp_nodelay(self.inner, if enable { cass_true } else { cass_false });
         }
         self
     }
@@ -631,7 +854,7 @@ impl Cluster {
     pub fn set_tcp_keepalive(&mut self, enable: bool, delay: Duration) -> &Self {
         unsafe {
             cass_cluster_set_tcp_keepalive(
-                self.0,
+                self.inner,
                 if enable { cass_true } else { cass_false },
                 delay.as_secs() as u32,
             );
@@ -646,7 +869,7 @@ impl Cluster {
     /// Default: server-side timestamp generator.
     pub fn set_timestamp_gen(&mut self, tsg: &TimestampGen) -> &mut Self {
         unsafe {
-            cass_cluster_set_timestamp_gen(self.0, TimestampGen::inner(tsg));
+            cass_cluster_set_timestamp_gen(self.inner, TimestampGen::inner(tsg));
             self
         }
     }
@@ -660,7 +883,7 @@ impl Cluster {
     /// Default: 30 seconds
     pub fn set_connection_heartbeat_interval(&mut self, hearbeat: Duration) -> &mut Self {
         unsafe {
-            cass_cluster_set_connection_heartbeat_interval(self.0, hearbeat.as_secs() as u32);
+            cass_cluster_set_connection_heartbeat_interval(self.inner, hearbeat.as_secs() as u32);
             self
         }
     }
@@ -672,7 +895,7 @@ impl Cluster {
     /// Default: 60 seconds
     pub fn set_connection_idle_timeout(&mut self, timeout: Duration) -> &mut Self {
         unsafe {
-            cass_cluster_set_connection_idle_timeout(self.0, timeout.as_secs() as u32);
+            cass_cluster_set_connection_idle_timeout(self.inner, timeout.as_secs() as u32);
             self
         }
     }
@@ -689,7 +912,7 @@ impl Cluster {
     /// will return an error.
     pub fn set_retry_policy(&mut self, retry_policy: RetryPolicy) -> &mut Self {
         unsafe {
-            cass_cluster_set_retry_policy(self.0, retry_policy.inner());
+            cass_cluster_set_retry_policy(self.inner, retry_policy.inner());
             self
         }
     }
@@ -704,8 +927,129 @@ impl Cluster {
     /// Default: true (enabled).
     pub fn set_use_schema(&mut self, enabled: bool) -> &Self {
         unsafe {
-            cass_cluster_set_use_schema(self.0, if enabled { cass_true } else { cass_false });
+            cass_cluster_set_use_schema(self.inner, if enabled { cass_true } else { cass_false });
         }
         self
     }
+
+    /// Returns a stream of host events: hosts of the cluster going up or
+    /// down, and hosts being added to or removed from the cluster.
+    ///
+    /// Events are only seen from sessions connected after this is called.
+    /// They are buffered until read, so the stream should be read
+    /// continually, or dropped.
+    pub fn host_events(&mut self) -> Result<HostEvents> {
+        Ok(self.host_listeners()?.stream())
+    }
+
+    /// Sets a callback which is called with each host event, as for
+    /// `host_events`. It is called on one of the driver's IO threads, so
+    /// should return quickly. A callback which panics is not called again.
+    pub fn on_host_event(
+        &mut self,
+        mut callback: impl FnMut(HostEvent) + Send + 'static,
+    ) -> Result<&mut Self> {
+        self.host_listeners()?.add(move |event| {
+            callback(*event);
+            true
+        });
+        Ok(self)
+    }
+
+    /// The listeners for host events, installing them with the driver if
+    /// this is the first. Each session connected from now on keeps them
+    /// alive, since the driver calls them from the session.
+    fn host_listeners(&mut self) -> Result<&HostListeners> {
+        let listeners = match self.host_listeners.take() {
+            Some(listeners) => listeners,
+            None => {
+                let listeners = HostListeners::new();
+                unsafe {
+                    cass_cluster_set_host_listener_callback(
+                        self.inner,
+                        Some(notify_host_listeners),
+                        listeners.data(),
+                    )
+                    .to_result(())?;
+                }
+                listeners
+            }
+        };
+        Ok(self.host_listeners.insert(listeners))
+    }
+}
+
+/// A check of an item of a host filter, returning the problem with it.
+pub(crate) type FilterCheck = fn(&str) -> ::std::result::Result<(), String>;
+
+/// Check each item of a filter, joining them into the comma-separated list
+/// the driver expects.
+fn filter_list(
+    items: impl IntoIterator<Item = impl AsRef<str>>,
+    check: FilterCheck,
+) -> Result<String> {
+    let mut list = String::new();
+    for item in items {
+        let item = item.as_ref().trim();
+        check(item).map_err(ErrorKind::InvalidConfig)?;
+        if !list.is_empty() {
+            list.push(',');
+        }
+        list.push_str(item);
+    }
+    Ok(list)
+}
+
+/// Check a host is an IP address or a valid hostname.
+pub(crate) fn check_host(host: &str) -> ::std::result::Result<(), String> {
+    if host.parse::<IpAddr>().is_ok() {
+        return Ok(());
+    }
+    let name = host.strip_suffix('.').unwrap_or(host);
+    let valid_label = |label: &str| {
+        !label.is_empty()
+            && label.len() <= 63
+            && !label.starts_with('-')
+            && !label.ends_with('-')
+            && label
+                .bytes()
+                .all(|b| b.is_ascii_alphanumeric() || b == b'-')
+    };
+    if name.is_empty() || name.len() > 253 || !name.split('.').all(valid_label) {
+        return Err(format!("{:?} is not an IP address or hostname", host));
+    }
+    Ok(())
+}
+
+/// Check a data center name can be passed in a filter.
+pub(crate) fn check_dc(dc: &str) -> ::std::result::Result<(), String> {
+    if dc.is_empty() || dc.contains(',') {
+        return Err(format!("{:?} is not a valid data center name", dc));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_host() {
+        for host in &["127.0.0.1", "::1", "localhost", "node-1.dc1.example.com."] {
+            assert_eq!(check_host(host), Ok(()), "{}", host);
+        }
+        for host in &["", "a,b", "-node", "node_1", "a..b", "10.0.0.1:9042"] {
+            assert!(check_host(host).is_err(), "{}", host);
+        }
+    }
+
+    #[test]
+    fn test_filter_list() {
+        assert_eq!(
+            filter_list(vec![" 10.0.0.1", "node1 "], check_host).unwrap(),
+            "10.0.0.1,node1"
+        );
+        assert_eq!(filter_list(Vec::<String>::new(), check_host).unwrap(), "");
+        assert!(filter_list(&["dc1", "dc,2"], check_dc).is_err());
+    }
 }
@@ -0,0 +1,1106 @@
+use crate::cassandra::cluster::{self, Cluster, CqlProtocol};
+use crate::cassandra::consistency::Consistency;
+use crate::cassandra::error::*;
+use crate::cassandra::policy::retry::RetryPolicy;
+use crate::cassandra::ssl::{Ssl, SslVerifyFlag};
+use crate::cassandra::time::TimestampGen;
+use crate::cassandra::uri;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use std::env;
+use std::fmt::Display;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
+
+// The driver's defaults, used to validate settings which are only partly given.
+const DEFAULT_CORE_CONNECTIONS_PER_HOST: u32 = 1;
+const DEFAULT_MAX_CONNECTIONS_PER_HOST: u32 = 2;
+const DEFAULT_WRITE_BYTES_HIGH_WATER_MARK: u32 = 64 * 1024;
+const DEFAULT_WRITE_BYTES_LOW_WATER_MARK: u32 = 32 * 1024;
+const DEFAULT_PENDING_REQUESTS_HIGH_WATER_MARK: u32 = 256;
+const DEFAULT_PENDING_REQUESTS_LOW_WATER_MARK: u32 = 128;
+
+/// The configuration of a `Cluster`, covering the settings of its `set_*`
+/// methods, for building a cluster with `Cluster::from_config`.
+///
+/// Settings which are `None` (or empty) are left at the driver's defaults.
+/// Times are given in milliseconds or seconds, as for the corresponding
+/// driver setting.
+///
+/// With the `serde` feature, this can be serialized and deserialized; the
+/// `toml` and `yaml` features add `from_toml_str` and `from_yaml_str`. It can
+/// always be read from environment variables with `from_env`.
+///
+/// ```
+/// # use cassandra_cpp::*;
+/// # fn test() -> Result<()> {
+/// let config = ClusterConfig {
+///     contact_points: vec!["127.0.0.1".to_string()],
+///     request_timeout_ms: Some(5000),
+///     ..ClusterConfig::default()
+/// };
+/// let cluster = Cluster::from_config(&config)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(default, deny_unknown_fields)
+)]
+pub struct ClusterConfig {
+    /// Contact points. Required unless a cloud secure connection bundle is used.
+    pub contact_points: Vec<String>,
+    /// The port to connect to.
+    pub port: Option<u16>,
+    /// The local address to bind when connecting.
+    pub local_address: Option<String>,
+    /// The CQL protocol version.
+    pub protocol_version: Option<CqlProtocol>,
+    /// The path of a cloud secure connection bundle, used instead of contact
+    /// points.
+    pub cloud_secure_connection_bundle: Option<PathBuf>,
+    /// TLS settings; TLS is enabled if present.
+    pub tls: Option<TlsConfig>,
+    /// Plain text authentication credentials.
+    pub credentials: Option<Credentials>,
+    /// The keyspace sessions connect to.
+    pub keyspace: Option<String>,
+    /// The default consistency of requests.
+    pub consistency: Option<Consistency>,
+    /// The default serial consistency of requests, `SERIAL` or `LOCAL_SERIAL`.
+    pub serial_consistency: Option<Consistency>,
+    /// The number of IO threads.
+    pub num_threads_io: Option<u32>,
+    /// The size of the queue of pending requests.
+    pub queue_size_io: Option<u32>,
+    /// The size of the queue of events.
+    pub queue_size_event: Option<u32>,
+    /// The number of connections to each host per IO thread.
+    pub core_connections_per_host: Option<u32>,
+    /// The maximum number of connections to each host per IO thread.
+    pub max_connections_per_host: Option<u32>,
+    /// The maximum number of connections created concurrently.
+    pub max_concurrent_creation: Option<u32>,
+    /// The number of concurrent requests on a connection before another is
+    /// created.
+    pub max_concurrent_requests_threshold: Option<u32>,
+    /// The maximum number of requests processed by an IO worker per flush.
+    pub max_requests_per_flush: Option<u32>,
+    /// The number of bytes outstanding on a connection above which writes are
+    /// disabled.
+    pub write_bytes_high_water_mark: Option<u32>,
+    /// The number of bytes outstanding on a connection below which writes are
+    /// resumed. Must be less than the high water mark.
+    pub write_bytes_low_water_mark: Option<u32>,
+    /// The number of requests queued for a connection pool above which writes
+    /// are disabled.
+    pub pending_requests_high_water_mark: Option<u32>,
+    /// The number of requests queued for a connection pool below which writes
+    /// are resumed. Must be less than the high water mark.
+    pub pending_requests_low_water_mark: Option<u32>,
+    /// The timeout for connecting to a node, in milliseconds.
+    pub connect_timeout_ms: Option<u64>,
+    /// The timeout for a response from a node, in milliseconds.
+    pub request_timeout_ms: Option<u64>,
+    /// The timeout for resolving a host name, in milliseconds.
+    pub resolve_timeout_ms: Option<u64>,
+    /// The reconnection policy.
+    pub reconnect: Option<ReconnectPolicy>,
+    /// The load balancing policy.
+    pub load_balancing: Option<LoadBalancing>,
+    /// Whether to use token-aware routing.
+    pub token_aware_routing: Option<bool>,
+    /// Whether token-aware routing shuffles replicas.
+    pub token_aware_routing_shuffle_replicas: Option<bool>,
+    /// Latency-aware routing settings; latency-aware routing is enabled if
+    /// present.
+    pub latency_aware_routing: Option<LatencyAwareRouting>,
+    /// Filters on the hosts to connect to, applied in order.
+    pub host_filters: Vec<HostFilter>,
+    /// Whether to disable Nagle's algorithm.
+    pub tcp_nodelay: Option<bool>,
+    /// The TCP keep-alive delay, in seconds; keep-alive is enabled if present.
+    pub tcp_keepalive_secs: Option<u64>,
+    /// The timestamp generator.
+    pub timestamp_gen: Option<TimestampGenKind>,
+    /// The interval between heartbeats on idle connections, in seconds.
+    pub connection_heartbeat_interval_secs: Option<u64>,
+    /// How long a connection may go without a heartbeat response, in seconds.
+    pub connection_idle_timeout_secs: Option<u64>,
+    /// The retry policy.
+    pub retry_policy: Option<RetryPolicyKind>,
+    /// Whether to log the decisions of the retry policy.
+    pub log_retries: Option<bool>,
+    /// Whether to retrieve and update schema metadata.
+    pub use_schema: Option<bool>,
+}
+
+/// TLS settings for a `ClusterConfig`. Certificates and keys are read from
+/// PEM files.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(default, deny_unknown_fields)
+)]
+pub struct TlsConfig {
+    /// Certificates trusted to verify the peer's certificate.
+    pub trusted_certs: Vec<PathBuf>,
+    /// The client certificate chain.
+    pub cert: Option<PathBuf>,
+    /// The client private key.
+    pub private_key: Option<PathBuf>,
+    /// The password of the client private key.
+    pub private_key_password: Option<String>,
+    /// How to verify the peer, if not the driver's default of `PEER_CERT`.
+    pub verify: Option<Vec<SslVerifyFlag>>,
+}
+
+/// Plain text authentication credentials.
+#[derive(Clone, Default, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(deny_unknown_fields)
+)]
+pub struct Credentials {
+    /// The user name.
+    pub username: String,
+    /// The password.
+    pub password: String,
+}
+
+/// The password is not shown.
+impl std::fmt::Debug for Credentials {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("Credentials")
+            .field("username", &self.username)
+            .finish()
+    }
+}
+
+/// A reconnection policy.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)
+)]
+pub enum ReconnectPolicy {
+    /// Wait a constant time between attempts.
+    Constant {
+        /// The time to wait, in milliseconds.
+        wait_ms: u32,
+    },
+    /// Wait exponentially longer between attempts, up to a maximum.
+    Exponential {
+        /// The first delay, in milliseconds.
+        base_delay_ms: u64,
+        /// The maximum delay, in milliseconds.
+        max_delay_ms: u64,
+    },
+}
+
+/// A load balancing policy.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)
+)]
+pub enum LoadBalancing {
+    /// Round-robin over all hosts.
+    RoundRobin,
+    /// Prefer hosts in the local data center.
+    DcAware {
+        /// The local data center.
+        local_dc: String,
+        /// The number of hosts used in each remote data center.
+        #[cfg_attr(feature = "serde", serde(default))]
+        used_hosts_per_remote_dc: u32,
+        /// Whether remote hosts may be used for `LOCAL_*` consistency levels.
+        #[cfg_attr(feature = "serde", serde(default))]
+        allow_remote_dcs_for_local_cl: bool,
+    },
+}
+
+/// Latency-aware routing settings. See
+/// `Cluster::set_latency_aware_routing_settings`.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(default, deny_unknown_fields)
+)]
+#[allow(missing_docs)]
+pub struct LatencyAwareRouting {
+    pub exclusion_threshold: f64,
+    pub scale_ms: u64,
+    pub retry_period_ms: u64,
+    pub update_rate_ms: u64,
+    pub min_measured: u64,
+}
+
+/// The driver's defaults.
+impl Default for LatencyAwareRouting {
+    fn default() -> Self {
+        LatencyAwareRouting {
+            exclusion_threshold: 2.0,
+            scale_ms: 100,
+            retry_period_ms: 10_000,
+            update_rate_ms: 100,
+            min_measured: 50,
+        }
+    }
+}
+
+/// A filter on the hosts the driver connects to. See
+/// `Cluster::set_whitelist_filtering` and related methods.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)
+)]
+pub enum HostFilter {
+    /// Connect only to these hosts.
+    WhitelistHosts {
+        /// IP addresses or hostnames.
+        hosts: Vec<String>,
+    },
+    /// Never connect to these hosts.
+    BlacklistHosts {
+        /// IP addresses or hostnames.
+        hosts: Vec<String>,
+    },
+    /// Connect only to hosts in these data centers.
+    WhitelistDcs {
+        /// Data center names.
+        dcs: Vec<String>,
+    },
+    /// Never connect to hosts in these data centers.
+    BlacklistDcs {
+        /// Data center names.
+        dcs: Vec<String>,
+    },
+}
+
+impl HostFilter {
+    /// The name of the filter, as in configuration files.
+    fn name(&self) -> &'static str {
+        match self {
+            HostFilter::WhitelistHosts { .. } => "whitelist_hosts",
+            HostFilter::BlacklistHosts { .. } => "blacklist_hosts",
+            HostFilter::WhitelistDcs { .. } => "whitelist_dcs",
+            HostFilter::BlacklistDcs { .. } => "blacklist_dcs",
+        }
+    }
+}
+
+/// A timestamp generator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(rename_all = "snake_case")
+)]
+#[allow(missing_docs)]
+pub enum TimestampGenKind {
+    ServerSide,
+    Monotonic,
+}
+
+/// A retry policy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(rename_all = "snake_case")
+)]
+#[allow(missing_docs)]
+pub enum RetryPolicyKind {
+    Default,
+    DowngradingConsistency,
+    Fallthrough,
+}
+
+impl FromStr for TimestampGenKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> ::std::result::Result<Self, Self::Err> {
+        match s {
+            "server_side" => Ok(TimestampGenKind::ServerSide),
+            "monotonic" => Ok(TimestampGenKind::Monotonic),
+            _ => Err(format!("Unrecognized timestamp generator: {}", s)),
+        }
+    }
+}
+
+impl FromStr for RetryPolicyKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> ::std::result::Result<Self, Self::Err> {
+        match s {
+            "default" => Ok(RetryPolicyKind::Default),
+            "downgrading_consistency" => Ok(RetryPolicyKind::DowngradingConsistency),
+            "fallthrough" => Ok(RetryPolicyKind::Fallthrough),
+            _ => Err(format!("Unrecognized retry policy: {}", s)),
+        }
+    }
+}
+
+fn invalid(reason: impl Into<String>) -> Error {
+    ErrorKind::InvalidConfig(reason.into()).into()
+}
+
+/// Reads settings from environment variables named by a prefix.
+struct Env<'a> {
+    prefix: &'a str,
+}
+
+impl Env<'_> {
+    fn var(&self, name: &str) -> Result<Option<String>> {
+        let key = format!("{}_{}", self.prefix, name);
+        match env::var(&key) {
+            Ok(value) => Ok(Some(value)),
+            Err(env::VarError::NotPresent) => Ok(None),
+            Err(env::VarError::NotUnicode(_)) => {
+                Err(invalid(format!("{} is not valid UTF-8", key)))
+            }
+        }
+    }
+
+    fn parse<T: FromStr>(&self, name: &str) -> Result<Option<T>>
+    where
+        T::Err: Display,
+    {
+        match self.var(name)? {
+            Some(value) => value
+                .trim()
+                .parse()
+                .map(Some)
+                .map_err(|e| invalid(format!("{}_{}: {}", self.prefix, name, e))),
+            None => Ok(None),
+        }
+    }
+
+    /// A comma-separated list, empty if the variable is not set.
+    fn list<T: FromStr>(&self, name: &str) -> Result<Vec<T>>
+    where
+        T::Err: Display,
+    {
+        match self.var(name)? {
+            Some(value) => value
+                .split(',')
+                .map(str::trim)
+                .filter(|item| !item.is_empty())
+                .map(|item| {
+                    item.parse()
+                        .map_err(|e| invalid(format!("{}_{}: {}", self.prefix, name, e)))
+                })
+                .collect(),
+            None => Ok(vec![]),
+        }
+    }
+}
+
+impl ClusterConfig {
+    /// Read the configuration from environment variables, each named by
+    /// `prefix`, an underscore, and the field name in upper case, e.g.
+    /// `CASSANDRA_CONTACT_POINTS` or `CASSANDRA_REQUEST_TIMEOUT_MS` for the
+    /// prefix `CASSANDRA`. Lists are comma-separated.
+    ///
+    /// Nested settings are flattened:
+    ///
+    /// * `TLS_TRUSTED_CERTS`, `TLS_CERT`, `TLS_PRIVATE_KEY`,
+    ///   `TLS_PRIVATE_KEY_PASSWORD` and `TLS_VERIFY` (e.g. `PEER_IDENTITY`);
+    ///   TLS is enabled if any is set.
+    /// * `USERNAME` and `PASSWORD`.
+    /// * `CONSISTENCY` and `SERIAL_CONSISTENCY`, by name, e.g. `LOCAL_QUORUM`.
+    /// * `RECONNECT_WAIT_MS` for a constant reconnection policy, or
+    ///   `RECONNECT_BASE_DELAY_MS` and `RECONNECT_MAX_DELAY_MS` for an
+    ///   exponential one.
+    /// * `LOAD_BALANCING` (`round_robin` or `dc_aware`), `LOCAL_DC`,
+    ///   `USED_HOSTS_PER_REMOTE_DC` and `ALLOW_REMOTE_DCS_FOR_LOCAL_CL`.
+    /// * `LATENCY_AWARE_ROUTING` (`true` to enable), and
+    ///   `LATENCY_AWARE_ROUTING_` followed by the name of a setting to change
+    ///   it from its default.
+    /// * `WHITELIST_HOSTS`, `BLACKLIST_HOSTS`, `WHITELIST_DCS` and
+    ///   `BLACKLIST_DCS`, each adding a host filter if set.
+    pub fn from_env(prefix: &str) -> Result<Self> {
+        let env = Env { prefix };
+
+        let tls = TlsConfig {
+            trusted_certs: env.list("TLS_TRUSTED_CERTS")?,
+            cert: env.parse("TLS_CERT")?,
+            private_key: env.parse("TLS_PRIVATE_KEY")?,
+            private_key_password: env.var("TLS_PRIVATE_KEY_PASSWORD")?,
+            verify: match env.var("TLS_VERIFY")? {
+                Some(_) => Some(env.list("TLS_VERIFY")?),
+                None => None,
+            },
+        };
+        let credentials = match (env.var("USERNAME")?, env.var("PASSWORD")?) {
+            (None, None) => None,
+            (username, password) => Some(Credentials {
+                username: username.unwrap_or_default(),
+                password: password.unwrap_or_default(),
+            }),
+        };
+        let reconnect = match (
+            env.parse("RECONNECT_WAIT_MS")?,
+            env.parse("RECONNECT_BASE_DELAY_MS")?,
+            env.parse("RECONNECT_MAX_DELAY_MS")?,
+        ) {
+            (None, None, None) => None,
+            (Some(wait_ms), None, None) => Some(ReconnectPolicy::Constant { wait_ms }),
+            (None, Some(base_delay_ms), Some(max_delay_ms)) => Some(ReconnectPolicy::Exponential {
+                base_delay_ms,
+                max_delay_ms,
+            }),
+            _ => {
+                return Err(invalid(format!(
+                    "Set either {0}_RECONNECT_WAIT_MS, or both {0}_RECONNECT_BASE_DELAY_MS and \
+                     {0}_RECONNECT_MAX_DELAY_MS",
+                    prefix
+                )))
+            }
+        };
+        let load_balancing = match env.var("LOAD_BALANCING")?.as_deref().map(str::trim) {
+            None => None,
+            Some("round_robin") => Some(LoadBalancing::RoundRobin),
+            Some("dc_aware") => Some(LoadBalancing::DcAware {
+                local_dc: env.var("LOCAL_DC")?.unwrap_or_default(),
+                used_hosts_per_remote_dc: env.parse("USED_HOSTS_PER_REMOTE_DC")?.unwrap_or(0),
+                allow_remote_dcs_for_local_cl: env
+                    .parse("ALLOW_REMOTE_DCS_FOR_LOCAL_CL")?
+                    .unwrap_or(false),
+            }),
+            Some(other) => {
+                return Err(invalid(format!(
+                    "{}_LOAD_BALANCING: Unrecognized load balancing policy: {}",
+                    prefix, other
+                )))
+            }
+        };
+        let latency_aware_routing = if env.parse("LATENCY_AWARE_ROUTING")?.unwrap_or(false) {
+            let defaults = LatencyAwareRouting::default();
+            Some(LatencyAwareRouting {
+                exclusion_threshold: env
+                    .parse("LATENCY_AWARE_ROUTING_EXCLUSION_THRESHOLD")?
+                    .unwrap_or(defaults.exclusion_threshold),
+                scale_ms: env
+                    .parse("LATENCY_AWARE_ROUTING_SCALE_MS")?
+                    .unwrap_or(defaults.scale_ms),
+                retry_period_ms: env
+                    .parse("LATENCY_AWARE_ROUTING_RETRY_PERIOD_MS")?
+                    .unwrap_or(defaults.retry_period_ms),
+                update_rate_ms: env
+                    .parse("LATENCY_AWARE_ROUTING_UPDATE_RATE_MS")?
+                    .unwrap_or(defaults.update_rate_ms),
+                min_measured: env
+                    .parse("LATENCY_AWARE_ROUTING_MIN_MEASURED")?
+                    .unwrap_or(defaults.min_measured),
+            })
+        } else {
+            None
+        };
+
+        let mut host_filters = vec![];
+        let hosts = env.list("WHITELIST_HOSTS")?;
+        if !hosts.is_empty() {
+            host_filters.push(HostFilter::WhitelistHosts { hosts });
+        }
+        let hosts = env.list("BLACKLIST_HOSTS")?;
+        if !hosts.is_empty() {
+            host_filters.push(HostFilter::BlacklistHosts { hosts });
+        }
+        let dcs = env.list("WHITELIST_DCS")?;
+        if !dcs.is_empty() {
+            host_filters.push(HostFilter::WhitelistDcs { dcs });
+        }
+        let dcs = env.list("BLACKLIST_DCS")?;
+        if !dcs.is_empty() {
+            host_filters.push(HostFilter::BlacklistDcs { dcs });
+        }
+
+        Ok(ClusterConfig {
+            contact_points: env.list("CONTACT_POINTS")?,
+            port: env.parse("PORT")?,
+            local_address: env.var("LOCAL_ADDRESS")?,
+            protocol_version: env.parse("PROTOCOL_VERSION")?,
+            cloud_secure_connection_bundle: env.parse("CLOUD_SECURE_CONNECTION_BUNDLE")?,
+            tls: if tls == TlsConfig::default() {
+                None
+            } else {
+                Some(tls)
+            },
+            credentials,
+            keyspace: env.var("KEYSPACE")?,
+            consistency: env.parse("CONSISTENCY")?,
+            serial_consistency: env.parse("SERIAL_CONSISTENCY")?,
+            num_threads_io: env.parse("NUM_THREADS_IO")?,
+            queue_size_io: env.parse("QUEUE_SIZE_IO")?,
+            queue_size_event: env.parse("QUEUE_SIZE_EVENT")?,
+            core_connections_per_host: env.parse("CORE_CONNECTIONS_PER_HOST")?,
+            max_connections_per_host: env.parse("MAX_CONNECTIONS_PER_HOST")?,
+            max_concurrent_creation: env.parse("MAX_CONCURRENT_CREATION")?,
+            max_concurrent_requests_threshold: env.parse("MAX_CONCURRENT_REQUESTS_THRESHOLD")?,
+            max_requests_per_flush: env.parse("MAX_REQUESTS_PER_FLUSH")?,
+            write_bytes_high_water_mark: env.parse("WRITE_BYTES_HIGH_WATER_MARK")?,
+            write_bytes_low_water_mark: env.parse("WRITE_BYTES_LOW_WATER_MARK")?,
+            pending_requests_high_water_mark: env.parse("PENDING_REQUESTS_HIGH_WATER_MARK")?,
+            pending_requests_low_water_mark: env.parse("PENDING_REQUESTS_LOW_WATER_MARK")?,
+            connect_timeout_ms: env.parse("CONNECT_TIMEOUT_MS")?,
+            request_timeout_ms: env.parse("REQUEST_TIMEOUT_MS")?,
+            resolve_timeout_ms: env.parse("RESOLVE_TIMEOUT_MS")?,
+            reconnect,
+            load_balancing,
+            token_aware_routing: env.parse("TOKEN_AWARE_ROUTING")?,
+            token_aware_routing_shuffle_replicas: env
+                .parse("TOKEN_AWARE_ROUTING_SHUFFLE_REPLICAS")?,
+            latency_aware_routing,
+            host_filters,
+            tcp_nodelay: env.parse("TCP_NODELAY")?,
+            tcp_keepalive_secs: env.parse("TCP_KEEPALIVE_SECS")?,
+            timestamp_gen: env.parse("TIMESTAMP_GEN")?,
+            connection_heartbeat_interval_secs: env.parse("CONNECTION_HEARTBEAT_INTERVAL_SECS")?,
+            connection_idle_timeout_secs: env.parse("CONNECTION_IDLE_TIMEOUT_SECS")?,
+            retry_policy: env.parse("RETRY_POLICY")?,
+            log_retries: env.parse("LOG_RETRIES")?,
+            use_schema: env.parse("USE_SCHEMA")?,
+        })
+    }
+
+    /// Parse the configuration from a connection URI. See `Cluster::from_uri`.
+    pub fn from_uri(uri: &str) -> Result<Self> {
+        uri::parse(uri)
+    }
+
+    /// Parse the configuration from TOML.
+    #[cfg(feature = "toml")]
+    pub fn from_toml_str(s: &str) -> Result<Self> {
+        toml::from_str(s).map_err(|e| invalid(e.to_string()))
+    }
+
+    /// Parse the configuration from YAML.
+    #[cfg(feature = "yaml")]
+    pub fn from_yaml_str(s: &str) -> Result<Self> {
+        serde_yaml::from_str(s).map_err(|e| invalid(e.to_string()))
+    }
+
+    /// Check the settings are consistent, reporting every problem found.
+    pub fn validate(&self) -> Result<()> {
+        let mut problems = vec![];
+
+        match (
+            self.contact_points.is_empty(),
+            self.cloud_secure_connection_bundle.is_some(),
+        ) {
+            (true, false) => {
+                problems.push("contact_points or cloud_secure_connection_bundle is required".into())
+            }
+            (false, true) => problems.push(
+                "contact_points and cloud_secure_connection_bundle are mutually exclusive".into(),
+            ),
+            _ => {}
+        }
+        for host in &self.contact_points {
+            if let Err(e) = cluster::check_host(host.trim()) {
+                problems.push(format!("contact_points: {}", e));
+            }
+        }
+        for filter in &self.host_filters {
+            let (items, check): (_, cluster::FilterCheck) = match filter {
+                HostFilter::WhitelistHosts { hosts } | HostFilter::BlacklistHosts { hosts } => {
+                    (hosts, cluster::check_host)
+                }
+                HostFilter::WhitelistDcs { dcs } | HostFilter::BlacklistDcs { dcs } => {
+                    (dcs, cluster::check_dc)
+                }
+            };
+            // An empty filter would disable any filter of the same kind before it.
+            if items.is_empty() {
+                problems.push(format!("host filter {} is empty", filter.name()));
+            }
+            for item in items {
+                if let Err(e) = check(item.trim()) {
+                    problems.push(format!("host filter {}: {}", filter.name(), e));
+                }
+            }
+        }
+
+        let mut check_order = |low_name: &str, low: u32, high_name: &str, high: u32| {
+            if low >= high {
+                problems.push(format!(
+                    "{} ({}) must be less than {} ({})",
+                    low_name, low, high_name, high
+                ));
+            }
+        };
+        check_order(
+            "write_bytes_low_water_mark",
+            self.write_bytes_low_water_mark
+                .unwrap_or(DEFAULT_WRITE_BYTES_LOW_WATER_MARK),
+            "write_bytes_high_water_mark",
+            self.write_bytes_high_water_mark
+                .unwrap_or(DEFAULT_WRITE_BYTES_HIGH_WATER_MARK),
+        );
+        check_order(
+            "pending_requests_low_water_mark",
+            self.pending_requests_low_water_mark
+                .unwrap_or(DEFAULT_PENDING_REQUESTS_LOW_WATER_MARK),
+            "pending_requests_high_water_mark",
+            self.pending_requests_high_water_mark
+                .unwrap_or(DEFAULT_PENDING_REQUESTS_HIGH_WATER_MARK),
+        );
+
+        let core = self
+            .core_connections_per_host
+            .unwrap_or(DEFAULT_CORE_CONNECTIONS_PER_HOST);
+        let max = self
+            .max_connections_per_host
+            .unwrap_or(DEFAULT_MAX_CONNECTIONS_PER_HOST);
+        if core > max {
+            problems.push(format!(
+                "core_connections_per_host ({}) must not exceed max_connections_per_host ({})",
+                core, max
+            ));
+        }
+        if let Some(ReconnectPolicy::Exponential {
+            base_delay_ms,
+            max_delay_ms,
+        }) = self.reconnect
+        {
+            if base_delay_ms > max_delay_ms {
+                problems.push(format!(
+                    "reconnect base_delay_ms ({}) must not exceed max_delay_ms ({})",
+                    base_delay_ms, max_delay_ms
+                ));
+            }
+        }
+        if let Some(LoadBalancing::DcAware { ref local_dc, .. }) = self.load_balancing {
+            if local_dc.is_empty() {
+                problems.push("dc_aware load balancing requires local_dc".into());
+            }
+        }
+        if let Some(ref latency) = self.latency_aware_routing {
+            if latency.exclusion_threshold < 1.0 {
+                problems.push(format!(
+                    "latency_aware_routing exclusion_threshold ({}) must be at least 1.0",
+                    latency.exclusion_threshold
+                ));
+            }
+        }
+        if let Some(serial_consistency) = self.serial_consistency {
+            if serial_consistency != Consistency::SERIAL
+                && serial_consistency != Consistency::LOCAL_SERIAL
+            {
+                problems.push(format!(
+                    "serial_consistency ({}) must be SERIAL or LOCAL_SERIAL",
+                    serial_consistency
+                ));
+            }
+        }
+        if let Some(ref credentials) = self.credentials {
+            if credentials.username.is_empty() {
+                problems.push("credentials require a username".into());
+            }
+        }
+        if let Some(ref tls) = self.tls {
+            if tls.cert.is_some() != tls.private_key.is_some() {
+                problems.push("tls cert and private_key must be given together".into());
+            }
+            if tls.private_key_password.is_some() && tls.private_key.is_none() {
+                problems.push("tls private_key_password requires private_key".into());
+            }
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(invalid(problems.join("; ")))
+        }
+    }
+
+    /// Apply the settings to a new cluster. The configuration must be valid.
+    pub(crate) fn apply(&self, cluster: &mut Cluster) -> Result<()> {
+        if !self.contact_points.is_empty() {
+            cluster.set_contact_points(&self.contact_points.join(","))?;
+        }
+        if let Some(port) = self.port {
+            cluster.set_port(port)?;
+        }
+        if let Some(ref local_address) = self.local_address {
+            cluster.set_local_address(local_address)?;
+        }
+        if let Some(protocol_version) = self.protocol_version {
+            cluster.set_protocol_version(protocol_version)?;
+        }
+        if let Some(ref bundle) = self.cloud_secure_connection_bundle {
+            let path = bundle
+                .to_str()
+                .ok_or_else(|| invalid("cloud_secure_connection_bundle is not valid UTF-8"))?;
+            cluster.set_cloud_secure_connection_bundle(path)?;
+        }
+        if let Some(ref tls) = self.tls {
+            cluster.set_ssl(tls.build()?);
+        }
+        if let Some(ref credentials) = self.credentials {
+            cluster.set_credentials(&credentials.username, &credentials.password)?;
+        }
+        if let Some(ref keyspace) = self.keyspace {
+            cluster.set_keyspace(keyspace);
+        }
+        if let Some(consistency) = self.consistency {
+            cluster.set_consistency(consistency)?;
+        }
+        if let Some(serial_consistency) = self.serial_consistency {
+            cluster.set_serial_consistency(serial_consistency)?;
+        }
+
+        macro_rules! set {
+            ($($field:ident => $setter:ident),* $(,)?) => {
+                $(
+                    if let Some(value) = self.$field {
+                        cluster.$setter(value)?;
+                    }
+                )*
+            };
+        }
+        set!(
+            num_threads_io => set_num_threads_io,
+            queue_size_io => set_queue_size_io,
+            queue_size_event => set_queue_size_event,
+            max_connections_per_host => set_max_connections_per_host,
+            core_connections_per_host => set_core_connections_per_host,
+            max_concurrent_creation => set_max_concurrent_creation,
+            max_concurrent_requests_threshold => set_max_concurrent_requests_threshold,
+            max_requests_per_flush => set_max_requests_per_flush,
+            write_bytes_high_water_mark => set_write_bytes_high_water_mark,
+            write_bytes_low_water_mark => set_write_bytes_low_water_mark,
+            pending_requests_high_water_mark => set_pending_requests_high_water_mark,
+            pending_requests_low_water_mark => set_pending_requests_low_water_mark,
+        );
+
+        if let Some(ms) = self.connect_timeout_ms {
+            cluster.set_connect_timeout(Duration::from_millis(ms));
+        }
+        if let Some(ms) = self.request_timeout_ms {
+            cluster.set_request_timeout(Duration::from_millis(ms));
+        }
+        if let Some(ms) = self.resolve_timeout_ms {
+            cluster.set_resolve_timeout(Duration::from_millis(ms));
+        }
+        match self.reconnect {
+            Some(ReconnectPolicy::Constant { wait_ms }) => {
+                cluster.set_reconnect_wait_time(wait_ms);
+            }
+            Some(ReconnectPolicy::Exponential {
+                base_delay_ms,
+                max_delay_ms,
+            }) => {
+                cluster.set_exponential_reconnect(
+                    Duration::from_millis(base_delay_ms),
+                    Duration::from_millis(max_delay_ms),
+                );
+            }
+            None => {}
+        }
+        match self.load_balancing {
+            Some(LoadBalancing::RoundRobin) => {
+                cluster.set_load_balance_round_robin();
+            }
+            Some(LoadBalancing::DcAware {
+                ref local_dc,
+                used_hosts_per_remote_dc,
+                allow_remote_dcs_for_local_cl,
+            }) => {
+                // The type parameter is unused, so any type will do.
+                cluster.set_load_balance_dc_aware::<()>(
+                    local_dc,
+                    used_hosts_per_remote_dc,
+                    allow_remote_dcs_for_local_cl,
+                )?;
+            }
+            None => {}
+        }
+        if let Some(enabled) = self.token_aware_routing {
+            cluster.set_token_aware_routing(enabled);
+        }
+        if let Some(enabled) = self.token_aware_routing_shuffle_replicas {
+            cluster.set_token_aware_routing_shuffle_replicas(enabled);
+        }
+        if let Some(ref latency) = self.latency_aware_routing {
+            cluster.set_latency_aware_routing(true);
+            cluster.set_latency_aware_routing_settings(
+                latency.exclusion_threshold,
+                Duration::from_millis(latency.scale_ms),
+                Duration::from_millis(latency.retry_period_ms),
+                Duration::from_millis(latency.update_rate_ms),
+                latency.min_measured,
+            );
+        }
+        for filter in &self.host_filters {
+            match filter {
+                HostFilter::WhitelistHosts { hosts } => cluster.set_whitelist_filtering(hosts)?,
+                HostFilter::BlacklistHosts { hosts } => cluster.set_blacklist_filtering(hosts)?,
+                HostFilter::WhitelistDcs { dcs } => cluster.set_whitelist_dc_filtering(dcs)?,
+                HostFilter::BlacklistDcs { dcs } => cluster.set_blacklist_dc_filtering(dcs)?,
+            };
+        }
+        if let Some(enabled) = self.tcp_nodelay {
+            cluster.set_tcp_nodelay(enabled);
+        }
+        if let Some(secs) = self.tcp_keepalive_secs {
+            cluster.set_tcp_keepalive(true, Duration::from_secs(secs));
+        }
+        match self.timestamp_gen {
+            Some(TimestampGenKind::ServerSide) => {
+                cluster.set_timestamp_gen(&TimestampGen::gen_server_side_new());
+            }
+            Some(TimestampGenKind::Monotonic) => {
+                cluster.set_timestamp_gen(&TimestampGen::gen_monotonic_new());
+            }
+            None => {}
+        }
+        if let Some(secs) = self.connection_heartbeat_interval_secs {
+            cluster.set_connection_heartbeat_interval(Duration::from_secs(secs));
+        }
+        if let Some(secs) = self.connection_idle_timeout_secs {
+            cluster.set_connection_idle_timeout(Duration::from_secs(secs));
+        }
+        let log_retries = self.log_retries.unwrap_or(false);
+        let retry_policy = match self.retry_policy {
+            Some(RetryPolicyKind::Default) => Some(RetryPolicy::default_new()),
+            Some(RetryPolicyKind::DowngradingConsistency) => {
+                Some(RetryPolicy::downgrading_consistency_new())
+            }
+            Some(RetryPolicyKind::Fallthrough) => Some(RetryPolicy::fallthrough_new()),
+            None if log_retries => Some(RetryPolicy::default_new()),
+            None => None,
+        };
+        if let Some(retry_policy) = retry_policy {
+            cluster.set_retry_policy(if log_retries {
+                RetryPolicy::logging_new(retry_policy)
+            } else {
+                retry_policy
+            });
+        }
+        if let Some(enabled) = self.use_schema {
+            cluster.set_use_schema(enabled);
+        }
+        Ok(())
+    }
+}
+
+fn read_pem(path: &Path) -> Result<String> {
+    fs::read_to_string(path)
+        .map_err(|e| invalid(format!("Could not read {}: {}", path.display(), e)))
+}
+
+impl TlsConfig {
+    /// Build the SSL context, reading the certificates and key.
+    fn build(&self) -> Result<Ssl> {
+        let mut ssl = Ssl::default();
+        for path in &self.trusted_certs {
+            ssl.add_trusted_cert(read_pem(path)?)?;
+        }
+        if let Some(ref path) = self.cert {
+            ssl.set_cert(&read_pem(path)?)?;
+        }
+        if let Some(ref path) = self.private_key {
+            let password = self.private_key_password.as_deref().unwrap_or("");
+            ssl.set_private_key(&read_pem(path)?, password)?;
+        }
+        if let Some(ref verify) = self.verify {
+            ssl.set_verify_flags(verify);
+        }
+        Ok(ssl)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> ClusterConfig {
+        ClusterConfig {
+            contact_points: vec!["127.0.0.1".to_string()],
+            ..ClusterConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(config().validate().is_ok());
+        assert!(ClusterConfig::default().validate().is_err());
+
+        // Water marks are checked against the driver's defaults.
+        let c = ClusterConfig {
+            write_bytes_low_water_mark: Some(64 * 1024),
+            ..config()
+        };
+        assert!(c.validate().is_err());
+        let c = ClusterConfig {
+            write_bytes_low_water_mark: Some(64 * 1024),
+            write_bytes_high_water_mark: Some(128 * 1024),
+            ..config()
+        };
+        assert!(c.validate().is_ok());
+
+        let c = ClusterConfig {
+            pending_requests_low_water_mark: Some(10),
+            pending_requests_high_water_mark: Some(10),
+            core_connections_per_host: Some(4),
+            ..config()
+        };
+        let message = c.validate().unwrap_err().to_string();
+        assert!(
+            message.contains("pending_requests_low_water_mark (10)"),
+            "{}",
+            message
+        );
+        assert!(
+            message.contains("core_connections_per_host (4)"),
+            "{}",
+            message
+        );
+
+        let c = ClusterConfig {
+            host_filters: vec![
+                HostFilter::BlacklistHosts {
+                    hosts: vec!["10.0.0.1".to_string(), "bad host".to_string()],
+                },
+                HostFilter::WhitelistDcs { dcs: vec![] },
+            ],
+            contact_points: vec!["10.0.0.1".to_string(), "node_1".to_string()],
+            ..config()
+        };
+        let message = c.validate().unwrap_err().to_string();
+        assert!(message.contains("\"bad host\""), "{}", message);
+        assert!(
+            message.contains("contact_points: \"node_1\""),
+            "{}",
+            message
+        );
+        assert!(message.contains("whitelist_dcs is empty"), "{}", message);
+    }
+
+    #[test]
+    fn test_from_env() {
+        let vars = [
+            ("CASS_CONFIG_TEST_CONTACT_POINTS", "10.0.0.1, 10.0.0.2"),
+            ("CASS_CONFIG_TEST_REQUEST_TIMEOUT_MS", "2500"),
+            ("CASS_CONFIG_TEST_LOAD_BALANCING", "dc_aware"),
+            ("CASS_CONFIG_TEST_LOCAL_DC", "dc1"),
+            ("CASS_CONFIG_TEST_TLS_VERIFY", "PEER_CERT,PEER_IDENTITY"),
+            ("CASS_CONFIG_TEST_RETRY_POLICY", "fallthrough"),
+            ("CASS_CONFIG_TEST_RECONNECT_WAIT_MS", "500"),
+            ("CASS_CONFIG_TEST_BLACKLIST_DCS", "analytics"),
+        ];
+        for (key, value) in &vars {
+            env::set_var(key, value);
+        }
+        let c = ClusterConfig::from_env("CASS_CONFIG_TEST").unwrap();
+        assert_eq!(c.contact_points, vec!["10.0.0.1", "10.0.0.2"]);
+        assert_eq!(c.request_timeout_ms, Some(2500));
+        assert_eq!(
+            c.load_balancing,
+            Some(LoadBalancing::DcAware {
+                local_dc: "dc1".to_string(),
+                used_hosts_per_remote_dc: 0,
+                allow_remote_dcs_for_local_cl: false,
+            })
+        );
+        assert_eq!(
+            c.tls.unwrap().verify,
+            Some(vec![SslVerifyFlag::PEER_CERT, SslVerifyFlag::PEER_IDENTITY])
+        );
+        assert_eq!(c.retry_policy, Some(RetryPolicyKind::Fallthrough));
+        assert_eq!(
+            c.reconnect,
+            Some(ReconnectPolicy::Constant { wait_ms: 500 })
+        );
+        assert_eq!(c.credentials, None);
+        assert_eq!(
+            c.host_filters,
+            vec![HostFilter::BlacklistDcs {
+                dcs: vec!["analytics".to_string()]
+            }]
+        );
+
+        env::set_var("CASS_CONFIG_TEST_PORT", "not a port");
+        assert!(ClusterConfig::from_env("CASS_CONFIG_TEST").is_err());
+        env::remove_var("CASS_CONFIG_TEST_PORT");
+        for (key, _) in &vars {
+            env::remove_var(key);
+        }
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn test_from_toml() {
+        let c = ClusterConfig::from_toml_str(
+            r#"
+            contact_points = ["127.0.0.1"]
+            port = 9042
+            write_bytes_high_water_mark = 131072
+
+            [credentials]
+            username = "cassandra"
+            password = "secret"
+
+            [load_balancing]
+            type = "round_robin"
+
+            [reconnect]
+            type = "exponential"
+            base_delay_ms = 1000
+            max_delay_ms = 60000
+
+            [tls]
+            verify = ["PEER_IDENTITY"]
+
+            [[host_filters]]
+            type = "blacklist_hosts"
+            hosts = ["10.0.0.9"]
+            "#,
+        )
+        .unwrap();
+        assert_eq!(c.port, Some(9042));
+        assert_eq!(c.load_balancing, Some(LoadBalancing::RoundRobin));
+        assert_eq!(c.credentials.unwrap().username, "cassandra");
+        assert_eq!(
+            c.host_filters,
+            vec![HostFilter::BlacklistHosts {
+                hosts: vec!["10.0.0.9".to_string()]
+            }]
+        );
+        assert!(ClusterConfig::from_toml_str("unknown_setting = 1").is_err());
+    }
+
+    #[cfg(feature = "yaml")]
+    #[test]
+    fn test_from_yaml() {
+        let c = ClusterConfig::from_yaml_str(
+            "
+contact_points: [127.0.0.1]
+request_timeout_ms: 5000
+latency_aware_routing:
+  exclusion_threshold: 3.0
+timestamp_gen: monotonic
+",
+        )
+        .unwrap();
+        assert_eq!(c.request_timeout_ms, Some(5000));
+        assert_eq!(c.latency_aware_routing.unwrap().scale_ms, 100);
+        assert_eq!(c.timestamp_gen, Some(TimestampGenKind::Monotonic));
+    }
+}
@@ -3,6 +3,7 @@ use crate::cassandra::data_type::DataType;
 use crate::cassandra::error::*;
 use crate::cassandra::inet::Inet;
 use crate::cassandra::tuple::Tuple;
+use crate::cassandra::type_check::{ElementTypes, Provided};
 use crate::cassandra::user_type::UserType;
 use crate::cassandra::util::{Protected, ProtectedInner};
 use crate::cassandra::uuid::Uuid;
@@ -122,7 +123,7 @@ pub trait CassCollection: Sized {
 
 /// A cassandra list collection
 #[derive(Debug)]
-pub struct List(*mut _CassCollection);
+pub struct List(*mut _CassCollection, ElementTypes);
 
 // The underlying C type has no thread-local state, and forbids only concurrent
 // mutation/free: https://datastax.github.io/cpp-driver/topics/#thread-safety
@@ -140,7 +141,7 @@ impl Protected<*mut _CassCollection> for List {
         if inner.is_null() {
             panic!("Unexpected null pointer")
         };
-        List(inner)
+        List(inner, ElementTypes::default())
     }
 }
 
@@ -155,7 +156,7 @@ impl Protected<*mut _CassCollection> for Map {
         if inner.is_null() {
             panic!("Unexpected null pointer")
         };
-        Map(inner)
+        Map(inner, ElementTypes::default())
     }
 }
 
@@ -170,7 +171,7 @@ impl Protected<*mut _CassCollection> for Set {
         if inner.is_null() {
             panic!("Unexpected null pointer")
         };
-        Set(inner)
+        Set(inner, ElementTypes::default())
     }
 }
 
@@ -180,6 +181,20 @@ impl Drop for List {
     }
 }
 
+impl List {
+    /// Records the type of an appended element, which the driver does not check
+    /// if the list was created without a data type.
+    fn record(&mut self, element: Provided) -> &mut Self {
+        self.1.record(1, element);
+        self
+    }
+
+    /// What is known about the type of the list, for checking it when bound.
+    pub(crate) fn provided(&self) -> Provided {
+        Provided::from_data_type(&self.data_type(), &self.1)
+    }
+}
+
 impl CassCollection for List {
     type Value = _CassCollection;
 
@@ -190,10 +205,10 @@ impl CassCollection for List {
 
     fn new_from_data_type(value: DataType, item_count: usize) -> Self {
         unsafe {
-            List(cass_collection_new_from_data_type(
-                value.inner(),
-                item_count,
-            ))
+            List(
+                cass_collection_new_from_data_type(value.inner(), item_count),
+                ElementTypes::default(),
+            )
         }
     }
 
@@ -204,38 +219,66 @@ impl CassCollection for List {
 
     /// Appends a "tinyint" to the collection.
     fn append_int8(&mut self, value: i8) -> Result<&mut Self> {
-        unsafe { cass_collection_append_int8(self.inner(), value).to_result(self) }
+        unsafe {
+            cass_collection_append_int8(self.inner(), value)
+                .to_result(self)
+                .map(|s| s.record(Provided::Scalar("tinyint")))
+        }
     }
 
     /// Appends an "smallint" to the collection.
     fn append_int16(&mut self, value: i16) -> Result<&mut Self> {
-        unsafe { cass_collection_append_int16(self.inner(), value).to_result(self) }
+        unsafe {
+            cass_collection_append_int16(self.inner(), value)
+                .to_result(self)
+                .map(|s| s.record(Provided::Scalar("smallint")))
+        }
     }
 
     /// Appends an "int" to the collection.
     fn append_int32(&mut self, value: i32) -> Result<&mut Self> {
-        unsafe { cass_collection_append_int32(self.inner(), value).to_result(self) }
+        unsafe {
+            cass_collection_append_int32(self.inner(), value)
+                .to_result(self)
+                .map(|s| s.record(Provided::Scalar("int")))
+        }
     }
 
     /// Appends a "date" to the collection.
     fn append_uint32(&mut self, value: u32) -> Result<&mut Self> {
-        unsafe { cass_collection_append_uint32(self.inner(), value).to_result(self) }
+        unsafe {
+            cass_collection_append_uint32(self.inner(), value)
+                .to_result(self)
+                .map(|s| s.record(Provided::Scalar("date")))
+        }
     }
 
     /// Appends a "bigint", "counter", "timestamp" or "time" to the
     /// collection.
     fn append_int64(&mut self, value: i64) -> Result<&mut Self> {
-        unsafe { cass_collection_append_int64(self.inner(), value).to_result(self) }
+        unsafe {
+            cass_collection_append_int64(self.inner(), value)
+                .to_result(self)
+                .map(|s| s.record(Provided::Scalar("bigint")))
+        }
     }
 
     /// Appends a "float" to the collection.
     fn append_float(&mut self, value: f32) -> Result<&mut Self> {
-        unsafe { cass_collection_append_float(self.inner(), value).to_result(self) }
+        unsafe {
+            cass_collection_append_float(self.inner(), value)
+                .to_result(self)
+                .map(|s| s.record(Provided::Scalar("float")))
+        }
     }
 
     /// Appends a "double" to the collection.
     fn append_double(&mut self, value: f64) -> Result<&mut Self> {
-        unsafe { cass_collection_append_double(self.inner(), value).to_result(self) }
+        unsafe {
+            cass_collection_append_double(self.inner(), value)
+                .to_result(self)
+                .map(|s| s.record(Provided::Scalar("double")))
+        }
     }
 
     /// Appends a "boolean" to the collection.
@@ -243,6 +286,7 @@ impl CassCollection for List {
         unsafe {
             cass_collection_append_bool(self.inner(), if value { cass_true } else { cass_false })
                 .to_result(self)
+                .map(|s| s.record(Provided::Scalar("boolean")))
         }
     }
 
@@ -251,7 +295,9 @@ impl CassCollection for List {
         unsafe {
             let value_ptr = value.as_ptr() as *const c_char;
             let result = cass_collection_append_string_n(self.inner(), value_ptr, value.len());
-            result.to_result(self)
+            result
+                .to_result(self)
+                .map(|s| s.record(Provided::Scalar("text")))
         }
     }
 
@@ -259,49 +305,84 @@ impl CassCollection for List {
     fn append_bytes(&mut self, value: Vec<u8>) -> Result<&mut Self> {
         unsafe {
             let bytes = cass_collection_append_bytes(self.inner(), value[..].as_ptr(), value.len());
-            bytes.to_result(self)
+            bytes
+                .to_result(self)
+                .map(|s| s.record(Provided::Scalar("blob")))
         }
     }
 
     /// Appends a "uuid" or "timeuuid"  to the collection.
     fn append_uuid(&mut self, value: Uuid) -> Result<&mut Self> {
-        unsafe { cass_collection_append_uuid(self.inner(), value.inner()).to_result(self) }
+        unsafe {
+            cass_collection_append_uuid(self.inner(), value.inner())
+                .to_result(self)
+                .map(|s| s.record(Provided::Scalar("uuid")))
+        }
     }
 
     /// Appends an "inet" to the collection.
     fn append_inet(&mut self, value: Inet) -> Result<&mut Self> {
-        unsafe { cass_collection_append_inet(self.inner(), value.inner()).to_result(self) }
+        unsafe {
+            cass_collection_append_inet(self.inner(), value.inner())
+                .to_result(self)
+                .map(|s| s.record(Provided::Scalar("inet")))
+        }
     }
 
     /// Appends a "list" to the collection.
     fn append_list(&mut self, value: List) -> Result<&mut Self> {
-        unsafe { cass_collection_append_collection(self.inner(), value.0).to_result(self) }
+        let element = value.provided();
+        unsafe {
+            cass_collection_append_collection(self.inner(), value.0)
+                .to_result(self)
+                .map(|s| s.record(element))
+        }
     }
 
     /// Appends a "set" to the collection.
     fn append_set(&mut self, value: Set) -> Result<&mut Self> {
-        unsafe { cass_collection_append_collection(self.inner(), value.0).to_result(self) }
+        let element = value.provided();
+        unsafe {
+            cass_collection_append_collection(self.inner(), value.0)
+                .to_result(self)
+                .map(|s| s.record(element))
+        }
     }
 
     /// Appends a "map" to the collection.
     fn append_map(&mut self, value: Map) -> Result<&mut Self> {
-        unsafe { cass_collection_append_collection(self.inner(), value.0).to_result(self) }
+        let element = value.provided();
+        unsafe {
+            cass_collection_append_collection(self.inner(), value.0)
+                .to_result(self)
+                .map(|s| s.record(element))
+        }
     }
 
     /// Appends a "tuple" to the collection.
     fn append_tuple(&mut self, value: Tuple) -> Result<&mut Self> {
-        unsafe { cass_collection_append_tuple(self.inner(), value.inner()).to_result(self) }
+        let element = value.provided();
+        unsafe {
+            cass_collection_append_tuple(self.inner(), value.inner())
+                .to_result(self)
+                .map(|s| s.record(element))
+        }
     }
 
     /// Appends a "udt" to the collection.
     fn append_user_type(&mut self, value: &UserType) -> Result<&mut Self> {
-        unsafe { cass_collection_append_user_type(self.inner(), value.inner()).to_result(self) }
+        let element = value.provided();
+        unsafe {
+            cass_collection_append_user_type(self.inner(), value.inner())
+                .to_result(self)
+                .map(|s| s.record(element))
+        }
     }
 }
 
 /// A Cassandra set
 #[derive(Debug)]
-pub struct Set(*mut _CassCollection);
+pub struct Set(*mut _CassCollection, ElementTypes);
 
 // The underlying C type has no thread-local state, and forbids only concurrent
 // mutation/free: https://datastax.github.io/cpp-driver/topics/#thread-safety
@@ -314,6 +395,20 @@ impl Drop for Set {
     }
 }
 
+impl Set {
+    /// Records the type of an appended element, which the driver does not check
+    /// if the set was created without a data type.
+    fn record(&mut self, element: Provided) -> &mut Self {
+        self.1.record(1, element);
+        self
+    }
+
+    /// What is known about the type of the set, for checking it when bound.
+    pub(crate) fn provided(&self) -> Provided {
+        Provided::from_data_type(&self.data_type(), &self.1)
+    }
+}
+
 // impl CassIterator for Set{
 //
 // }
@@ -323,15 +418,20 @@ impl CassCollection for Set {
 
     /// create a new list
     fn with_capacity(capacity: usize) -> Self {
-        unsafe { Set(cass_collection_new(CASS_COLLECTION_TYPE_SET, capacity)) }
+        unsafe {
+            Set(
+                cass_collection_new(CASS_COLLECTION_TYPE_SET, capacity),
+                ElementTypes::default(),
+            )
+        }
     }
 
     fn new_from_data_type(value: DataType, item_count: usize) -> Self {
         unsafe {
-            Set(cass_collection_new_from_data_type(
-                value.inner(),
-                item_count,
-            ))
+            Set(
+                cass_collection_new_from_data_type(value.inner(), item_count),
+                ElementTypes::default(),
+            )
         }
     }
     /// Gets the data type of a collection.
@@ -341,38 +441,66 @@ impl CassCollection for Set {
 
     /// Appends a "tinyint" to the collection.
     fn append_int8(&mut self, value: i8) -> Result<&mut Self> {
-        unsafe { cass_collection_append_int8(self.inner(), value).to_result(self) }
+        unsafe {
+            cass_collection_append_int8(self.inner(), value)
+                .to_result(self)
+                .map(|s| s.record(Provided::Scalar("tinyint")))
+        }
     }
 
     /// Appends an "smallint" to the collection.
     fn append_int16(&mut self, value: i16) -> Result<&mut Self> {
-        unsafe { cass_collection_append_int16(self.inner(), value).to_result(self) }
+        unsafe {
+            cass_collection_append_int16(self.inner(), value)
+                .to_result(self)
+                .map(|s| s.record(Provided::Scalar("smallint")))
+        }
     }
 
     /// Appends an "int" to the collection.
     fn append_int32(&mut self, value: i32) -> Result<&mut Self> {
-        unsafe { cass_collection_append_int32(self.inner(), value).to_result(self) }
+        unsafe {
+            cass_collection_append_int32(self.inner(), value)
+                .to_result(self)
+                .map(|s| s.record(Provided::Scalar("int")))
+        }
     }
 
     /// Appends a "date" to the collection.
     fn append_uint32(&mut self, value: u32) -> Result<&mut Self> {
-        unsafe { cass_collection_append_uint32(self.inner(), value).to_result(self) }
+        unsafe {
+            cass_collection_append_uint32(self.inner(), value)
+                .to_result(self)
+                .map(|s| s.record(Provided::Scalar("date")))
+        }
     }
 
     /// Appends a "bigint", "counter", "timestamp" or "time" to the
     /// collection.
     fn append_int64(&mut self, value: i64) -> Result<&mut Self> {
-        unsafe { cass_collection_append_int64(self.inner(), value).to_result(self) }
+        unsafe {
+            cass_collection_append_int64(self.inner(), value)
+                .to_result(self)
+                .map(|s| s.record(Provided::Scalar("bigint")))
+        }
     }
 
     /// Appends a "float" to the collection.
     fn append_float(&mut self, value: f32) -> Result<&mut Self> {
-        unsafe { cass_collection_append_float(self.inner(), value).to_result(self) }
+        unsafe {
+            cass_collection_append_float(self.inner(), value)
+                .to_result(self)
+                .map(|s| s.record(Provided::Scalar("float")))
+        }
     }
 
     /// Appends a "double" to the collection.
     fn append_double(&mut self, value: f64) -> Result<&mut Self> {
-        unsafe { cass_collection_append_double(self.inner(), value).to_result(self) }
+        unsafe {
+            cass_collection_append_double(self.inner(), value)
+                .to_result(self)
+                .map(|s| s.record(Provided::Scalar("double")))
+        }
     }
 
     /// Appends a "boolean" to the collection.
@@ -380,6 +508,7 @@ impl CassCollection for Set {
         unsafe {
             cass_collection_append_bool(self.inner(), if value { cass_true } else { cass_false })
                 .to_result(self)
+                .map(|s| s.record(Provided::Scalar("boolean")))
         }
     }
 
@@ -388,7 +517,9 @@ impl CassCollection for Set {
         unsafe {
             let value_ptr = value.as_ptr() as *const c_char;
             let result = cass_collection_append_string_n(self.inner(), value_ptr, value.len());
-            result.to_result(self)
+            result
+                .to_result(self)
+                .map(|s| s.record(Provided::Scalar("text")))
         }
     }
 
@@ -396,49 +527,84 @@ impl CassCollection for Set {
     fn append_bytes(&mut self, value: Vec<u8>) -> Result<&mut Self> {
         unsafe {
             let bytes = cass_collection_append_bytes(self.inner(), value[..].as_ptr(), value.len());
-            bytes.to_result(self)
+            bytes
+                .to_result(self)
+                .map(|s| s.record(Provided::Scalar("blob")))
         }
     }
 
     /// Appends a "uuid" or "timeuuid"  to the collection.
     fn append_uuid(&mut self, value: Uuid) -> Result<&mut Self> {
-        unsafe { cass_collection_append_uuid(self.inner(), value.inner()).to_result(self) }
+        unsafe {
+            cass_collection_append_uuid(self.inner(), value.inner())
+                .to_result(self)
+                .map(|s| s.record(Provided::Scalar("uuid")))
+        }
     }
 
     /// Appends an "inet" to the collection.
     fn append_inet(&mut self, value: Inet) -> Result<&mut Self> {
-        unsafe { cass_collection_append_inet(self.inner(), value.inner()).to_result(self) }
+        unsafe {
+            cass_collection_append_inet(self.inner(), value.inner())
+                .to_result(self)
+                .map(|s| s.record(Provided::Scalar("inet")))
+        }
     }
 
     /// Appends a "list" to the collection.
     fn append_list(&mut self, value: List) -> Result<&mut Self> {
-        unsafe { cass_collection_append_collection(self.inner(), value.0).to_result(self) }
+        let element = value.provided();
+        unsafe {
+            cass_collection_append_collection(self.inner(), value.0)
+                .to_result(self)
+                .map(|s| s.record(element))
+        }
     }
 
     /// Appends a "set" to the collection.
     fn append_set(&mut self, value: Set) -> Result<&mut Self> {
-        unsafe { cass_collection_append_collection(self.inner(), value.0).to_result(self) }
+        let element = value.provided();
+        unsafe {
+            cass_collection_append_collection(self.inner(), value.0)
+                .to_result(self)
+                .map(|s| s.record(element))
+        }
     }
 
     /// Appends a "map" to the collection.
     fn append_map(&mut self, value: Map) -> Result<&mut Self> {
-        unsafe { cass_collection_append_collection(self.inner(), value.0).to_result(self) }
+        let element = value.provided();
+        unsafe {
+            cass_collection_append_collection(self.inner(), value.0)
+                .to_result(self)
+                .map(|s| s.record(element))
+        }
     }
 
     /// Appends a "tuple" to the collection.
     fn append_tuple(&mut self, value: Tuple) -> Result<&mut Self> {
-        unsafe { cass_collection_append_tuple(self.inner(), value.inner()).to_result(self) }
+        let element = value.provided();
+        unsafe {
+            cass_collection_append_tuple(self.inner(), value.inner())
+                .to_result(self)
+                .map(|s| s.record(element))
+        }
     }
 
     /// Appends a "udt" to the collection.
     fn append_user_type(&mut self, value: &UserType) -> Result<&mut Self> {
-        unsafe { cass_collection_append_user_type(self.inner(), value.inner()).to_result(self) }
+        let element = value.provided();
+        unsafe {
+            cass_collection_append_user_type(self.inner(), value.inner())
+                .to_result(self)
+                .map(|s| s.record(element))
+        }
     }
 }
 
 /// A Cassandra Map
 #[derive(Debug)]
-pub struct Map(*mut _CassCollection);
+pub struct Map(*mut _CassCollection, ElementTypes);
 
 // The underlying C type has no thread-local state, and forbids only concurrent
 // mutation/free: https://datastax.github.io/cpp-driver/topics/#thread-safety
@@ -451,19 +617,39 @@ impl Drop for Map {
     }
 }
 
+impl Map {
+    /// Records the type of an appended element, which the driver does not check
+    /// if the map was created without a data type. Keys and values are
+    /// recorded alternately.
+    fn record(&mut self, element: Provided) -> &mut Self {
+        self.1.record(2, element);
+        self
+    }
+
+    /// What is known about the type of the map, for checking it when bound.
+    pub(crate) fn provided(&self) -> Provided {
+        Provided::from_data_type(&self.data_type(), &self.1)
+    }
+}
+
 impl CassCollection for Map {
     type Value = _CassCollection;
 
     fn with_capacity(capacity: usize) -> Self {
-        unsafe { Map(cass_collection_new(CASS_COLLECTION_TYPE_MAP, capacity)) }
+        unsafe {
+            Map(
+                cass_collection_new(CASS_COLLECTION_TYPE_MAP, capacity),
+                ElementTypes::default(),
+            )
+        }
     }
 
     fn new_from_data_type(value: DataType, item_count: usize) -> Self {
         unsafe {
-            Map(cass_collection_new_from_data_type(
-                value.inner(),
-                item_count,
-            ))
+            Map(
+                cass_collection_new_from_data_type(value.inner(), item_count),
+                ElementTypes::default(),
+            )
         }
     }
 
@@ -474,38 +660,66 @@ impl CassCollection for Map {
 
     /// Appends a "tinyint" to the collection.
     fn append_int8(&mut self, value: i8) -> Result<&mut Self> {
-        unsafe { cass_collection_append_int8(self.inner(), value).to_result(self) }
+        unsafe {
+            cass_collection_append_int8(self.inner(), value)
+                .to_result(self)
+                .map(|s| s.record(Provided::Scalar("tinyint")))
+        }
     }
 
     /// Appends an "smallint" to the collection.
     fn append_int16(&mut self, value: i16) -> Result<&mut Self> {
-        unsafe { cass_collection_append_int16(self.inner(), value).to_result(self) }
+        unsafe {
+            cass_collection_append_int16(self.inner(), value)
+                .to_result(self)
+                .map(|s| s.record(Provided::Scalar("smallint")))
+        }
     }
 
     /// Appends an "int" to the collection.
     fn append_int32(&mut self, value: i32) -> Result<&mut Self> {
-        unsafe { cass_collection_append_int32(self.inner(), value).to_result(self) }
+        unsafe {
+            cass_collection_append_int32(self.inner(), value)
+                .to_result(self)
+                .map(|s| s.record(Provided::Scalar("int")))
+        }
     }
 
     /// Appends a "date" to the collection.
     fn append_uint32(&mut self, value: u32) -> Result<&mut Self> {
-        unsafe { cass_collection_append_uint32(self.inner(), value).to_result(self) }
+        unsafe {
+            cass_collection_append_uint32(self.inner(), value)
+                .to_result(self)
+                .map(|s| s.record(Provided::Scalar("date")))
+        }
     }
 
     /// Appends a "bigint", "counter", "timestamp" or "time" to the
     /// collection.
     fn append_int64(&mut self, value: i64) -> Result<&mut Self> {
-        unsafe { cass_collection_append_int64(self.inner(), value).to_result(self) }
+        unsafe {
+            cass_collection_append_int64(self.inner(), value)
+                .to_result(self)
+                .map(|s| s.record(Provided::Scalar("bigint")))
+        }
     }
 
     /// Appends a "float" to the collection.
     fn append_float(&mut self, value: f32) -> Result<&mut Self> {
-        unsafe { cass_collection_append_float(self.inner(), value).to_result(self) }
+        unsafe {
+            cass_collection_append_float(self.inner(), value)
+                .to_result(self)
+                .map(|s| s.record(Provided::Scalar("float")))
+        }
     }
 
     /// Appends a "double" to the collection.
     fn append_double(&mut self, value: f64) -> Result<&mut Self> {
-        unsafe { cass_collection_append_double(self.inner(), value).to_result(self) }
+        unsafe {
+            cass_collection_append_double(self.inner(), value)
+                .to_result(self)
+                .map(|s| s.record(Provided::Scalar("double")))
+        }
     }
 
     /// Appends a "boolean" to the collection.
@@ -513,6 +727,7 @@ impl CassCollection for Map {
         unsafe {
             cass_collection_append_bool(self.inner(), if value { cass_true } else { cass_false })
                 .to_result(self)
+                .map(|s| s.record(Provided::Scalar("boolean")))
         }
     }
 
@@ -521,7 +736,9 @@ impl CassCollection for Map {
         unsafe {
             let value_ptr = value.as_ptr() as *const c_char;
             let result = cass_collection_append_string_n(self.inner(), value_ptr, value.len());
-            result.to_result(self)
+            result
+                .to_result(self)
+                .map(|s| s.record(Provided::Scalar("text")))
         }
     }
 
@@ -529,42 +746,77 @@ impl CassCollection for Map {
     fn append_bytes(&mut self, value: Vec<u8>) -> Result<&mut Self> {
         unsafe {
             let bytes = cass_collection_append_bytes(self.inner(), value[..].as_ptr(), value.len());
-            bytes.to_result(self)
+            bytes
+                .to_result(self)
+                .map(|s| s.record(Provided::Scalar("blob")))
         }
     }
 
     /// Appends a "uuid" or "timeuuid"  to the collection.
     fn append_uuid(&mut self, value: Uuid) -> Result<&mut Self> {
-        unsafe { cass_collection_append_uuid(self.inner(), value.inner()).to_result(self) }
+        unsafe {
+            cass_collection_append_uuid(self.inner(), value.inner())
+                .to_result(self)
+                .map(|s| s.record(Provided::Scalar("uuid")))
+        }
     }
 
     /// Appends an "inet" to the collection.
     fn append_inet(&mut self, value: Inet) -> Result<&mut Self> {
-        unsafe { cass_collection_append_inet(self.inner(), value.inner()).to_result(self) }
+        unsafe {
+            cass_collection_append_inet(self.inner(), value.inner())
+                .to_result(self)
+                .map(|s| s.record(Provided::Scalar("inet")))
+        }
     }
 
     /// Appends a "list" to the collection.
     fn append_list(&mut self, value: List) -> Result<&mut Self> {
-        unsafe { cass_collection_append_collection(self.inner(), value.0).to_result(self) }
+        let element = value.provided();
+        unsafe {
+            cass_collection_append_collection(self.inner(), value.0)
+                .to_result(self)
+                .map(|s| s.record(element))
+        }
     }
 
     /// Appends a "set" to the collection.
     fn append_set(&mut self, value: Set) -> Result<&mut Self> {
-        unsafe { cass_collection_append_collection(self.inner(), value.0).to_result(self) }
+        let element = value.provided();
+        unsafe {
+            cass_collection_append_collection(self.inner(), value.0)
+                .to_result(self)
+                .map(|s| s.record(element))
+        }
     }
 
     /// Appends a "map" to the collection.
     fn append_map(&mut self, value: Map) -> Result<&mut Self> {
-        unsafe { cass_collection_append_collection(self.inner(), value.0).to_result(self) }
+        let element = value.provided();
+        unsafe {
+            cass_collection_append_collection(self.inner(), value.0)
+                .to_result(self)
+                .map(|s| s.record(element))
+        }
     }
 
     /// Appends a "tuple" to the collection.
     fn append_tuple(&mut self, value: Tuple) -> Result<&mut Self> {
-        unsafe { cass_collection_append_tuple(self.inner(), value.inner()).to_result(self) }
+        let element = value.provided();
+        unsafe {
+            cass_collection_append_tuple(self.inner(), value.inner())
+                .to_result(self)
+                .map(|s| s.record(element))
+        }
     }
 
     /// Appends a "udt" to the collection.
     fn append_user_type(&mut self, value: &UserType) -> Result<&mut Self> {
-        unsafe { cass_collection_append_user_type(self.inner(), value.inner()).to_result(self) }
+        let element = value.provided();
+        unsafe {
+            cass_collection_append_user_type(self.inner(), value.inner())
+                .to_result(self)
+                .map(|s| s.record(element))
+        }
     }
 }
@@ -0,0 +1,231 @@
+use crate::cassandra::error::*;
+use crate::cassandra::result::CassResult;
+use crate::cassandra::statement::Statement;
+
+use futures::future::BoxFuture;
+use futures::stream::{FuturesUnordered, Stream, StreamExt};
+use futures::FutureExt;
+
+use std::fmt;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// What to do when a statement fails during `Session::execute_concurrent`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorMode {
+    /// Stop at the first failure: issue no further statements, abandon those
+    /// in flight, and report the error.
+    FailFast,
+    /// Execute every statement, reporting each failure alongside the
+    /// successful results.
+    CollectAll,
+}
+
+/// The progress of a `ConcurrentExecution`, reported after each statement
+/// completes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Progress {
+    /// The number of statements which have completed, successfully or not.
+    pub completed: usize,
+    /// The number of completed statements which failed.
+    pub failed: usize,
+    /// The number of statements which were executing when the last one
+    /// completed, including it. This reaches the concurrency limit when the
+    /// limit is saturated.
+    pub in_flight: usize,
+    /// The total number of statements, if known from the iterator's size hint.
+    pub total: Option<usize>,
+}
+
+type ProgressCallback = Box<dyn FnMut(&Progress) + Send>;
+
+/// The execution of many statements with at most a fixed number of requests in
+/// flight at once. Created by `Session::execute_concurrent`.
+///
+/// Statements are taken from the iterator lazily, only when there is room for
+/// another request, so the iterator may be arbitrarily long.
+///
+/// ```no_run
+/// # use cassandra_cpp::*;
+/// # async fn test(session: &Session) -> Result<()> {
+/// let insert = session.prepare("INSERT INTO ks.t (id) VALUES (?)").await?;
+/// let statements = (0..10_000).map(|id| {
+///     let mut statement = insert.bind();
+///     statement.bind(0, id).expect("bind");
+///     statement
+/// });
+/// let results = session
+///     .execute_concurrent(statements, 64)
+///     .on_progress(|progress| println!("{} done", progress.completed))
+///     .collect()
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[must_use]
+pub struct ConcurrentExecution<I> {
+    statements: I,
+    max_in_flight: usize,
+    error_mode: ErrorMode,
+    on_progress: Option<ProgressCallback>,
+}
+
+impl<I> fmt::Debug for ConcurrentExecution<I> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ConcurrentExecution")
+            .field("max_in_flight", &self.max_in_flight)
+            .field("error_mode", &self.error_mode)
+            .finish()
+    }
+}
+
+impl<I: Iterator<Item = Statement>> ConcurrentExecution<I> {
+    pub(crate) fn new(statements: I, max_in_flight: usize) -> Self {
+        ConcurrentExecution {
+            statements,
+            max_in_flight: max_in_flight.max(1),
+            error_mode: ErrorMode::FailFast,
+            on_progress: None,
+        }
+    }
+
+    /// Set how failures are handled. The default is `ErrorMode::FailFast`.
+    pub fn error_mode(mut self, error_mode: ErrorMode) -> Self {
+        self.error_mode = error_mode;
+        self
+    }
+
+    /// Call `callback` each time a statement completes.
+    pub fn on_progress(mut self, callback: impl FnMut(&Progress) + Send + 'static) -> Self {
+        self.on_progress = Some(Box::new(callback));
+        self
+    }
+
+    /// Execute all statements and return their results in the order of the
+    /// input statements.
+    ///
+    /// With `ErrorMode::FailFast`, this fails with the first error encountered,
+    /// and every returned result is `Ok`. With `ErrorMode::CollectAll`, this
+    /// never fails, and each statement's result is returned individually.
+    pub async fn collect(self) -> Result<Vec<Result<CassResult>>> {
+        let fail_fast = self.error_mode == ErrorMode::FailFast;
+        let mut stream = self.into_stream();
+        let mut results = vec![];
+        while let Some((index, result)) = stream.next().await {
+            let result = match result {
+                Err(err) if fail_fast => return Err(err),
+                result => result,
+            };
+            if results.len() <= index {
+                results.resize_with(index + 1, || None);
+            }
+            results[index] = Some(result);
+        }
+        Ok(results
+            .into_iter()
+            .map(|result| result.expect("every statement completed"))
+            .collect())
+    }
+
+    /// Execute all statements, yielding each result with the index of its
+    /// statement in the input, in the order the statements complete.
+    ///
+    /// With `ErrorMode::FailFast`, the stream ends after yielding the first
+    /// error.
+    pub fn into_stream(self) -> ConcurrentStream<I> {
+        let total = match self.statements.size_hint() {
+            (lower, Some(upper)) if lower == upper => Some(lower),
+            _ => None,
+        };
+        ConcurrentStream {
+            statements: self.statements.enumerate(),
+            max_in_flight: self.max_in_flight,
+            error_mode: self.error_mode,
+            on_progress: self.on_progress,
+            in_flight: FuturesUnordered::new(),
+            progress: Progress {
+                completed: 0,
+                failed: 0,
+                in_flight: 0,
+                total,
+            },
+            finished: false,
+        }
+    }
+}
+
+/// The results of a `ConcurrentExecution`, in completion order. Created by
+/// `ConcurrentExecution::into_stream`.
+#[must_use]
+pub struct ConcurrentStream<I> {
+    statements: std::iter::Enumerate<I>,
+    max_in_flight: usize,
+    error_mode: ErrorMode,
+    on_progress: Option<ProgressCallback>,
+    in_flight: FuturesUnordered<BoxFuture<'static, (usize, Result<CassResult>)>>,
+    progress: Progress,
+    finished: bool,
+}
+
+impl<I> Unpin for ConcurrentStream<I> {}
+
+impl<I> fmt::Debug for ConcurrentStream<I> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ConcurrentStream")
+            .field("max_in_flight", &self.max_in_flight)
+            .field("error_mode", &self.error_mode)
+            .field("progress", &self.progress)
+            .finish()
+    }
+}
+
+impl<I> ConcurrentStream<I> {
+    /// The progress of the execution so far.
+    pub fn progress(&self) -> &Progress {
+        &self.progress
+    }
+}
+
+impl<I: Iterator<Item = Statement>> Stream for ConcurrentStream<I> {
+    type Item = (usize, Result<CassResult>);
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        if this.finished {
+            return Poll::Ready(None);
+        }
+        while this.in_flight.len() < this.max_in_flight {
+            match this.statements.next() {
+                Some((index, statement)) => {
+                    let future = statement.execute().map(move |result| (index, result));
+                    this.in_flight.push(future.boxed());
+                }
+                None => break,
+            }
+        }
+        // Counted before polling, so the statement which completes is
+        // included.
+        this.progress.in_flight = this.in_flight.len();
+        match this.in_flight.poll_next_unpin(cx) {
+            Poll::Ready(Some((index, result))) => {
+                this.progress.completed += 1;
+                if result.is_err() {
+                    this.progress.failed += 1;
+                    if this.error_mode == ErrorMode::FailFast {
+                        this.finished = true;
+                        this.in_flight = FuturesUnordered::new();
+                    }
+                }
+                if let Some(callback) = &mut this.on_progress {
+                    callback(&this.progress);
+                }
+                Poll::Ready(Some((index, result)))
+            }
+            Poll::Ready(None) => {
+                this.finished = true;
+                Poll::Ready(None)
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
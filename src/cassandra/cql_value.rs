@@ -0,0 +1,65 @@
+use crate::cassandra::collection::{List, Map, Set};
+use crate::cassandra::error::*;
+use crate::cassandra::inet::Inet;
+use crate::cassandra::statement::Statement;
+use crate::cassandra::tuple::Tuple;
+use crate::cassandra::user_type::UserType;
+use crate::cassandra::uuid::Uuid;
+
+use bigdecimal::BigDecimal;
+
+/// A Rust value which can be bound to a statement parameter. Used to bind a
+/// whole parameter list at once with `Statement::bind_all` and
+/// `PreparedStatement::bind_with`.
+pub trait ToCqlValue {
+    /// Bind the value to the parameter at `index`.
+    fn bind_to(&self, statement: &mut Statement, index: usize) -> Result<()>;
+}
+
+macro_rules! impl_to_cql_value {
+    ($t:ty, |$self_:ident, $statement:ident, $index:ident| $bind:expr) => {
+        impl ToCqlValue for $t {
+            fn bind_to(&$self_, $statement: &mut Statement, $index: usize) -> Result<()> {
+                $bind.map(|_| ())
+            }
+        }
+    };
+}
+
+impl_to_cql_value!(bool, |self, s, i| s.bind_bool(i, *self));
+impl_to_cql_value!(i8, |self, s, i| s.bind_int8(i, *self));
+impl_to_cql_value!(i16, |self, s, i| s.bind_int16(i, *self));
+impl_to_cql_value!(i32, |self, s, i| s.bind_int32(i, *self));
+impl_to_cql_value!(i64, |self, s, i| s.bind_int64(i, *self));
+impl_to_cql_value!(u32, |self, s, i| s.bind_uint32(i, *self));
+impl_to_cql_value!(f32, |self, s, i| s.bind_float(i, *self));
+impl_to_cql_value!(f64, |self, s, i| s.bind_double(i, *self));
+impl_to_cql_value!(str, |self, s, i| s.bind_string(i, self));
+impl_to_cql_value!(String, |self, s, i| s.bind_string(i, self));
+impl_to_cql_value!([u8], |self, s, i| s.bind_bytes(i, self.to_vec()));
+impl_to_cql_value!(Vec<u8>, |self, s, i| s.bind_bytes(i, self.clone()));
+impl_to_cql_value!(Uuid, |self, s, i| s.bind_uuid(i, *self));
+impl_to_cql_value!(uuid::Uuid, |self, s, i| s.bind_uuid(i, (*self).into()));
+impl_to_cql_value!(Inet, |self, s, i| s.bind_inet(i, *self));
+impl_to_cql_value!(BigDecimal, |self, s, i| s.bind_decimal(i, self));
+impl_to_cql_value!(List, |self, s, i| s.bind_list(i, self));
+impl_to_cql_value!(Set, |self, s, i| s.bind_set(i, self));
+impl_to_cql_value!(Map, |self, s, i| s.bind_map(i, self));
+impl_to_cql_value!(Tuple, |self, s, i| s.bind_tuple(i, self));
+impl_to_cql_value!(UserType, |self, s, i| s.bind_user_type(i, self));
+
+/// `None` binds null, which may be bound to a parameter of any type.
+impl<T: ToCqlValue> ToCqlValue for Option<T> {
+    fn bind_to(&self, statement: &mut Statement, index: usize) -> Result<()> {
+        match self {
+            Some(value) => value.bind_to(statement, index),
+            None => statement.bind_null(index).map(|_| ()),
+        }
+    }
+}
+
+impl<T: ToCqlValue + ?Sized> ToCqlValue for &T {
+    fn bind_to(&self, statement: &mut Statement, index: usize) -> Result<()> {
+        (**self).bind_to(statement, index)
+    }
+}
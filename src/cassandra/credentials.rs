@@ -0,0 +1,195 @@
+use crate::cassandra::authenticator::{AuthExchange, Authenticator};
+use crate::cassandra::cluster_config::Credentials;
+use crate::cassandra::error::*;
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// A source of credentials, queried each time a connection authenticates
+/// with a host. Install it with `Cluster::set_credential_provider`, so that
+/// credentials which are rotated are picked up by new connections without a
+/// restart.
+///
+/// The credentials are sent with the `PLAIN` SASL mechanism, as used by
+/// Cassandra's `PasswordAuthenticator`. The provider is called from the
+/// driver's IO threads, so should not block for long.
+pub trait CredentialProvider: Send + Sync {
+    /// The current credentials. An error fails the connection attempt.
+    fn credentials(&self) -> Result<Credentials>;
+}
+
+/// Fixed credentials.
+impl CredentialProvider for Credentials {
+    fn credentials(&self) -> Result<Credentials> {
+        Ok(self.clone())
+    }
+}
+
+fn unavailable(reason: String) -> Error {
+    ErrorKind::AuthenticationFailed(reason).into()
+}
+
+/// Credentials read from environment variables each time they are needed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnvCredentials {
+    username_var: String,
+    password_var: String,
+}
+
+impl EnvCredentials {
+    /// Reads the user name and password from the given variables.
+    pub fn new(username_var: impl Into<String>, password_var: impl Into<String>) -> Self {
+        EnvCredentials {
+            username_var: username_var.into(),
+            password_var: password_var.into(),
+        }
+    }
+
+    fn var(name: &str) -> Result<String> {
+        env::var(name).map_err(|e| unavailable(format!("{}: {}", name, e)))
+    }
+}
+
+impl CredentialProvider for EnvCredentials {
+    fn credentials(&self) -> Result<Credentials> {
+        Ok(Credentials {
+            username: Self::var(&self.username_var)?,
+            password: Self::var(&self.password_var)?,
+        })
+    }
+}
+
+/// Credentials read from a file each time they are needed, so a rotation is
+/// picked up by the next connection. The file is small, and read only when a
+/// connection is opened.
+///
+/// By default the file holds the user name on its first line and the
+/// password on its second. With `FileCredentials::with_username`, it holds
+/// just the password or token, as with a mounted secret. Trailing line
+/// breaks are ignored.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileCredentials {
+    path: PathBuf,
+    username: Option<String>,
+}
+
+impl FileCredentials {
+    /// Reads the user name and password from the given file.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        FileCredentials {
+            path: path.into(),
+            username: None,
+        }
+    }
+
+    /// Reads the password from the given file, using a fixed user name.
+    pub fn with_username(username: impl Into<String>, path: impl Into<PathBuf>) -> Self {
+        FileCredentials {
+            username: Some(username.into()),
+            ..Self::new(path)
+        }
+    }
+
+    /// The file read.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl CredentialProvider for FileCredentials {
+    fn credentials(&self) -> Result<Credentials> {
+        let contents = fs::read_to_string(&self.path)
+            .map_err(|e| unavailable(format!("{}: {}", self.path.display(), e)))?;
+        let mut lines = contents.lines();
+        let username = match &self.username {
+            Some(username) => username.clone(),
+            None => lines.next().unwrap_or_default().to_string(),
+        };
+        let password = lines
+            .next()
+            .ok_or_else(|| unavailable(format!("{}: missing password", self.path.display())))?;
+        Ok(Credentials {
+            username,
+            password: password.to_string(),
+        })
+    }
+}
+
+/// Authenticates with the `PLAIN` mechanism, using credentials from a
+/// provider.
+pub(crate) struct PlainAuthenticator<P>(pub(crate) P);
+
+impl<P: CredentialProvider> Authenticator for PlainAuthenticator<P> {
+    fn initial_response(&self, _exchange: &mut AuthExchange) -> Result<Vec<u8>> {
+        Ok(plain_token(&self.0.credentials()?))
+    }
+}
+
+/// The `PLAIN` token for some credentials: an empty authorization identity,
+/// then the user name and password, each preceded by a NUL.
+fn plain_token(credentials: &Credentials) -> Vec<u8> {
+    let mut token = Vec::with_capacity(credentials.username.len() + credentials.password.len() + 2);
+    token.push(0);
+    token.extend_from_slice(credentials.username.as_bytes());
+    token.push(0);
+    token.extend_from_slice(credentials.password.as_bytes());
+    token
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plain_token() {
+        let credentials = Credentials {
+            username: "user".to_string(),
+            password: "pass".to_string(),
+        };
+        assert_eq!(plain_token(&credentials), b"\0user\0pass");
+    }
+
+    #[test]
+    fn test_env_credentials() {
+        let provider = EnvCredentials::new("CASS_CREDS_TEST_USER", "CASS_CREDS_TEST_PASSWORD");
+        env::set_var("CASS_CREDS_TEST_USER", "user");
+        assert!(provider.credentials().is_err());
+        env::set_var("CASS_CREDS_TEST_PASSWORD", "one");
+        assert_eq!(provider.credentials().unwrap().password, "one");
+        env::set_var("CASS_CREDS_TEST_PASSWORD", "two");
+        assert_eq!(provider.credentials().unwrap().password, "two");
+    }
+
+    #[test]
+    fn test_file_credentials() {
+        let path = env::temp_dir().join(format!("cass_creds_test_{}", std::process::id()));
+        let provider = FileCredentials::new(&path);
+        assert!(provider.credentials().is_err());
+
+        fs::write(&path, "user\none\n").unwrap();
+        let credentials = provider.credentials().unwrap();
+        assert_eq!(credentials.username, "user");
+        assert_eq!(credentials.password, "one");
+
+        // A rotation is picked up straight away, even if the file's length
+        // and modification time are unchanged.
+        let modified = fs::metadata(&path).unwrap().modified().unwrap();
+        fs::write(&path, "user\ntwo\n").unwrap();
+        fs::File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(modified)
+            .unwrap();
+        assert_eq!(provider.credentials().unwrap().password, "two");
+
+        let provider = FileCredentials::with_username("user", &path);
+        fs::write(&path, "token\n").unwrap();
+        let credentials = provider.credentials().unwrap();
+        assert_eq!(credentials.username, "user");
+        assert_eq!(credentials.password, "token");
+
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -29,6 +29,7 @@ use crate::cassandra_sys::CassDataType as _CassDataType;
 use std::ffi::CString;
 use std::marker::PhantomData;
 use std::os::raw::c_char;
+use std::{slice, str};
 
 /// Any Cassandra datatype. This is an owned type.
 #[derive(Debug)]
@@ -84,11 +85,76 @@ impl Drop for DataType {
     }
 }
 
-impl ConstDataType<'_> {
+impl<'a> ConstDataType<'a> {
     /// Creates a new user defined type from existing data type.
     pub fn new_user_type(&self) -> UserType {
         unsafe { UserType::build(cass_user_type_new_from_data_type(self.0)) }
     }
+
+    /// Creates a new, owned copy of the data type.
+    pub fn new_from_existing(&self) -> DataType {
+        unsafe { DataType(cass_data_type_new_from_existing(self.0)) }
+    }
+
+    /// Gets the value type of the data type.
+    pub fn get_type(&self) -> ValueType {
+        unsafe { ValueType::build(cass_data_type_type(self.0)) }
+    }
+
+    /// Gets the type name of a UDT data type.
+    ///
+    /// <b>Note:</b> Only valid for UDT data types.
+    pub fn type_name(&self) -> Result<&'a str> {
+        let mut name = std::ptr::null();
+        let mut name_length = 0;
+        unsafe {
+            cass_data_type_type_name(self.0, &mut name, &mut name_length)
+                .to_result(())
+                .and_then(|_| {
+                    Ok(str::from_utf8(slice::from_raw_parts(
+                        name as *const u8,
+                        name_length,
+                    ))?)
+                })
+        }
+    }
+
+    /// Gets the sub-data type count of a UDT (user defined type), tuple
+    /// or collection. A collection or tuple created without a data type
+    /// has none.
+    pub fn sub_type_count(&self) -> usize {
+        unsafe { cass_data_sub_type_count(self.0) }
+    }
+
+    /// Gets the sub-data type of a UDT (user defined type), tuple or collection
+    /// at the specified index, or `None` if there is no such sub-type.
+    pub fn sub_data_type(&self, index: usize) -> Option<ConstDataType<'a>> {
+        let sub_data_type = unsafe { cass_data_type_sub_data_type(self.0, index) };
+        if sub_data_type.is_null() {
+            None
+        } else {
+            Some(ConstDataType(sub_data_type, PhantomData))
+        }
+    }
+
+    /// Gets the name of the field of a UDT (user defined type) at the
+    /// specified index.
+    ///
+    /// <b>Note:</b> Only valid for UDT data types.
+    pub fn sub_type_name(&self, index: usize) -> Result<&'a str> {
+        let mut name = std::ptr::null();
+        let mut name_length = 0;
+        unsafe {
+            cass_data_type_sub_type_name(self.0, index, &mut name, &mut name_length)
+                .to_result(())
+                .and_then(|_| {
+                    Ok(str::from_utf8(slice::from_raw_parts(
+                        name as *const u8,
+                        name_length,
+                    ))?)
+                })
+        }
+    }
 }
 
 impl DataType {
@@ -1,5 +1,6 @@
 use crate::cassandra::consistency::Consistency;
 use crate::cassandra::util::{Protected, ProtectedInner};
+use crate::cassandra::uuid::Uuid;
 use crate::cassandra::value::ValueType;
 use crate::cassandra::write_type::WriteType;
 
@@ -24,6 +25,7 @@ use crate::Session;
 
 use std::ffi::CStr;
 use std::fmt::Debug;
+use std::time::Duration;
 
 use std::{slice, str};
 
@@ -44,6 +46,20 @@ error_chain! {
             display("Cassandra error {:?}: {}", &code, &msg)
         }
 
+        /// The wrong number of values was bound to a statement.
+        BindArityMismatch(expected: usize, provided: usize) {
+            description("Wrong number of values bound")
+            display("Statement has {} parameters but {} values were bound", expected, provided)
+        }
+
+        /// A value of the wrong type was bound to a parameter of a prepared
+        /// statement: the parameter's index, name and CQL type, and the type of
+        /// the value.
+        BindTypeMismatch(index: usize, name: String, expected: String, provided: String) {
+            description("Bound value has the wrong type")
+            display("Parameter {} ({}) has type {} but a value of type {} was bound", index, name, expected, provided)
+        }
+
         /// Errors that happen when an invalid session is passed to a batch.
         BatchSessionMismatch(batch_session: Session, statement_session: Session) {
             description("Batch cannot add a statement belonging to another session.")
@@ -68,6 +84,86 @@ error_chain! {
             display("Cassandra detailed error {:?}: {}", &code, &msg)
         }
 
+        /// Authentication with a host failed, or could not be attempted.
+        AuthenticationFailed(reason: String) {
+            description("Authentication failed")
+            display("Authentication failed: {}", reason)
+        }
+
+        /// A cluster configuration is invalid.
+        InvalidConfig(reason: String) {
+            description("Invalid configuration")
+            display("Invalid configuration: {}", reason)
+        }
+
+        /// A paging cursor could not be decoded.
+        InvalidPagingCursor(reason: String) {
+            description("Invalid paging cursor")
+            display("Invalid paging cursor: {}", reason)
+        }
+
+        /// Keyset paging was requested for a table with no clustering key.
+        NoClusteringKey(table: String) {
+            description("Table has no clustering key")
+            display("Table {} has no clustering key", table)
+        }
+
+        /// A statement executed as a lightweight transaction returned no
+        /// `[applied]` column, so it was not conditional.
+        NotConditional {
+            description("Statement is not conditional")
+            display("Statement is not a conditional statement (lightweight transaction)")
+        }
+
+        /// A paging cursor was used with a different query or bound values from
+        /// the ones it was created for.
+        PagingCursorMismatch {
+            description("Paging cursor does not match statement")
+            display("Paging cursor was created for a different query or bound values")
+        }
+
+        /// A paging cursor was requested for a statement binding a value which
+        /// cannot be fingerprinted: a collection, tuple or user type. Its
+        /// index or name, and its type.
+        PagingCursorUnsupported(key: String, type_name: String) {
+            description("Paging cursor unsupported for statement")
+            display("Paging cursors are not supported for statements binding a {} (parameter {})", type_name, key)
+        }
+
+        /// A paging cursor was requested for a statement whose values were
+        /// bound without `Statement::record_bindings`.
+        BindingsNotRecorded {
+            description("Bindings not recorded")
+            display("Paging cursors need the statement's values to be bound after Statement::record_bindings")
+        }
+
+        /// An operation did not complete within its timeout, and was
+        /// abandoned.
+        Timeout(timeout: Duration) {
+            description("Operation timed out")
+            display("Operation did not complete within {:?}", timeout)
+        }
+
+        /// The trace of a request could not be read, or was still incomplete.
+        TraceUnavailable(tracing_id: Uuid) {
+            description("Trace unavailable")
+            display("Trace {} is unavailable or incomplete", tracing_id)
+        }
+
+        /// A keyspace's replication strategy or the cluster's partitioner is not
+        /// supported by the token ring.
+        UnsupportedReplication(reason: String) {
+            description("Unsupported replication")
+            display("Unsupported replication: {}", reason)
+        }
+
+        /// A versioned read-modify-write conflicted with other writers on every
+        /// attempt.
+        VersionConflict(attempts: u32) {
+            description("Version conflict")
+            display("Row was modified concurrently on each of {} attempts", attempts)
+        }
+
         /// Unsupported type encountered.
         UnsupportedType(expected: &'static str, actual: ValueType) {
             description("Unsupported type")
@@ -314,6 +410,26 @@ enhance_nullary_enum!(CassErrorCode, CassError_, {
     (SSL_CLOSED, CASS_ERROR_SSL_CLOSED, "SSL_CLOSED"),
 }, omit { CASS_OK, CASS_ERROR_LAST_ENTRY });
 
+/// Whether an error is likely to be transient, i.e., the request may succeed
+/// if retried: timeouts, unavailable replicas, and overload.
+pub(crate) fn is_transient(error: &Error) -> bool {
+    let code = match error.kind() {
+        ErrorKind::CassError(code, _) => code,
+        ErrorKind::CassErrorResult(code, ..) => code,
+        _ => return false,
+    };
+    matches!(
+        code,
+        CassErrorCode::LIB_REQUEST_TIMED_OUT
+            | CassErrorCode::LIB_REQUEST_QUEUE_FULL
+            | CassErrorCode::LIB_NO_HOSTS_AVAILABLE
+            | CassErrorCode::SERVER_UNAVAILABLE
+            | CassErrorCode::SERVER_OVERLOADED
+            | CassErrorCode::SERVER_WRITE_TIMEOUT
+            | CassErrorCode::SERVER_READ_TIMEOUT
+    )
+}
+
 /// Extract an optional C string lossily (i.e., using a replacement char for non-UTF-8 sequences).
 pub(crate) unsafe fn get_lossy_string<F>(get: F) -> Option<String>
 where
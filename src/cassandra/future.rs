@@ -2,7 +2,9 @@ use crate::cassandra::custom_payload::CustomPayloadResponse;
 use crate::cassandra::error::*;
 use crate::cassandra::prepared::PreparedStatement;
 use crate::cassandra::result::CassResult;
-use crate::cassandra::util::{Protected, ProtectedWithSession};
+use crate::cassandra::session::InFlight;
+use crate::cassandra::util::{self, Delay, Protected, ProtectedWithSession};
+use crate::cassandra::uuid::Uuid;
 
 use crate::cassandra_sys::cass_future_custom_payload_item;
 use crate::cassandra_sys::cass_future_custom_payload_item_count;
@@ -14,22 +16,28 @@ use crate::cassandra_sys::cass_future_get_prepared;
 use crate::cassandra_sys::cass_future_get_result;
 use crate::cassandra_sys::cass_future_ready;
 use crate::cassandra_sys::cass_future_set_callback;
+use crate::cassandra_sys::cass_future_tracing_id;
+use crate::cassandra_sys::cass_future_wait_timed;
 
 use crate::cassandra_sys::cass_true;
 use crate::cassandra_sys::CassFuture as _Future;
+use crate::cassandra_sys::CassUuid;
 use crate::cassandra_sys::CASS_OK;
 use crate::Session;
 
 use parking_lot::Mutex;
 
+use std::convert::TryFrom;
 use std::future::Future;
 use std::marker::PhantomData;
 use std::mem;
+use std::panic::{self, AssertUnwindSafe};
 use std::pin::Pin;
 use std::slice;
 use std::str;
 use std::sync::Arc;
 use std::task::{Context, Poll, Waker};
+use std::time::Duration;
 
 /// A future representing the result of a Cassandra driver operation.
 ///
@@ -50,6 +58,9 @@ pub struct CassFuture<T> {
     /// The session the future is being executed upon.
     session: Option<Session>,
 
+    /// Counts the request as in flight on the session, if it is a request.
+    in_flight: Option<InFlight>,
+
     /// Treat as if it contains a T.
     phantom: PhantomData<T>,
 }
@@ -73,9 +84,18 @@ impl<T> CassFuture<T> {
     /// be used to control how the result is extracted from the underlying Cassandra
     /// driver future (see `Completable`).
     pub(crate) fn build(session: Session, inner: *mut _Future) -> Self {
+        let mut future = Self::build_untracked(session, inner);
+        future.in_flight = future.session.as_ref().map(Session::track_request);
+        future
+    }
+
+    /// Wrap a Cassandra driver future which is not a request, e.g. connecting,
+    /// so is not counted by `Session::in_flight_requests`.
+    pub(crate) fn build_untracked(session: Session, inner: *mut _Future) -> Self {
         CassFuture {
             inner,
             session: Some(session),
+            in_flight: None,
             state: Arc::new(FutureTarget {
                 inner: Mutex::new(FutureState::Created),
             }),
@@ -191,12 +211,129 @@ impl Completable for (CassResult, CustomPayloadResponse) {
     }
 }
 
+/// Futures that complete with a normal result and the tracing id of the request.
+///
+/// The tracing id is only present if tracing was enabled on the statement. The
+/// result is returned even without one, since the request was still executed.
+impl Completable for (CassResult, Option<Uuid>) {
+    unsafe fn get(_session: Session, inner: *mut _Future) -> Option<Self> {
+        let mut tracing_id = CassUuid {
+            time_and_version: 0,
+            clock_seq_and_node: 0,
+        };
+        let tracing_id = cass_future_tracing_id(inner, &mut tracing_id)
+            .to_result(())
+            .ok()
+            .map(|_| Uuid::build(tracing_id));
+        cass_future_get_result(inner)
+            .as_ref()
+            .map(|r| (CassResult::build(r as *const _), tracing_id))
+    }
+}
+
 impl<T: Completable> CassFuture<T> {
     /// Synchronously executes the CassFuture, blocking until it
     /// completes.
     pub fn wait(mut self) -> Result<T> {
         unsafe { get_completion(self.take_session(), self.inner) }
     }
+
+    /// Synchronously executes the CassFuture, blocking until it completes or
+    /// `timeout` passes.
+    ///
+    /// On timeout, fails with `ErrorKind::Timeout`, and the request is
+    /// abandoned: it is counted by `Session::abandoned_requests`, but may
+    /// still be executed by the cluster.
+    pub fn wait_timeout(mut self, timeout: Duration) -> Result<T> {
+        let micros = u64::try_from(timeout.as_micros()).unwrap_or(u64::MAX);
+        if unsafe { cass_future_wait_timed(self.inner, micros) } != cass_true {
+            return Err(self.abandon(timeout));
+        }
+        unsafe { get_completion(self.take_session(), self.inner) }
+    }
+
+    /// Wrap this future so that it fails with `ErrorKind::Timeout` if it does
+    /// not complete within `timeout`, abandoning the request as
+    /// `wait_timeout` does.
+    ///
+    /// The deadline is timed without reference to any async runtime, so this
+    /// may be used with any executor. All deadlines share one timer thread.
+    pub fn with_deadline(self, timeout: Duration) -> Deadline<T> {
+        Deadline {
+            future: self,
+            delay: util::delay(timeout),
+            timeout,
+        }
+    }
+
+    /// Arrange for `callback` to be called with the result of this future when
+    /// it completes, instead of awaiting or waiting on it. This is for
+    /// integration with event loops which are not based on Rust futures.
+    ///
+    /// The callback is called exactly once, on a driver I/O thread, or on this
+    /// thread if the future has already completed. It must not block, since
+    /// that stalls the driver's other requests; in particular, it must not wait
+    /// on another `CassFuture`. If it panics, the panic is caught and
+    /// discarded, since it cannot unwind into the driver.
+    ///
+    /// If the future has already been polled, a callback is already installed,
+    /// and `callback` is called immediately with `LIB_CALLBACK_ALREADY_SET`.
+    pub fn on_complete<F>(mut self, callback: F)
+    where
+        F: FnOnce(Result<T>) + Send + 'static,
+        T: 'static,
+    {
+        let completion = Box::new(Completion::<T> {
+            session: self.take_session(),
+            in_flight: self.in_flight.take(),
+            callback: Box::new(callback),
+        });
+        let data = Box::into_raw(completion) as *mut ::std::os::raw::c_void;
+        // Our reference to the driver future is freed when `self` is dropped,
+        // but the driver keeps the future alive until the callback is called.
+        let rc =
+            unsafe { cass_future_set_callback(self.inner, Some(notify_completion::<T>), data) };
+        if let Err(e) = rc.to_result(()) {
+            let completion = unsafe { Box::from_raw(data as *mut Completion<T>) };
+            (completion.callback)(Err(e));
+        }
+    }
+
+    /// Give up on the request, returning the error to report.
+    fn abandon(&self, timeout: Duration) -> Error {
+        if let Some(ref session) = self.session {
+            session.record_abandoned();
+        }
+        ErrorKind::Timeout(timeout).into()
+    }
+}
+
+/// A `CassFuture` which fails with `ErrorKind::Timeout` if it does not
+/// complete by a deadline. Created by `CassFuture::with_deadline`.
+///
+/// After the deadline the driver may still complete the underlying future and
+/// call back into Rust; the callback target is kept alive until then (see
+/// `FutureTarget`), so it is safe to drop a `Deadline` at any time.
+#[must_use]
+#[derive(Debug)]
+pub struct Deadline<T> {
+    future: CassFuture<T>,
+    delay: Delay,
+    timeout: Duration,
+}
+
+impl<T: Completable> Future for Deadline<T> {
+    type Output = Result<T>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        if let Poll::Ready(result) = Pin::new(&mut self.future).poll(cx) {
+            return Poll::Ready(result);
+        }
+        match Pin::new(&mut self.delay).poll(cx) {
+            Poll::Ready(()) => Poll::Ready(Err(self.future.abandon(self.timeout))),
+            Poll::Pending => Poll::Pending,
+        }
+    }
 }
 
 /// A Cassandra future is a normal Rust future.
@@ -335,9 +472,41 @@ enum FutureState {
     Ready,
 }
 
+/// The target of a callback installed by `CassFuture::on_complete`, owned by
+/// the driver until the callback is called.
+struct Completion<T> {
+    session: Session,
+    in_flight: Option<InFlight>,
+    callback: Box<dyn FnOnce(Result<T>) + Send>,
+}
+
+/// Callback which passes the result of a future to the closure given to
+/// `CassFuture::on_complete`. Called by the C++ driver exactly once, when the
+/// future is ready, with a pointer to the boxed `Completion`.
+unsafe extern "C" fn notify_completion<T: Completable>(
+    c_future: *mut _Future,
+    data: *mut ::std::os::raw::c_void,
+) {
+    let completion = Box::from_raw(data as *mut Completion<T>);
+    let Completion {
+        session,
+        in_flight,
+        callback,
+    } = *completion;
+    // A panic must not unwind into C.
+    let _ = panic::catch_unwind(AssertUnwindSafe(move || {
+        callback(get_completion(session, c_future))
+    }));
+    drop(in_flight);
+}
+
 /// Callback which wakes the task waiting on this future.
 /// Called by the C++ driver when the future is ready,
 /// with a pointer to the `CassFuture`.
+///
+/// The `CassFuture` may have been dropped by now, e.g. if it timed out, in
+/// which case the `keep_alive` reference in the state is the last reference
+/// to the target. The target must not be touched once that is dropped.
 unsafe extern "C" fn notify_task(_c_future: *mut _Future, data: *mut ::std::os::raw::c_void) {
     let future_target: &FutureTarget = &*(data as *const FutureTarget);
     // The future is now ready, so transition to the appropriate state.
@@ -345,10 +514,11 @@ unsafe extern "C" fn notify_task(_c_future: *mut _Future, data: *mut ::std::os::
         let mut lock = future_target.inner.lock();
         mem::replace(&mut *lock, FutureState::Ready)
     };
-    if let FutureState::Awaiting { ref waker, .. } = state {
-        waker.wake_by_ref();
-    } else {
-        // This can never happen.
-        panic!("Callback invoked before callback set");
+    // We are only called back once the callback has been set, i.e. in the
+    // `Awaiting` state. Panicking here would unwind into C, so any other state
+    // is ignored.
+    if let FutureState::Awaiting { waker, keep_alive } = state {
+        waker.wake();
+        drop(keep_alive);
     }
 }
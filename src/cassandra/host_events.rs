@@ -0,0 +1,188 @@
+use crate::cassandra::inet::Inet;
+use crate::cassandra::util::Protected;
+
+use crate::cassandra_sys::CassHostListenerEvent_;
+use crate::cassandra_sys::CassInet;
+
+use futures::channel::mpsc;
+use futures::Stream;
+use parking_lot::Mutex;
+use std::fmt;
+use std::os::raw::c_void;
+use std::panic::{self, AssertUnwindSafe};
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+/// The kind of a host event.
+#[derive(Debug, Eq, PartialEq, Copy, Clone, Hash)]
+pub enum HostEventKind {
+    /// The host was marked up.
+    Up,
+    /// The host was marked down.
+    Down,
+    /// The host was added to the cluster.
+    Added,
+    /// The host was removed from the cluster.
+    Removed,
+}
+
+enhance_nullary_enum!(HostEventKind, CassHostListenerEvent_, {
+    (Up, CASS_HOST_LISTENER_EVENT_UP, "UP"),
+    (Down, CASS_HOST_LISTENER_EVENT_DOWN, "DOWN"),
+    (Added, CASS_HOST_LISTENER_EVENT_ADD, "ADDED"),
+    (Removed, CASS_HOST_LISTENER_EVENT_REMOVE, "REMOVED"),
+});
+
+/// A change in the state of a host, or in the topology of the cluster.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct HostEvent {
+    /// What happened to the host.
+    pub kind: HostEventKind,
+    /// The address of the host.
+    pub address: Inet,
+}
+
+/// A listener for host events, which returns whether it wants more. Once it
+/// does not, or it panics, it is taken out, so that it is not called again
+/// before it is removed.
+type Listener = Arc<Mutex<Option<Box<dyn FnMut(&HostEvent) -> bool + Send>>>>;
+
+/// The listeners for the host events of a cluster, to which the driver's
+/// single host listener callback dispatches.
+///
+/// The driver never says when it has finished with the data passed to the
+/// callback, but it only calls it from sessions, so the listeners are shared
+/// by the cluster and each session connected from it, and freed after the
+/// last of them.
+pub(crate) struct HostListeners(Mutex<Vec<Listener>>);
+
+impl fmt::Debug for HostListeners {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "HostListeners({})", self.0.lock().len())
+    }
+}
+
+impl HostListeners {
+    /// Creates the listeners for a cluster.
+    pub(crate) fn new() -> Arc<HostListeners> {
+        Arc::new(HostListeners(Mutex::new(Vec::new())))
+    }
+
+    /// Adds a listener, which is called for every event until it returns
+    /// false.
+    pub(crate) fn add(&self, listener: impl FnMut(&HostEvent) -> bool + Send + 'static) {
+        self.0
+            .lock()
+            .push(Arc::new(Mutex::new(Some(Box::new(listener)))))
+    }
+
+    /// The data to pass to `notify_host_listeners`, which is valid as long as
+    /// the listeners are.
+    pub(crate) fn data(self: &Arc<Self>) -> *mut c_void {
+        Arc::as_ptr(self) as *mut c_void
+    }
+
+    /// Adds a listener which sends events to a stream.
+    pub(crate) fn stream(&self) -> HostEvents {
+        let (sender, receiver) = mpsc::unbounded();
+        self.add(move |event| sender.unbounded_send(*event).is_ok());
+        HostEvents(receiver)
+    }
+}
+
+/// Callback which passes a host event to each listener. Called by the C++
+/// driver on one of its IO threads, with a pointer to the `HostListeners`.
+pub(crate) unsafe extern "C" fn notify_host_listeners(
+    event: CassHostListenerEvent_,
+    address: CassInet,
+    data: *mut c_void,
+) {
+    let listeners = &*(data as *const HostListeners);
+    let event = HostEvent {
+        kind: HostEventKind::build(event),
+        address: Inet::build(address),
+    };
+    // The listeners are called without the list locked, so that they may
+    // add listeners.
+    let snapshot = listeners.0.lock().clone();
+    let mut finished = vec![];
+    for listener in snapshot {
+        let mut callback = listener.lock();
+        if let Some(call) = callback.as_mut() {
+            // A panic must not unwind into C, so a listener which panics is
+            // removed instead.
+            if !panic::catch_unwind(AssertUnwindSafe(|| call(&event))).unwrap_or(false) {
+                *callback = None;
+            }
+        }
+        if callback.is_none() {
+            drop(callback);
+            finished.push(listener);
+        }
+    }
+    if !finished.is_empty() {
+        listeners
+            .0
+            .lock()
+            .retain(|listener| !finished.iter().any(|done| Arc::ptr_eq(done, listener)));
+    }
+}
+
+/// A stream of host events, created by `Cluster::host_events`. It buffers
+/// events until they are read, and ends once the cluster and every session
+/// connected from it have been dropped.
+#[derive(Debug)]
+pub struct HostEvents(mpsc::UnboundedReceiver<HostEvent>);
+
+impl Stream for HostEvents {
+    type Item = HostEvent;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<HostEvent>> {
+        Pin::new(&mut self.0).poll_next(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn notify(listeners: &Arc<HostListeners>) {
+        let address = CassInet {
+            address: [127, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0],
+            address_length: 4,
+        };
+        unsafe {
+            notify_host_listeners(
+                CassHostListenerEvent_::CASS_HOST_LISTENER_EVENT_UP,
+                address,
+                listeners.data(),
+            )
+        }
+    }
+
+    #[test]
+    fn test_listener_adds_listener() {
+        let listeners = HostListeners::new();
+        let calls = Arc::new(Mutex::new(0));
+        let (inner, counted) = (listeners.clone(), calls.clone());
+        listeners.add(move |_| {
+            let counted = counted.clone();
+            inner.add(move |_| {
+                *counted.lock() += 1;
+                true
+            });
+            false
+        });
+        notify(&listeners);
+        assert_eq!(*calls.lock(), 0);
+        notify(&listeners);
+        assert_eq!(*calls.lock(), 1);
+        assert_eq!(listeners.0.lock().len(), 1);
+
+        listeners.add(|_| panic!("listener panicked"));
+        notify(&listeners);
+        assert_eq!(*calls.lock(), 2);
+        assert_eq!(listeners.0.lock().len(), 1);
+    }
+}
@@ -58,6 +58,11 @@ impl Default for Inet {
 }
 
 impl Inet {
+    /// The raw bytes of the address: 4 for IPv4, or 16 for IPv6.
+    pub(crate) fn bytes(&self) -> &[u8] {
+        &self.0.address[..self.0.address_length as usize]
+    }
+
     /// Constructs an inet v4 object.
     pub fn cass_inet_init_v4(address: &Ipv4Addr) -> Inet {
         unsafe { Inet(cass_inet_init_v4(address.octets().as_ptr())) }
@@ -0,0 +1,423 @@
+use crate::cassandra::error::*;
+use crate::cassandra::iterator::LendingIterator;
+use crate::cassandra::row::FromRow;
+use crate::cassandra::schema::column_meta::ColumnMeta;
+use crate::cassandra::schema::table_meta::TableMeta;
+use crate::cassandra::statement::Statement;
+use crate::cassandra::util::quote_identifier;
+use crate::Session;
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+
+use std::convert::TryInto;
+use std::fmt;
+use std::str::FromStr;
+
+/// The version of the `KeysetCursor` encoding.
+const CURSOR_VERSION: u8 = 1;
+
+/// The order of a clustering column within its partition.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ClusteringOrder {
+    /// Ascending order (the default).
+    Asc,
+    /// Descending order, i.e., `WITH CLUSTERING ORDER BY (... DESC)`.
+    Desc,
+}
+
+impl ClusteringOrder {
+    /// The relation selecting rows after a given value, in clustering order.
+    fn after(self) -> &'static str {
+        match self {
+            ClusteringOrder::Asc => ">",
+            ClusteringOrder::Desc => "<",
+        }
+    }
+}
+
+/// Pages through a partition by clustering key rather than by the driver's
+/// paging state.
+///
+/// Each page is read with a continuation query of the form
+///
+/// ```text
+/// SELECT * FROM ks.table WHERE pk = ? AND (ck1, ck2) > (?, ?) LIMIT n
+/// ```
+///
+/// where the clustering key values are those of the last row of the previous
+/// page, as recorded in a `KeysetCursor`. Unlike a paging state, such a cursor
+/// stays valid across schema changes and driver upgrades, and can be kept
+/// indefinitely.
+///
+/// If all clustering columns are in descending order the relation is `<`
+/// instead. A table with mixed clustering orders cannot be continued with a
+/// single relation, so the continuation is split into one query per
+/// clustering column, e.g., `ck1 = ? AND ck2 < ?` followed by `ck1 > ?`,
+/// which are executed in turn until the page is full.
+///
+/// Partition key values are bound by the caller: every continuation statement
+/// has the partition key columns as its first parameters, in order.
+#[derive(Debug, Clone)]
+pub struct KeysetPager {
+    keyspace: String,
+    table: String,
+    partition_key: Vec<String>,
+    clustering_key: Vec<(String, ClusteringOrder)>,
+    columns: Option<Vec<String>>,
+    page_size: usize,
+}
+
+/// The position of a `KeysetPager` within a partition: the clustering key of
+/// the last row seen, as raw CQL values.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct KeysetCursor(Vec<Vec<u8>>);
+
+impl KeysetPager {
+    /// Create a pager for the given table, returning up to `page_size` rows
+    /// per page. Fails with `NoClusteringKey` if the table has no clustering
+    /// columns.
+    pub fn new(table: &TableMeta, page_size: usize) -> Result<Self> {
+        let name = table.get_name();
+        let partition_key = (0..table.partition_key_count())
+            .filter_map(|index| table.partition_key(index))
+            .collect::<Vec<_>>();
+        let keyspace = match partition_key.first() {
+            Some(column) => column.text_field("keyspace_name")?,
+            None => None,
+        }
+        .ok_or_else(|| CassErrorCode::LIB_NULL_VALUE.to_error())?;
+
+        let mut clustering_key = vec![];
+        for index in 0..table.clustering_key_count() {
+            if let Some(column) = table.cluster_key(index) {
+                let order = match column.text_field("clustering_order")?.as_deref() {
+                    Some(order) if order.eq_ignore_ascii_case("desc") => ClusteringOrder::Desc,
+                    _ => ClusteringOrder::Asc,
+                };
+                clustering_key.push((column.name(), order));
+            }
+        }
+        if clustering_key.is_empty() {
+            return Err(ErrorKind::NoClusteringKey(name).into());
+        }
+
+        Ok(KeysetPager {
+            keyspace,
+            table: name,
+            partition_key: partition_key.iter().map(ColumnMeta::name).collect(),
+            clustering_key,
+            columns: None,
+            page_size,
+        })
+    }
+
+    /// Select only the given columns, rather than `*`. The clustering columns
+    /// are always selected, after these if not among them.
+    pub fn columns(mut self, columns: &[&str]) -> Self {
+        self.columns = Some(columns.iter().map(|c| c.to_string()).collect());
+        self
+    }
+
+    /// The clustering columns of the table and their order.
+    pub fn clustering_key(&self) -> &[(String, ClusteringOrder)] {
+        &self.clustering_key
+    }
+
+    fn select(&self) -> String {
+        match &self.columns {
+            None => "*".to_string(),
+            Some(columns) => {
+                let mut columns = columns.clone();
+                for (name, _) in &self.clustering_key {
+                    if !columns.contains(name) {
+                        columns.push(name.clone());
+                    }
+                }
+                columns
+                    .iter()
+                    .map(|c| quote_identifier(c))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            }
+        }
+    }
+
+    /// Build a query selecting the rows whose first `equal` clustering
+    /// columns match the cursor, with a relation `after` on the rest.
+    fn query(
+        &self,
+        equal: usize,
+        after: Option<&[(String, ClusteringOrder)]>,
+        limit: usize,
+    ) -> String {
+        let mut query = format!(
+            "SELECT {} FROM {}.{} WHERE ",
+            self.select(),
+            quote_identifier(&self.keyspace),
+            quote_identifier(&self.table)
+        );
+        let mut relations = self
+            .partition_key
+            .iter()
+            .chain(self.clustering_key[..equal].iter().map(|(name, _)| name))
+            .map(|name| format!("{} = ?", quote_identifier(name)))
+            .collect::<Vec<_>>();
+        if let Some(after) = after {
+            let names = after
+                .iter()
+                .map(|(name, _)| quote_identifier(name))
+                .collect::<Vec<_>>();
+            let relation = after[0].1.after();
+            relations.push(if names.len() == 1 {
+                format!("{} {} ?", names[0], relation)
+            } else {
+                format!(
+                    "({}) {} ({})",
+                    names.join(", "),
+                    relation,
+                    vec!["?"; names.len()].join(", ")
+                )
+            });
+        }
+        query.push_str(&relations.join(" AND "));
+        query.push_str(&format!(" LIMIT {}", limit));
+        query
+    }
+
+    /// The continuation statements for the page after `cursor`, or for the
+    /// first page if there is no cursor. The statements must be executed in
+    /// order until `page_size` rows have been read in total; the caller
+    /// must first bind the partition key to each.
+    pub fn statements(
+        &self,
+        session: &Session,
+        cursor: Option<&KeysetCursor>,
+    ) -> Result<Vec<Statement>> {
+        let cursor = match cursor {
+            None => return Ok(vec![session.statement(self.query(0, None, self.page_size))]),
+            Some(cursor) => cursor,
+        };
+        if cursor.0.len() != self.clustering_key.len() {
+            return Err(ErrorKind::InvalidPagingCursor(format!(
+                "expected {} clustering columns, got {}",
+                self.clustering_key.len(),
+                cursor.0.len()
+            ))
+            .into());
+        }
+
+        let first_order = self.clustering_key[0].1;
+        let uniform = self
+            .clustering_key
+            .iter()
+            .all(|(_, order)| *order == first_order);
+        let mut ranges = vec![];
+        if uniform {
+            ranges.push((0, &self.clustering_key[..]));
+        } else {
+            // Rows after the cursor either share its first `equal` columns
+            // and come after it in the next, or share fewer columns.
+            for equal in (0..self.clustering_key.len()).rev() {
+                ranges.push((equal, &self.clustering_key[equal..=equal]));
+            }
+        }
+
+        let offset = self.partition_key.len();
+        let mut statements = vec![];
+        for (equal, after) in ranges {
+            let mut statement = session.statement(self.query(equal, Some(after), self.page_size));
+            for (index, value) in cursor.0[..equal + after.len()].iter().enumerate() {
+                statement.bind_bytes(offset + index, value.clone())?;
+            }
+            statements.push(statement);
+        }
+        Ok(statements)
+    }
+
+    /// Read the page after `cursor`, or the first page if there is no cursor.
+    /// `bind_partition_key` is called to bind the partition key to each
+    /// statement executed.
+    ///
+    /// Returns the rows along with a cursor for the next page, or `None` if
+    /// this was the last page.
+    ///
+    /// ```no_run
+    /// # use cassandra_cpp::*;
+    /// # async fn test(session: Session) -> Result<()> {
+    /// let schema = session.get_schema_meta();
+    /// let keyspace = schema.get_keyspace_by_name("examples");
+    /// let table = keyspace.table_by_name("events").unwrap();
+    /// let pager = KeysetPager::new(&table, 100)?;
+    ///
+    /// let mut cursor = None;
+    /// loop {
+    ///     let (rows, next) = pager
+    ///         .execute_page::<(String, i64, String), _>(&session, cursor.as_ref(), |s| {
+    ///             s.bind(0, "sensor-1")?;
+    ///             Ok(())
+    ///         })
+    ///         .await?;
+    ///     // ... do something with `rows` ...
+    ///     match next {
+    ///         None => break,
+    ///         Some(next) => cursor = Some(next),
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn execute_page<T, F>(
+        &self,
+        session: &Session,
+        cursor: Option<&KeysetCursor>,
+        bind_partition_key: F,
+    ) -> Result<(Vec<T>, Option<KeysetCursor>)>
+    where
+        T: FromRow,
+        F: Fn(&mut Statement) -> Result<()>,
+    {
+        let mut rows = vec![];
+        let mut last = cursor.cloned();
+        for mut statement in self.statements(session, cursor)? {
+            if rows.len() == self.page_size {
+                break;
+            }
+            bind_partition_key(&mut statement)?;
+            let result = statement.execute().await?;
+            let mut iter = result.iter();
+            while let Some(row) = iter.next() {
+                if rows.len() == self.page_size {
+                    break;
+                }
+                rows.push(T::from_row(&row)?);
+                let key = self
+                    .clustering_key
+                    .iter()
+                    .map(|(name, _)| {
+                        let value = row.get_column_by_name(quote_identifier(name))?;
+                        Ok(value.get_bytes()?.to_vec())
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                last = Some(KeysetCursor(key));
+            }
+        }
+        let next = if rows.len() < self.page_size {
+            None
+        } else {
+            last
+        };
+        Ok((rows, next))
+    }
+}
+
+impl KeysetCursor {
+    /// The serialized values of the clustering columns of the last row seen.
+    pub fn values(&self) -> &[Vec<u8>] {
+        &self.0
+    }
+
+    /// Encode the cursor as a URL-safe string.
+    pub fn encode(&self) -> String {
+        let mut bytes = vec![CURSOR_VERSION];
+        for value in &self.0 {
+            bytes.extend_from_slice(&(value.len() as u32).to_be_bytes());
+            bytes.extend_from_slice(value);
+        }
+        URL_SAFE_NO_PAD.encode(bytes)
+    }
+
+    /// Decode a cursor previously encoded with `encode`.
+    pub fn decode(cursor: &str) -> Result<Self> {
+        let invalid =
+            |reason: &str| Error::from(ErrorKind::InvalidPagingCursor(reason.to_string()));
+        let bytes = URL_SAFE_NO_PAD
+            .decode(cursor)
+            .map_err(|e| invalid(&e.to_string()))?;
+        let mut rest = match bytes.split_first() {
+            Some((&CURSOR_VERSION, rest)) => rest,
+            Some((version, _)) => return Err(invalid(&format!("unsupported version {}", version))),
+            None => return Err(invalid("empty")),
+        };
+        let mut values = vec![];
+        while !rest.is_empty() {
+            if rest.len() < 4 {
+                return Err(invalid("truncated"));
+            }
+            let (len, tail) = rest.split_at(4);
+            let len = u32::from_be_bytes(len.try_into().unwrap()) as usize;
+            if tail.len() < len {
+                return Err(invalid("truncated"));
+            }
+            let (value, tail) = tail.split_at(len);
+            values.push(value.to_vec());
+            rest = tail;
+        }
+        Ok(KeysetCursor(values))
+    }
+}
+
+impl fmt::Display for KeysetCursor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.encode())
+    }
+}
+
+impl FromStr for KeysetCursor {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        KeysetCursor::decode(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pager(orders: &[ClusteringOrder]) -> KeysetPager {
+        KeysetPager {
+            keyspace: "ks".to_string(),
+            table: "events".to_string(),
+            partition_key: vec!["id".to_string()],
+            clustering_key: orders
+                .iter()
+                .enumerate()
+                .map(|(i, order)| (format!("ck{}", i + 1), *order))
+                .collect(),
+            columns: None,
+            page_size: 10,
+        }
+    }
+
+    #[test]
+    fn test_queries() {
+        use ClusteringOrder::*;
+        let asc = pager(&[Asc, Asc]);
+        assert_eq!(
+            asc.query(0, None, 10),
+            "SELECT * FROM \"ks\".\"events\" WHERE \"id\" = ? LIMIT 10"
+        );
+        assert_eq!(
+            asc.query(0, Some(&asc.clustering_key), 10),
+            "SELECT * FROM \"ks\".\"events\" WHERE \"id\" = ? AND (\"ck1\", \"ck2\") > (?, ?) LIMIT 10"
+        );
+        let desc = pager(&[Desc, Desc]);
+        assert_eq!(
+            desc.query(0, Some(&desc.clustering_key), 10),
+            "SELECT * FROM \"ks\".\"events\" WHERE \"id\" = ? AND (\"ck1\", \"ck2\") < (?, ?) LIMIT 10"
+        );
+        let mixed = pager(&[Asc, Desc]).columns(&["value"]);
+        assert_eq!(
+            mixed.query(1, Some(&mixed.clustering_key[1..]), 5),
+            "SELECT \"value\", \"ck1\", \"ck2\" FROM \"ks\".\"events\" WHERE \"id\" = ? AND \"ck1\" = ? AND \"ck2\" < ? LIMIT 5"
+        );
+    }
+
+    #[test]
+    fn test_cursor_round_trip() {
+        let cursor = KeysetCursor(vec![vec![], vec![0, 0, 0, 42], b"text".to_vec()]);
+        assert_eq!(cursor.encode().parse::<KeysetCursor>().unwrap(), cursor);
+        assert!(KeysetCursor::decode("AQAAAAU").is_err());
+    }
+}
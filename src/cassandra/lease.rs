@@ -0,0 +1,361 @@
+use crate::cassandra::consistency::Consistency;
+use crate::cassandra::error::*;
+use crate::cassandra::lwt::{self, LwtOutcome};
+use crate::cassandra::result::CassResult;
+use crate::cassandra::statement::{BindRustType, Statement};
+use crate::Session;
+
+use futures::channel::oneshot;
+use futures::future::{FutureExt, Shared};
+
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// A named lease (a distributed lock with an expiry) held by an owner, stored
+/// in a Cassandra table and maintained with lightweight transactions.
+///
+/// The table must have the schema created by `Lease::create_table`: a `text`
+/// primary key `name` and a `text` column `owner`. The lease row is written
+/// with a TTL, so a lease whose owner dies expires by itself.
+///
+/// When a lightweight transaction times out with an unknown outcome, the row is
+/// read back at `SERIAL` consistency, which completes any in-progress
+/// transaction, to find out whether the lease is held.
+///
+/// ```no_run
+/// # use cassandra_cpp::*;
+/// # use std::time::Duration;
+/// # async fn test(session: &Session) -> Result<()> {
+/// Lease::create_table(session, "ks.leases").await?;
+/// let ttl = Duration::from_secs(30);
+/// if let Some(lease) = Lease::acquire(session, "ks.leases", "nightly-job", "pod-1", ttl).await? {
+///     let guard = lease.guard();
+///     let lost = guard.lost();
+///     // ... run the job, checking `guard.is_held()` or selecting on `lost` ...
+///     guard.release().await?;
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Lease {
+    session: Session,
+    table: Arc<str>,
+    name: String,
+    owner: String,
+    ttl: Duration,
+}
+
+impl Lease {
+    /// Create a table suitable for storing leases, if it does not exist. The
+    /// name may be qualified with a keyspace.
+    pub async fn create_table(session: &Session, table: &str) -> Result<()> {
+        let query = format!(
+            "CREATE TABLE IF NOT EXISTS {} (name text PRIMARY KEY, owner text)",
+            table
+        );
+        session.execute(query).await?;
+        Ok(())
+    }
+
+    /// Try to acquire the lease `name` for `owner`, for `ttl` (rounded up to a
+    /// whole number of seconds). Returns `None` if the lease is held by another
+    /// owner. If `owner` already holds the lease, it is renewed.
+    pub async fn acquire(
+        session: &Session,
+        table: &str,
+        name: &str,
+        owner: &str,
+        ttl: Duration,
+    ) -> Result<Option<Lease>> {
+        let lease = Lease {
+            session: session.clone(),
+            table: table.into(),
+            name: name.to_string(),
+            owner: owner.to_string(),
+            ttl,
+        };
+        let mut statement = session.statement(format!(
+            "INSERT INTO {} (name, owner) VALUES (?, ?) IF NOT EXISTS USING TTL ?",
+            table
+        ));
+        statement.bind(0, name)?;
+        statement.bind(1, owner)?;
+        statement.bind(2, lease.ttl_seconds())?;
+        let held = match statement.execute_lwt().await? {
+            LwtOutcome::Applied => true,
+            LwtOutcome::NotApplied(existing) => {
+                let current = existing.get_column_by_name("owner")?;
+                !current.is_null() && current.get_str()? == owner && lease.renew().await?
+            }
+            LwtOutcome::Unknown(_) => lease.current_owner().await?.as_deref() == Some(owner),
+        };
+        Ok(if held { Some(lease) } else { None })
+    }
+
+    /// The name of the lease.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The owner holding the lease.
+    pub fn owner(&self) -> &str {
+        &self.owner
+    }
+
+    /// How long the lease lasts after each acquisition or renewal.
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    fn ttl_seconds(&self) -> i32 {
+        let seconds = self.ttl.as_secs() + u64::from(self.ttl.subsec_nanos() > 0);
+        seconds.clamp(1, i32::MAX as u64) as i32
+    }
+
+    /// Read the current owner of the lease at `SERIAL` consistency.
+    async fn current_owner(&self) -> Result<Option<String>> {
+        owner_of(self.current_owner_statement()?.execute().await?)
+    }
+
+    fn current_owner_statement(&self) -> Result<Statement> {
+        let mut statement = self
+            .session
+            .statement(format!("SELECT owner FROM {} WHERE name = ?", self.table));
+        statement.bind(0, self.name.as_str())?;
+        statement.set_consistency(Consistency::SERIAL)?;
+        Ok(statement)
+    }
+
+    /// Extend the lease by its TTL. Returns `false` if the lease is no longer
+    /// held by this owner, because it expired or was taken over.
+    ///
+    /// If the outcome of the renewal is unknown, this returns whether the lease
+    /// is still held, but the TTL may not have been extended.
+    pub async fn renew(&self) -> Result<bool> {
+        match self.renew_statement()?.execute_lwt().await? {
+            LwtOutcome::Applied => Ok(true),
+            LwtOutcome::NotApplied(_) => Ok(false),
+            LwtOutcome::Unknown(_) => {
+                Ok(self.current_owner().await?.as_deref() == Some(&self.owner))
+            }
+        }
+    }
+
+    /// Renew the lease as `renew` does, blocking until at most `deadline`,
+    /// after which it fails with `ErrorKind::Timeout`.
+    fn renew_by(&self, deadline: Instant) -> Result<bool> {
+        let remaining = || deadline.saturating_duration_since(Instant::now());
+        let result = self
+            .renew_statement()?
+            .execute_future()
+            .wait_timeout(remaining());
+        match lwt::outcome(result)? {
+            LwtOutcome::Applied => Ok(true),
+            LwtOutcome::NotApplied(_) => Ok(false),
+            LwtOutcome::Unknown(_) => {
+                let result = self
+                    .current_owner_statement()?
+                    .execute_future()
+                    .wait_timeout(remaining())?;
+                Ok(owner_of(result)?.as_deref() == Some(&self.owner))
+            }
+        }
+    }
+
+    fn renew_statement(&self) -> Result<Statement> {
+        let mut statement = self.session.statement(format!(
+            "UPDATE {} USING TTL ? SET owner = ? WHERE name = ? IF owner = ?",
+            self.table
+        ));
+        statement.bind(0, self.ttl_seconds())?;
+        statement.bind(1, self.owner.as_str())?;
+        statement.bind(2, self.name.as_str())?;
+        statement.bind(3, self.owner.as_str())?;
+        Ok(statement)
+    }
+
+    /// Release the lease, so another owner may acquire it. Returns `false` if
+    /// the lease was no longer held by this owner.
+    pub async fn release(&self) -> Result<bool> {
+        loop {
+            let mut statement = self.session.statement(format!(
+                "DELETE FROM {} WHERE name = ? IF owner = ?",
+                self.table
+            ));
+            statement.bind(0, self.name.as_str())?;
+            statement.bind(1, self.owner.as_str())?;
+            match statement.execute_lwt().await? {
+                LwtOutcome::Applied => return Ok(true),
+                LwtOutcome::NotApplied(_) => return Ok(false),
+                // The lease was ours; if it still is, the delete did not apply
+                // and we try again. Otherwise it applied, or the lease expired,
+                // which is just as good.
+                LwtOutcome::Unknown(_) => {
+                    if self.current_owner().await?.as_deref() != Some(&self.owner) {
+                        return Ok(true);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Keep the lease renewed in the background until the returned guard is
+    /// released or dropped.
+    ///
+    /// The lease is renewed every third of its TTL by a helper thread, so no
+    /// particular async runtime is needed. If a renewal finds the lease held by
+    /// another owner, or no renewal succeeds before the lease is about to
+    /// expire, the lease is lost: `LeaseGuard::is_held` becomes `false` and
+    /// `LeaseGuard::lost` completes. A renewal which hangs is abandoned at that
+    /// point, so the lease is reported lost before it can actually expire.
+    /// Dropping the guard releases the lease.
+    pub fn guard(self) -> LeaseGuard {
+        LeaseGuard::new(self)
+    }
+}
+
+/// The owner in the result of `Lease::current_owner_statement`.
+fn owner_of(result: CassResult) -> Result<Option<String>> {
+    match result.first_row() {
+        Some(row) => {
+            let owner = row.get_column(0)?;
+            if owner.is_null() {
+                Ok(None)
+            } else {
+                Ok(Some(owner.get_string()?))
+            }
+        }
+        None => Ok(None),
+    }
+}
+
+/// Why a `LeaseGuard` lost its lease.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LeaseLost {
+    /// A renewal found the lease held by another owner, or not held at all.
+    Taken,
+    /// No renewal succeeded before the lease's TTL, less a safety margin, had
+    /// passed since the last successful renewal.
+    Expired,
+}
+
+/// What the renewing thread should do.
+#[derive(Debug)]
+struct GuardState {
+    /// Set when the thread should stop, to whether it should release the lease.
+    stop: Mutex<Option<bool>>,
+    wake: Condvar,
+    held: AtomicBool,
+}
+
+impl GuardState {
+    fn stop(&self, release: bool) {
+        let mut stop = self.stop.lock().unwrap();
+        if stop.is_none() {
+            *stop = Some(release);
+            self.wake.notify_all();
+        }
+    }
+}
+
+/// A lease kept renewed in the background. Created by `Lease::guard`.
+#[derive(Debug)]
+pub struct LeaseGuard {
+    lease: Lease,
+    state: Arc<GuardState>,
+    lost: Shared<oneshot::Receiver<LeaseLost>>,
+}
+
+impl LeaseGuard {
+    fn new(lease: Lease) -> Self {
+        let state = Arc::new(GuardState {
+            stop: Mutex::new(None),
+            wake: Condvar::new(),
+            held: AtomicBool::new(true),
+        });
+        let (sender, receiver) = oneshot::channel();
+        let thread_lease = lease.clone();
+        let thread_state = state.clone();
+        thread::spawn(move || renew_until_stopped(thread_lease, thread_state, sender));
+        LeaseGuard {
+            lease,
+            state,
+            lost: receiver.shared(),
+        }
+    }
+
+    /// The lease being renewed.
+    pub fn lease(&self) -> &Lease {
+        &self.lease
+    }
+
+    /// Whether the lease is still believed to be held.
+    pub fn is_held(&self) -> bool {
+        self.state.held.load(Ordering::SeqCst)
+    }
+
+    /// A future which completes if the lease is lost, with the reason, or with
+    /// `None` once the guard is released or dropped while still holding it.
+    pub fn lost(&self) -> impl Future<Output = Option<LeaseLost>> + Send + 'static {
+        self.lost.clone().map(|lost| lost.ok())
+    }
+
+    /// Stop renewing the lease and release it. Returns `false` if the lease was
+    /// no longer held.
+    pub async fn release(self) -> Result<bool> {
+        self.state.stop(false);
+        self.lease.release().await
+    }
+}
+
+impl Drop for LeaseGuard {
+    /// Stops renewing the lease, and releases it in the background.
+    fn drop(&mut self) {
+        self.state.stop(true);
+    }
+}
+
+/// The body of a `LeaseGuard`'s renewing thread.
+fn renew_until_stopped(lease: Lease, state: Arc<GuardState>, lost: oneshot::Sender<LeaseLost>) {
+    // The TTL as written, which is at least a second, so this never spins.
+    let ttl = Duration::from_secs(lease.ttl_seconds() as u64);
+    let interval = ttl / 3;
+    // Consider the lease lost a little before it expires, so that its holder
+    // has time to stop before another owner can take it.
+    let lifetime = ttl - ttl / 10;
+    let mut expires = Instant::now() + lifetime;
+    let reason = loop {
+        let wait = interval.min(expires.saturating_duration_since(Instant::now()));
+        let stop = state.stop.lock().unwrap();
+        let (stop, _) = state
+            .wake
+            .wait_timeout_while(stop, wait, |stop| stop.is_none())
+            .unwrap();
+        if let Some(release) = *stop {
+            drop(stop);
+            if release {
+                let _ = futures::executor::block_on(lease.release());
+            }
+            return;
+        }
+        drop(stop);
+        if Instant::now() >= expires {
+            break LeaseLost::Expired;
+        }
+
+        let started = Instant::now();
+        match lease.renew_by(expires) {
+            Ok(true) => expires = started + lifetime,
+            Ok(false) => break LeaseLost::Taken,
+            Err(_) if Instant::now() >= expires => break LeaseLost::Expired,
+            // Try again at the next interval.
+            Err(_) => {}
+        }
+    };
+    state.held.store(false, Ordering::SeqCst);
+    let _ = lost.send(reason);
+}
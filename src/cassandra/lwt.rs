@@ -0,0 +1,134 @@
+use crate::cassandra::error::*;
+use crate::cassandra::result::CassResult;
+use crate::cassandra::row::Row;
+use crate::cassandra::value::Value;
+use crate::cassandra::write_type::WriteType;
+
+/// The name of the column in which Cassandra reports whether a conditional
+/// statement was applied.
+const APPLIED: &str = "[applied]";
+
+/// The outcome of a lightweight transaction. Returned by
+/// `Statement::execute_lwt`.
+#[derive(Debug)]
+pub enum LwtOutcome {
+    /// The condition held and the update was applied.
+    Applied,
+    /// The condition did not hold, so the update was not applied.
+    NotApplied(ExistingRow),
+    /// The request timed out, and the update may or may not have been applied.
+    /// Read the row at a serial consistency to find out.
+    Unknown(Error),
+}
+
+impl LwtOutcome {
+    /// Whether the update is known to have been applied.
+    pub fn is_applied(&self) -> bool {
+        matches!(self, LwtOutcome::Applied)
+    }
+}
+
+/// The current values of the row a lightweight transaction did not apply to,
+/// as returned by Cassandra alongside the `[applied]` column.
+///
+/// Which columns are present depends on the statement: `IF NOT EXISTS` returns
+/// the whole existing row, while `IF col = ?` returns only the columns in the
+/// condition, and nothing else if the row does not exist.
+#[derive(Debug)]
+pub struct ExistingRow(CassResult);
+
+impl ExistingRow {
+    /// Whether Cassandra returned any values for the row, i.e., whether the row
+    /// exists.
+    pub fn exists(&self) -> bool {
+        self.0.column_count() > 1
+    }
+
+    /// The row as returned by Cassandra, with the `[applied]` column first.
+    pub fn row(&self) -> Row {
+        self.0.first_row().expect("checked in lwt::outcome")
+    }
+
+    /// Get the value of the given column of the existing row.
+    pub fn get_column_by_name(&self, name: &str) -> Result<Value> {
+        self.row().get_column_by_name(name)
+    }
+
+    /// The underlying result.
+    pub fn into_result(self) -> CassResult {
+        self.0
+    }
+}
+
+/// Whether an error means a lightweight transaction may or may not have been
+/// applied: the coordinator timed out during the compare-and-set, or the
+/// client stopped waiting for the response, either in the driver or with
+/// `CassFuture::wait_timeout` or `CassFuture::with_deadline`.
+pub(crate) fn is_unknown(err: &Error) -> bool {
+    match err.kind() {
+        ErrorKind::CassErrorResult(code, _, _, _, _, _, _, write_type, ..) => {
+            *code == CassErrorCode::SERVER_WRITE_TIMEOUT && *write_type == WriteType::CAS
+        }
+        ErrorKind::CassError(code, _) => *code == CassErrorCode::LIB_REQUEST_TIMED_OUT,
+        ErrorKind::Timeout(_) => true,
+        _ => false,
+    }
+}
+
+/// Interpret the result of a conditional statement.
+pub(crate) fn outcome(result: Result<CassResult>) -> Result<LwtOutcome> {
+    let result = match result {
+        Ok(result) => result,
+        Err(err) if is_unknown(&err) => return Ok(LwtOutcome::Unknown(err)),
+        Err(err) => return Err(err),
+    };
+    let applied = match result.first_row() {
+        Some(row) if result.column_name(0).ok() == Some(APPLIED) => {
+            row.get_column(0)?.get_bool()?
+        }
+        _ => return Err(ErrorKind::NotConditional.into()),
+    };
+    if applied {
+        Ok(LwtOutcome::Applied)
+    } else {
+        Ok(LwtOutcome::NotApplied(ExistingRow(result)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cassandra::consistency::Consistency;
+
+    fn write_timeout(write_type: WriteType) -> Error {
+        ErrorKind::CassErrorResult(
+            CassErrorCode::SERVER_WRITE_TIMEOUT,
+            "timeout".to_string(),
+            Consistency::QUORUM,
+            1,
+            2,
+            0,
+            false,
+            write_type,
+            None,
+            None,
+            None,
+        )
+        .into()
+    }
+
+    #[test]
+    fn test_is_unknown() {
+        assert!(is_unknown(&write_timeout(WriteType::CAS)));
+        assert!(!is_unknown(&write_timeout(WriteType::SIMPLE)));
+        assert!(is_unknown(&CassErrorCode::LIB_REQUEST_TIMED_OUT.to_error()));
+        assert!(is_unknown(
+            &ErrorKind::Timeout(std::time::Duration::from_secs(1)).into()
+        ));
+        assert!(!is_unknown(&CassErrorCode::SERVER_INVALID_QUERY.to_error()));
+        assert!(matches!(
+            outcome(Err(write_timeout(WriteType::CAS))),
+            Ok(LwtOutcome::Unknown(_))
+        ));
+    }
+}
@@ -0,0 +1,313 @@
+use crate::cassandra::error::*;
+use crate::cassandra::future::CassFuture;
+use crate::cassandra::iterator::LendingIterator;
+use crate::cassandra::result::CassResult;
+use crate::cassandra::row::FromRow;
+use crate::cassandra::statement::Statement;
+use crate::cassandra::util::ProtectedInner;
+
+use crate::cassandra_sys::cass_session_execute;
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use futures::Stream;
+
+use std::collections::VecDeque;
+use std::convert::TryInto;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::str::FromStr;
+use std::task::{Context, Poll};
+
+/// A stream of the rows returned by a statement, across all pages of the
+/// result. Created by `Statement::into_row_stream`.
+///
+/// Each row is converted with `FromRow` as its page arrives, so the stream
+/// yields owned values. The page size is taken from the statement (see
+/// `Statement::set_paging_size`).
+///
+/// By default the next page is only requested once the current page has been
+/// consumed. Use `prefetch` to request pages ahead of the consumer; the stream
+/// never holds more than the current page, the prefetched pages, and a single
+/// request in flight.
+///
+/// The stream ends after yielding the first error.
+#[must_use]
+pub struct RowStream<T> {
+    statement: Statement,
+    in_flight: Option<CassFuture<CassResult>>,
+    fetched: VecDeque<CassResult>,
+    rows: VecDeque<Result<T>>,
+    prefetch: usize,
+    more_pages: bool,
+    failed: bool,
+}
+
+// We never project a pin onto the buffered rows.
+impl<T> Unpin for RowStream<T> {}
+
+impl<T> fmt::Debug for RowStream<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("RowStream")
+            .field("statement", &self.statement)
+            .field("in_flight", &self.in_flight.is_some())
+            .field("fetched", &self.fetched.len())
+            .field("rows", &self.rows.len())
+            .field("prefetch", &self.prefetch)
+            .field("more_pages", &self.more_pages)
+            .finish()
+    }
+}
+
+impl<T: FromRow> RowStream<T> {
+    pub(crate) fn new(statement: Statement) -> Self {
+        RowStream {
+            statement,
+            in_flight: None,
+            fetched: VecDeque::new(),
+            rows: VecDeque::new(),
+            prefetch: 0,
+            more_pages: true,
+            failed: false,
+        }
+    }
+
+    /// Sets the number of pages to fetch ahead of the page currently being
+    /// consumed. The default is 0, i.e., no prefetching.
+    pub fn prefetch(mut self, pages: usize) -> Self {
+        self.prefetch = pages;
+        self
+    }
+
+    /// Whether another page should be requested now.
+    fn wants_page(&self) -> bool {
+        self.in_flight.is_none()
+            && self.more_pages
+            && (self.fetched.len() < self.prefetch
+                || (self.fetched.is_empty() && self.rows.is_empty()))
+    }
+
+    fn execute(&self) -> CassFuture<CassResult> {
+        let session = self.statement.session();
+        let inner = unsafe { cass_session_execute(session.inner(), self.statement.inner()) };
+        CassFuture::build(session.clone(), inner)
+    }
+
+    /// Record a newly-arrived page, and set up the statement to fetch the next.
+    fn on_page(&mut self, result: CassResult) -> Result<()> {
+        match result.paging_state_token()? {
+            Some(token) => {
+                self.statement.set_paging_state_token(&token)?;
+            }
+            None => self.more_pages = false,
+        }
+        self.fetched.push_back(result);
+        Ok(())
+    }
+
+    /// Convert the rows of a page, stopping at the first failure.
+    fn decode(&mut self, result: CassResult) {
+        let mut iter = result.iter();
+        while let Some(row) = iter.next() {
+            let row = T::from_row(&row);
+            let failed = row.is_err();
+            self.rows.push_back(row);
+            if failed {
+                break;
+            }
+        }
+    }
+
+    fn fail(&mut self, err: Error) -> Poll<Option<Result<T>>> {
+        self.failed = true;
+        self.in_flight = None;
+        self.fetched.clear();
+        self.rows.clear();
+        Poll::Ready(Some(Err(err)))
+    }
+}
+
+impl<T: FromRow> Stream for RowStream<T> {
+    type Item = Result<T>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Result<T>>> {
+        let this = &mut *self;
+        if this.failed {
+            return Poll::Ready(None);
+        }
+        loop {
+            if let Some(future) = this.in_flight.as_mut() {
+                if let Poll::Ready(result) = Pin::new(future).poll(cx) {
+                    this.in_flight = None;
+                    if let Err(err) = result.and_then(|result| this.on_page(result)) {
+                        return this.fail(err);
+                    }
+                    continue;
+                }
+            }
+            if this.wants_page() {
+                // Loop round to poll the new request, so that we are woken when
+                // it completes.
+                this.in_flight = Some(this.execute());
+                continue;
+            }
+            if let Some(row) = this.rows.pop_front() {
+                return match row {
+                    Ok(row) => Poll::Ready(Some(Ok(row))),
+                    Err(err) => this.fail(err),
+                };
+            }
+            if let Some(result) = this.fetched.pop_front() {
+                this.decode(result);
+                continue;
+            }
+            return if this.in_flight.is_some() {
+                Poll::Pending
+            } else {
+                Poll::Ready(None)
+            };
+        }
+    }
+}
+
+/// The version of the `PagingCursor` encoding.
+const CURSOR_VERSION: u8 = 1;
+
+/// An opaque, resumable position within the result of a statement, suitable
+/// for handing out to API clients. Obtained from `Statement::execute_page`.
+///
+/// The cursor encodes the driver's paging state as URL-safe base64, together
+/// with a fingerprint of the query text and bound values of the statement it
+/// came from. Using the cursor with a statement that has a different
+/// fingerprint fails with `PagingCursorMismatch`, so a cursor cannot
+/// accidentally be replayed against a different query. The statement's values
+/// must be bound after `Statement::record_bindings`, or it fails with
+/// `BindingsNotRecorded`. Statements binding a collection, tuple or user type
+/// cannot be fingerprinted, so cannot be paged with a cursor; they fail with
+/// `PagingCursorUnsupported`.
+///
+/// The fingerprint is not a cryptographic signature: it does not stop a
+/// malicious client from forging a cursor. Sign or encrypt cursors if they
+/// come from untrusted environments.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PagingCursor {
+    fingerprint: u64,
+    paging_state: Vec<u8>,
+}
+
+impl PagingCursor {
+    /// Encode the cursor as a URL-safe string.
+    pub fn encode(&self) -> String {
+        let mut bytes = Vec::with_capacity(9 + self.paging_state.len());
+        bytes.push(CURSOR_VERSION);
+        bytes.extend_from_slice(&self.fingerprint.to_be_bytes());
+        bytes.extend_from_slice(&self.paging_state);
+        URL_SAFE_NO_PAD.encode(bytes)
+    }
+
+    /// Decode a cursor previously encoded with `encode`.
+    pub fn decode(cursor: &str) -> Result<Self> {
+        let bytes = URL_SAFE_NO_PAD
+            .decode(cursor)
+            .map_err(|e| ErrorKind::InvalidPagingCursor(e.to_string()))?;
+        match bytes.split_first() {
+            Some((&CURSOR_VERSION, rest)) if rest.len() > 8 => {
+                let (fingerprint, paging_state) = rest.split_at(8);
+                Ok(PagingCursor {
+                    fingerprint: u64::from_be_bytes(fingerprint.try_into().unwrap()),
+                    paging_state: paging_state.to_vec(),
+                })
+            }
+            Some((&CURSOR_VERSION, _)) => {
+                Err(ErrorKind::InvalidPagingCursor("too short".to_string()).into())
+            }
+            Some((version, _)) => Err(ErrorKind::InvalidPagingCursor(format!(
+                "unsupported version {}",
+                version
+            ))
+            .into()),
+            None => Err(ErrorKind::InvalidPagingCursor("empty".to_string()).into()),
+        }
+    }
+
+    /// Resume paging of `statement` from this cursor.
+    fn apply(&self, statement: &mut Statement) -> Result<()> {
+        if self.fingerprint != statement.fingerprint()? {
+            return Err(ErrorKind::PagingCursorMismatch.into());
+        }
+        statement.set_paging_state_token(&self.paging_state)?;
+        Ok(())
+    }
+}
+
+impl fmt::Display for PagingCursor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.encode())
+    }
+}
+
+impl FromStr for PagingCursor {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        PagingCursor::decode(s)
+    }
+}
+
+/// Execute a single page of `statement`, starting from `cursor` if given.
+pub(crate) async fn execute_page<T: FromRow>(
+    mut statement: Statement,
+    cursor: Option<&PagingCursor>,
+    page_size: i32,
+) -> Result<(Vec<T>, Option<PagingCursor>)> {
+    // Take the fingerprint before setting any paging options, which do not
+    // affect it anyway.
+    let fingerprint = statement.fingerprint()?;
+    if let Some(cursor) = cursor {
+        cursor.apply(&mut statement)?;
+    }
+    statement.set_paging_size(page_size)?;
+    let result = statement.execute().await?;
+
+    let mut rows = Vec::with_capacity(result.row_count() as usize);
+    let mut iter = result.iter();
+    while let Some(row) = iter.next() {
+        rows.push(T::from_row(&row)?);
+    }
+    let next = result
+        .paging_state_token()?
+        .map(|paging_state| PagingCursor {
+            fingerprint,
+            paging_state,
+        });
+    Ok((rows, next))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cursor_round_trip() {
+        let cursor = PagingCursor {
+            fingerprint: 0x0123_4567_89ab_cdef,
+            paging_state: vec![0, 1, 2, 253, 254, 255],
+        };
+        let encoded = cursor.encode();
+        assert!(encoded
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'));
+        assert_eq!(encoded.parse::<PagingCursor>().unwrap(), cursor);
+    }
+
+    #[test]
+    fn test_cursor_invalid() {
+        for cursor in &["", "not base64!", "AQ", "AgEjRWeJq83vAA"] {
+            match PagingCursor::decode(cursor) {
+                Err(Error(ErrorKind::InvalidPagingCursor(_), _)) => {}
+                other => panic!("Unexpected result for {:?}: {:?}", cursor, other),
+            }
+        }
+    }
+}
@@ -1,6 +1,11 @@
+use crate::cassandra::cql_value::ToCqlValue;
+use crate::cassandra::data_type::DataType;
 use crate::cassandra::error::*;
+use crate::cassandra::query_meta::{QueryMeta, ResultColumn};
 use crate::cassandra::statement::Statement;
+use crate::cassandra::type_check::{CqlType, Parameter};
 use crate::cassandra::util::{Protected, ProtectedInner, ProtectedWithSession};
+use crate::cassandra::value::ValueType;
 use crate::{cassandra::data_type::ConstDataType, Session};
 
 use crate::cassandra_sys::cass_prepared_bind;
@@ -10,12 +15,23 @@ use crate::cassandra_sys::cass_prepared_parameter_data_type_by_name_n;
 use crate::cassandra_sys::cass_prepared_parameter_name;
 use crate::cassandra_sys::CassPrepared as _PreparedStatement;
 use std::os::raw::c_char;
+use std::sync::{Arc, OnceLock};
 use std::{slice, str};
 
 /// A statement that has been prepared against at least one Cassandra node.
 /// Instances of this class should not be created directly, but through Session.prepare().
 #[derive(Debug)]
-pub struct PreparedStatement(*const _PreparedStatement, Session);
+pub struct PreparedStatement {
+    inner: *const _PreparedStatement,
+    /// The session the statement was prepared on.
+    session: Session,
+    /// The query text the statement was prepared from.
+    query: Arc<str>,
+    /// The names and types of the parameters, shared with bound statements.
+    parameters: Arc<[Parameter]>,
+    /// What is derived from the query text and the schema, once asked for.
+    meta: OnceLock<QueryMeta>,
+}
 
 unsafe impl Send for PreparedStatement {}
 unsafe impl Sync for PreparedStatement {}
@@ -23,14 +39,14 @@ unsafe impl Sync for PreparedStatement {}
 impl Drop for PreparedStatement {
     /// Frees a prepared statement
     fn drop(&mut self) {
-        unsafe { cass_prepared_free(self.0) }
+        unsafe { cass_prepared_free(self.inner) }
     }
 }
 
 impl ProtectedInner<*const _PreparedStatement> for PreparedStatement {
     #[inline(always)]
     fn inner(&self) -> *const _PreparedStatement {
-        self.0
+        self.inner
     }
 }
 
@@ -40,19 +56,122 @@ impl ProtectedWithSession<*const _PreparedStatement> for PreparedStatement {
         if inner.is_null() {
             panic!("Unexpected null pointer")
         };
-        PreparedStatement(inner, session)
+        let mut prepared = PreparedStatement {
+            inner,
+            session,
+            query: "".into(),
+            parameters: Arc::new([]),
+            meta: OnceLock::new(),
+        };
+        // The driver returns null for the type of a parameter past the end.
+        let parameters = (0..)
+            .take_while(|&index| unsafe {
+                !cass_prepared_parameter_data_type(inner, index).is_null()
+            })
+            .map(|index| Parameter {
+                name: prepared.parameter_name(index).unwrap_or("").to_string(),
+                data_type: CqlType::from_data_type(&prepared.parameter_data_type(index)),
+            })
+            .collect();
+        prepared.parameters = parameters;
+        prepared
     }
 
     #[inline(always)]
     fn session(&self) -> &Session {
-        &self.1
+        &self.session
     }
 }
 
 impl PreparedStatement {
     /// Creates a bound statement from a pre-prepared statement.
     pub fn bind(&self) -> Statement {
-        unsafe { Statement::build(cass_prepared_bind(self.inner()), self.session().clone()) }
+        let mut statement =
+            unsafe { Statement::build(cass_prepared_bind(self.inner()), self.session().clone()) };
+        statement.set_query(self.query.clone());
+        statement.set_parameters(self.parameters.clone());
+        statement
+    }
+
+    /// Creates a bound statement and binds all its parameters, in order. See
+    /// `Statement::bind_all`.
+    pub fn bind_with(&self, values: &[&dyn ToCqlValue]) -> Result<Statement> {
+        let mut statement = self.bind();
+        statement.bind_all(values)?;
+        Ok(statement)
+    }
+
+    /// Records the query text this statement was prepared from.
+    pub(crate) fn with_query(mut self, query: &str) -> Self {
+        self.query = query.into();
+        self
+    }
+
+    /// What we can derive from the query text and the schema, which is only
+    /// worked out when first asked for, since few callers need it.
+    fn meta(&self) -> &QueryMeta {
+        self.meta.get_or_init(|| {
+            let schema = self.session.get_schema_meta();
+            let keyspace = self.session.keyspace();
+            QueryMeta::derive(&self.query, keyspace, &schema, &self.parameters)
+        })
+    }
+
+    /// The query text this statement was prepared from.
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+
+    /// Gets the number of parameters.
+    pub fn parameter_count(&self) -> usize {
+        self.parameters.len()
+    }
+
+    /// Gets the number of columns in the rows the statement returns, or `None`
+    /// if they are not known.
+    ///
+    /// The driver does not expose the result metadata of a prepared statement,
+    /// so it is derived from the query text and the schema metadata the first
+    /// time any result column or the partition key indices are asked for. This
+    /// is done for `SELECT` statements whose selectors are columns, `*`,
+    /// `count`, `writetime` or `ttl`, and for `INSERT`, `UPDATE` and `DELETE`
+    /// statements, which return no rows unless they are conditional. A table
+    /// not qualified with its keyspace is looked up in the keyspace the session
+    /// was connected to.
+    pub fn result_column_count(&self) -> Option<usize> {
+        self.meta().result_columns.as_ref().map(Vec::len)
+    }
+
+    /// Gets the name of the result column at the specified index, if known.
+    /// See `result_column_count`.
+    pub fn result_column_name(&self, index: usize) -> Option<&str> {
+        self.result_column(index).map(|column| column.name.as_str())
+    }
+
+    /// Gets the value type of the result column at the specified index, if
+    /// known. See `result_column_count`.
+    pub fn result_column_type(&self, index: usize) -> Option<ValueType> {
+        self.result_column(index).map(|column| column.value_type)
+    }
+
+    /// Gets the data type of the result column at the specified index, if
+    /// known. See `result_column_count`.
+    pub fn result_column_data_type(&self, index: usize) -> Option<&DataType> {
+        self.result_column(index).map(|column| &column.data_type)
+    }
+
+    fn result_column(&self, index: usize) -> Option<&ResultColumn> {
+        self.meta().result_columns.as_ref()?.get(index)
+    }
+
+    /// Gets the indices of the parameters which give the partition key, in
+    /// partition key order, as used to route the statement to a replica. Like
+    /// the result columns, these are derived from the schema metadata. Returns
+    /// `None` if they are not known, or if some partition key column is not
+    /// bound by a parameter of its own, e.g., it is given by a literal or with
+    /// `IN`.
+    pub fn partition_key_indices(&self) -> Option<&[usize]> {
+        self.meta().partition_key_indices.as_deref()
     }
 
     /// Returns the session of which this prepared statement is bound to.
@@ -65,7 +184,7 @@ impl PreparedStatement {
         let mut name = std::ptr::null();
         let mut name_length = 0;
         unsafe {
-            cass_prepared_parameter_name(self.0, index, &mut name, &mut name_length)
+            cass_prepared_parameter_name(self.inner, index, &mut name, &mut name_length)
                 .to_result(())
                 .and_then(|_| {
                     Ok(str::from_utf8(slice::from_raw_parts(
@@ -80,7 +199,7 @@ impl PreparedStatement {
     ///
     /// Returns a reference to the data type of the parameter.
     pub fn parameter_data_type(&self, index: usize) -> ConstDataType {
-        unsafe { ConstDataType::build(cass_prepared_parameter_data_type(self.0, index)) }
+        unsafe { ConstDataType::build(cass_prepared_parameter_data_type(self.inner, index)) }
     }
 
     /// Gets the data type of a parameter for the specified name.
@@ -90,7 +209,7 @@ impl PreparedStatement {
         unsafe {
             let name_ptr = name.as_ptr() as *const c_char;
             ConstDataType::build(cass_prepared_parameter_data_type_by_name_n(
-                self.0,
+                self.inner,
                 name_ptr,
                 name.len(),
             ))
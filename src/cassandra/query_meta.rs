@@ -0,0 +1,415 @@
+use crate::cassandra::data_type::DataType;
+use crate::cassandra::schema::schema_meta::SchemaMeta;
+use crate::cassandra::schema::table_meta::TableMeta;
+use crate::cassandra::type_check::Parameter;
+use crate::cassandra::value::ValueType;
+use crate::cassandra_sys::CassColumnType;
+
+/// A column of the rows returned by a prepared statement.
+#[derive(Debug)]
+pub(crate) struct ResultColumn {
+    pub(crate) name: String,
+    pub(crate) value_type: ValueType,
+    pub(crate) data_type: DataType,
+}
+
+/// What the driver does not tell us about a prepared statement, derived from
+/// its query text and the schema metadata.
+#[derive(Debug, Default)]
+pub(crate) struct QueryMeta {
+    /// The columns of the rows the statement returns, if they could be derived.
+    pub(crate) result_columns: Option<Vec<ResultColumn>>,
+    /// The index of the parameter giving each partition key column, if every
+    /// partition key column is bound by a parameter.
+    pub(crate) partition_key_indices: Option<Vec<usize>>,
+}
+
+impl QueryMeta {
+    /// Derive what we can about a prepared statement. Only simple `SELECT`,
+    /// `INSERT`, `UPDATE` and `DELETE` statements are understood; for anything
+    /// else, nothing is derived. A table not qualified with its keyspace is
+    /// taken to be in `keyspace`, the keyspace of the session.
+    pub(crate) fn derive(
+        query: &str,
+        keyspace: Option<&str>,
+        schema: &SchemaMeta,
+        parameters: &[Parameter],
+    ) -> Self {
+        let tokens = tokenize(query);
+        let parsed = match parse(&tokens) {
+            Some(parsed) => parsed,
+            None => return QueryMeta::default(),
+        };
+        let keyspace = match parsed.keyspace.as_deref().or(keyspace) {
+            Some(keyspace) => keyspace,
+            None => return QueryMeta::default(),
+        };
+        let keyspace = match schema.keyspace_by_name(keyspace) {
+            Some(keyspace) => keyspace,
+            None => return QueryMeta::default(),
+        };
+        let table = match keyspace.table_by_name(&parsed.table) {
+            Some(table) => table,
+            None => return QueryMeta::default(),
+        };
+
+        let result_columns = match parsed.selectors {
+            Selectors::None => Some(vec![]),
+            Selectors::Unknown => None,
+            Selectors::Json => Some(vec![scalar_column("[json]", ValueType::TEXT)]),
+            Selectors::List(selectors) => selectors
+                .iter()
+                .map(|selector| result_columns(selector, &table))
+                .collect::<Option<Vec<_>>>()
+                .map(|columns| columns.into_iter().flatten().collect()),
+        };
+        let partition_key_indices = (0..table.partition_key_count())
+            .map(|index| {
+                let name = table.partition_key(index)?.name();
+                parameters.iter().position(|p| p.name == name)
+            })
+            .collect();
+        QueryMeta {
+            result_columns,
+            partition_key_indices,
+        }
+    }
+}
+
+fn scalar_column(name: &str, value_type: ValueType) -> ResultColumn {
+    ResultColumn {
+        name: name.to_string(),
+        value_type,
+        data_type: DataType::new(value_type),
+    }
+}
+
+/// The result columns a selector produces, or `None` if it is not understood.
+fn result_columns(selector: &[Token], table: &TableMeta) -> Option<Vec<ResultColumn>> {
+    let (selector, alias) = match selector {
+        [selector @ .., Token::Word(as_), alias] if as_ == "as" => (selector, identifier(alias)),
+        _ => (selector, None),
+    };
+    let column = match selector {
+        [Token::Symbol('*')] if alias.is_none() => return Some(all_columns(table)),
+        [column] => {
+            let (name, value_type, data_type) = table_column(table, &identifier(column)?)?;
+            ResultColumn {
+                name,
+                value_type,
+                data_type,
+            }
+        }
+        [Token::Word(function), Token::Symbol('('), argument, Token::Symbol(')')] => {
+            let (name, value_type) = match function.as_str() {
+                // Only counting rows is named plainly; counting a column's
+                // values is named after the function's qualified name.
+                "count" => match argument {
+                    Token::Symbol('*') => ("count".to_string(), ValueType::BIGINT),
+                    Token::Word(one) if one == "1" => ("count".to_string(), ValueType::BIGINT),
+                    _ => (
+                        format!("system.count({})", identifier(argument)?),
+                        ValueType::BIGINT,
+                    ),
+                },
+                "writetime" => (
+                    format!("writetime({})", identifier(argument)?),
+                    ValueType::BIGINT,
+                ),
+                "ttl" => (format!("ttl({})", identifier(argument)?), ValueType::INT),
+                _ => return None,
+            };
+            scalar_column(&name, value_type)
+        }
+        _ => return None,
+    };
+    Some(vec![ResultColumn {
+        name: alias.unwrap_or(column.name),
+        ..column
+    }])
+}
+
+/// The columns of `SELECT *`: the partition key, the clustering key, the static
+/// columns by name, then the regular columns by name.
+fn all_columns(table: &TableMeta) -> Vec<ResultColumn> {
+    let keys: Vec<String> = (0..table.partition_key_count())
+        .filter_map(|index| table.partition_key(index))
+        .chain((0..table.clustering_key_count()).filter_map(|index| table.cluster_key(index)))
+        .map(|column| column.name())
+        .collect();
+    let mut others: Vec<(bool, String)> = (0..table.column_count())
+        .map(|index| table.column(index))
+        .map(|column| {
+            let regular = column.get_type() != CassColumnType::CASS_COLUMN_TYPE_STATIC;
+            (regular, column.name())
+        })
+        .filter(|(_, name)| !keys.contains(name))
+        .collect();
+    others.sort();
+    keys.into_iter()
+        .chain(others.into_iter().map(|(_, name)| name))
+        .filter_map(|name| {
+            let (name, value_type, data_type) = table_column(table, &name)?;
+            Some(ResultColumn {
+                name,
+                value_type,
+                data_type,
+            })
+        })
+        .collect()
+}
+
+fn table_column(table: &TableMeta, name: &str) -> Option<(String, ValueType, DataType)> {
+    (0..table.column_count())
+        .map(|index| table.column(index))
+        .find(|column| column.name() == name)
+        .map(|column| {
+            let data_type = column.data_type();
+            (
+                column.name(),
+                data_type.get_type(),
+                data_type.new_from_existing(),
+            )
+        })
+}
+
+/// A token of CQL, as far as we need to understand it.
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    /// An unquoted identifier, keyword or number, lower-cased.
+    Word(String),
+    /// A double-quoted identifier.
+    Quoted(String),
+    /// A string literal.
+    Literal,
+    /// Any other character.
+    Symbol(char),
+}
+
+/// The name of an identifier token: unquoted identifiers are case-insensitive.
+fn identifier(token: &Token) -> Option<String> {
+    match token {
+        Token::Word(word) => Some(word.clone()),
+        Token::Quoted(name) => Some(name.clone()),
+        _ => None,
+    }
+}
+
+fn tokenize(query: &str) -> Vec<Token> {
+    let mut tokens = vec![];
+    let mut chars = query.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {}
+            '-' if chars.peek() == Some(&'-') => {
+                chars.by_ref().find(|&c| c == '\n');
+            }
+            '/' if chars.peek() == Some(&'/') => {
+                chars.by_ref().find(|&c| c == '\n');
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut previous = ' ';
+                for c in chars.by_ref() {
+                    if previous == '*' && c == '/' {
+                        break;
+                    }
+                    previous = c;
+                }
+            }
+            '\'' | '"' => {
+                // A doubled quote within quotes is an escaped quote.
+                let mut text = String::new();
+                while let Some(next) = chars.next() {
+                    if next == c {
+                        if chars.peek() == Some(&c) {
+                            chars.next();
+                        } else {
+                            break;
+                        }
+                    }
+                    text.push(next);
+                }
+                tokens.push(if c == '"' {
+                    Token::Quoted(text)
+                } else {
+                    Token::Literal
+                });
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                let mut word = c.to_lowercase().to_string();
+                while let Some(&next) = chars.peek() {
+                    if !(next.is_alphanumeric() || next == '_') {
+                        break;
+                    }
+                    word.extend(next.to_lowercase());
+                    chars.next();
+                }
+                tokens.push(Token::Word(word));
+            }
+            c => tokens.push(Token::Symbol(c)),
+        }
+    }
+    tokens
+}
+
+/// What a statement selects.
+#[derive(Debug, PartialEq)]
+enum Selectors<'a> {
+    /// The statement returns no rows.
+    None,
+    /// The statement returns rows we cannot describe, e.g. a conditional
+    /// update.
+    Unknown,
+    /// `SELECT JSON`.
+    Json,
+    /// The tokens of each selector.
+    List(Vec<&'a [Token]>),
+}
+
+#[derive(Debug, PartialEq)]
+struct Parsed<'a> {
+    /// The keyspace of the table, if qualified.
+    keyspace: Option<String>,
+    table: String,
+    selectors: Selectors<'a>,
+}
+
+fn is_word(token: Option<&Token>, word: &str) -> bool {
+    matches!(token, Some(Token::Word(w)) if w == word)
+}
+
+/// Parse a table name, which may be qualified with its keyspace, at the start
+/// of `tokens`.
+fn table_name(tokens: &[Token]) -> Option<(Option<String>, String)> {
+    match tokens {
+        [keyspace, Token::Symbol('.'), table, ..] => {
+            Some((Some(identifier(keyspace)?), identifier(table)?))
+        }
+        [table, ..] => Some((None, identifier(table)?)),
+        _ => None,
+    }
+}
+
+fn parse(tokens: &[Token]) -> Option<Parsed> {
+    let first = match tokens.first()? {
+        Token::Word(word) => word.as_str(),
+        _ => return None,
+    };
+    let conditional = || tokens.iter().any(|t| is_word(Some(t), "if"));
+    let (keyspace, table, selectors) = match first {
+        "select" => {
+            let mut rest = &tokens[1..];
+            if is_word(rest.first(), "distinct") {
+                rest = &rest[1..];
+            }
+            let json = is_word(rest.first(), "json");
+            let from = rest.iter().position(|t| is_word(Some(t), "from"))?;
+            let (keyspace, table) = table_name(&rest[from + 1..])?;
+            let selectors = if json {
+                Selectors::Json
+            } else {
+                Selectors::List(split_selectors(&rest[..from]))
+            };
+            (keyspace, table, selectors)
+        }
+        "insert" if is_word(tokens.get(1), "into") => {
+            let (keyspace, table) = table_name(&tokens[2..])?;
+            (keyspace, table, Selectors::None)
+        }
+        "update" => {
+            let (keyspace, table) = table_name(&tokens[1..])?;
+            (keyspace, table, Selectors::None)
+        }
+        "delete" => {
+            let from = tokens.iter().position(|t| is_word(Some(t), "from"))?;
+            let (keyspace, table) = table_name(&tokens[from + 1..])?;
+            (keyspace, table, Selectors::None)
+        }
+        _ => return None,
+    };
+    let selectors = if selectors == Selectors::None && conditional() {
+        Selectors::Unknown
+    } else {
+        selectors
+    };
+    Some(Parsed {
+        keyspace,
+        table,
+        selectors,
+    })
+}
+
+/// Split selectors at top-level commas.
+fn split_selectors(tokens: &[Token]) -> Vec<&[Token]> {
+    let mut selectors = vec![];
+    let mut depth = 0;
+    let mut start = 0;
+    for (index, token) in tokens.iter().enumerate() {
+        match token {
+            Token::Symbol('(') => depth += 1,
+            Token::Symbol(')') => depth -= 1,
+            Token::Symbol(',') if depth == 0 => {
+                selectors.push(&tokens[start..index]);
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    selectors.push(&tokens[start..]);
+    selectors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn word(w: &str) -> Token {
+        Token::Word(w.to_string())
+    }
+
+    #[test]
+    fn test_tokenize() {
+        assert_eq!(
+            tokenize("SELECT \"Name\", 'it''s' -- comment\n FROM ks.t /* x */"),
+            vec![
+                word("select"),
+                Token::Quoted("Name".to_string()),
+                Token::Symbol(','),
+                Token::Literal,
+                word("from"),
+                word("ks"),
+                Token::Symbol('.'),
+                word("t"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse() {
+        let tokens = tokenize("SELECT id, writetime(v) AS w FROM Ks.\"T\" WHERE id = ?");
+        let parsed = parse(&tokens).unwrap();
+        assert_eq!(parsed.keyspace.as_deref(), Some("ks"));
+        assert_eq!(parsed.table, "T");
+        match parsed.selectors {
+            Selectors::List(selectors) => {
+                assert_eq!(selectors.len(), 2);
+                assert_eq!(selectors[0], &[word("id")][..]);
+                assert_eq!(selectors[1].len(), 6);
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+
+        let tokens = tokenize("INSERT INTO ks.t (id) VALUES (?)");
+        assert_eq!(parse(&tokens).unwrap().selectors, Selectors::None);
+        let tokens = tokenize("UPDATE ks.t SET v = ? WHERE id = ? IF v = ?");
+        assert_eq!(parse(&tokens).unwrap().selectors, Selectors::Unknown);
+        let tokens = tokenize("SELECT JSON * FROM ks.t");
+        assert_eq!(parse(&tokens).unwrap().selectors, Selectors::Json);
+        let tokens = tokenize("SELECT * FROM users WHERE id = ?");
+        let parsed = parse(&tokens).unwrap();
+        assert_eq!(parsed.keyspace, None);
+        assert_eq!(parsed.table, "users");
+        assert_eq!(parse(&tokenize("TRUNCATE ks.t")), None);
+    }
+}
@@ -7,7 +7,7 @@ use crate::cassandra::iterator::{MapIterator, SetIterator, UserTypeIterator};
 
 use crate::cassandra::util::{Protected, ProtectedInner};
 use crate::cassandra::uuid::Uuid;
-use crate::cassandra::value::Value;
+use crate::cassandra::value::{OwnedValue, Value};
 use crate::cassandra_sys::cass_false;
 use crate::cassandra_sys::cass_iterator_free;
 use crate::cassandra_sys::cass_iterator_from_row;
@@ -343,6 +343,51 @@ impl AsRustType<BigDecimal> for Row<'_> {
     }
 }
 
+/// Conversion of a whole row into an owned Rust value, e.g., for use with
+/// `Statement::into_row_stream`.
+///
+/// This is implemented for tuples of up to eight types which can each be
+/// obtained from a column with `AsRustType`, taking the columns in order, and
+/// for `Vec<OwnedValue>`, which copies every column of a row of any shape.
+/// Implement it for your own types to decode rows into structs.
+pub trait FromRow: Sized {
+    /// Convert the row.
+    fn from_row(row: &Row) -> Result<Self>;
+}
+
+macro_rules! impl_from_row_for_tuple {
+    ($($t:ident => $index:tt),+) => {
+        impl<$($t),+> FromRow for ($($t,)+)
+        where
+            $(for<'r> Row<'r>: AsRustType<$t>),+
+        {
+            fn from_row(row: &Row) -> Result<Self> {
+                Ok(($(AsRustType::<$t>::get(row, $index)?,)+))
+            }
+        }
+    };
+}
+
+impl_from_row_for_tuple!(A => 0);
+impl_from_row_for_tuple!(A => 0, B => 1);
+impl_from_row_for_tuple!(A => 0, B => 1, C => 2);
+impl_from_row_for_tuple!(A => 0, B => 1, C => 2, D => 3);
+impl_from_row_for_tuple!(A => 0, B => 1, C => 2, D => 3, E => 4);
+impl_from_row_for_tuple!(A => 0, B => 1, C => 2, D => 3, E => 4, F => 5);
+impl_from_row_for_tuple!(A => 0, B => 1, C => 2, D => 3, E => 4, F => 5, G => 6);
+impl_from_row_for_tuple!(A => 0, B => 1, C => 2, D => 3, E => 4, F => 5, G => 6, H => 7);
+
+impl FromRow for Vec<OwnedValue> {
+    fn from_row(row: &Row) -> Result<Self> {
+        let mut values = Vec::new();
+        let mut iter = row.iter();
+        while let Some(value) = iter.next() {
+            values.push(value.get_owned()?);
+        }
+        Ok(values)
+    }
+}
+
 impl<'a> Row<'a> {
     /// Get a particular column by index
     pub fn get_column(&self, index: usize) -> Result<Value<'a>> {
@@ -0,0 +1,572 @@
+use crate::cassandra::consistency::Consistency;
+use crate::cassandra::error::*;
+use crate::cassandra::iterator::LendingIterator;
+use crate::cassandra::result::CassResult;
+use crate::cassandra::row::FromRow;
+use crate::cassandra::schema::table_meta::TableMeta;
+use crate::cassandra::statement::BindRustType;
+use crate::cassandra::util::{self, quote_identifier, Fnv64};
+use crate::Session;
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use futures::future::BoxFuture;
+use futures::stream::{FuturesUnordered, Stream, StreamExt};
+use futures::FutureExt;
+
+use std::collections::VecDeque;
+use std::convert::TryInto;
+use std::fmt;
+use std::hash::Hasher;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+/// The version of the `ScanCheckpoint` encoding.
+const CHECKPOINT_VERSION: u8 = 1;
+
+/// Scans a whole table in parallel, by splitting the Murmur3 token ring into
+/// sub-ranges and reading each with
+///
+/// ```text
+/// SELECT * FROM ks.table WHERE token(pk) > ? AND token(pk) <= ?
+/// ```
+///
+/// Up to `concurrency` ranges are read at a time, so the load is spread across
+/// the coordinators owning the ranges rather than concentrated on one. Rows are
+/// yielded as a stream, in no particular order.
+///
+/// Each page request is retried on transient failures (timeouts, unavailable
+/// replicas and overload), resuming the range from where it left off. The
+/// next page of a range is only requested once the previous one has been
+/// yielded, so at most one page per range is buffered. Progress is recorded
+/// in a `ScanCheckpoint`, which can be saved and used to resume an
+/// interrupted scan. Rows are delivered at least once: on resumption, rows
+/// from a partially-consumed page are yielded again.
+///
+/// This only supports tables using the `Murmur3Partitioner` (the default).
+#[derive(Debug, Clone)]
+pub struct TableScanner {
+    keyspace: String,
+    table: String,
+    partition_key: Vec<String>,
+    columns: Option<Vec<String>>,
+    splits: usize,
+    concurrency: usize,
+    settings: ScanSettings,
+}
+
+/// Options for each page request of a scan.
+#[derive(Debug, Clone, Copy)]
+struct ScanSettings {
+    page_size: i32,
+    consistency: Option<Consistency>,
+    max_retries: u32,
+    retry_delay: Duration,
+}
+
+/// The progress of a scan of one token range.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum RangeProgress {
+    /// No rows from this range have been consumed yet.
+    Pending,
+    /// Some pages of this range have been consumed; resume from this paging
+    /// state.
+    InProgress(Vec<u8>),
+    /// The whole range has been consumed.
+    Done,
+}
+
+/// A token range `(start, end]` of a scan, and the progress through it.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ScanRange {
+    /// The start of the range (exclusive).
+    pub start: i64,
+    /// The end of the range (inclusive).
+    pub end: i64,
+    /// How much of the range has been consumed.
+    pub progress: RangeProgress,
+}
+
+/// The progress of a `TableScanner` scan, from which it can be resumed.
+///
+/// The checkpoint is updated once all rows of a page have been yielded. It
+/// contains driver paging states, so should only be kept for a short time
+/// (e.g., to resume after a crash) and not across driver upgrades.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ScanCheckpoint {
+    fingerprint: u64,
+    ranges: Vec<ScanRange>,
+}
+
+/// Split the Murmur3 token ring into `splits` contiguous ranges of nearly
+/// equal size.
+fn split_ring(splits: usize) -> Vec<(i64, i64)> {
+    let splits = splits.max(1) as i128;
+    let min = i128::from(i64::MIN);
+    let width = i128::from(i64::MAX) - min;
+    let boundary = |i: i128| (min + width * i / splits) as i64;
+    (0..splits)
+        .map(|i| (boundary(i), boundary(i + 1)))
+        .collect()
+}
+
+impl TableScanner {
+    /// Create a scanner for the given table.
+    ///
+    /// By default the ring is split into 64 ranges, read 8 at a time with
+    /// pages of 1000 rows, and each page request is retried 3 times.
+    pub fn new(table: &TableMeta) -> Result<Self> {
+        let partition_key = (0..table.partition_key_count())
+            .filter_map(|index| table.partition_key(index))
+            .collect::<Vec<_>>();
+        let keyspace = match partition_key.first() {
+            Some(column) => column.text_field("keyspace_name")?,
+            None => None,
+        }
+        .ok_or_else(|| CassErrorCode::LIB_NULL_VALUE.to_error())?;
+
+        Ok(TableScanner {
+            keyspace,
+            table: table.get_name(),
+            partition_key: partition_key.iter().map(|column| column.name()).collect(),
+            columns: None,
+            splits: 64,
+            concurrency: 8,
+            settings: ScanSettings {
+                page_size: 1000,
+                consistency: None,
+                max_retries: 3,
+                retry_delay: Duration::from_millis(100),
+            },
+        })
+    }
+
+    /// Select only the given columns, rather than `*`.
+    pub fn columns(mut self, columns: &[&str]) -> Self {
+        self.columns = Some(columns.iter().map(|c| c.to_string()).collect());
+        self
+    }
+
+    /// Sets the number of token ranges to split the ring into.
+    pub fn splits(mut self, splits: usize) -> Self {
+        self.splits = splits.max(1);
+        self
+    }
+
+    /// Sets the maximum number of ranges to read concurrently.
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Sets the page size of each request.
+    pub fn page_size(mut self, page_size: i32) -> Self {
+        self.settings.page_size = page_size;
+        self
+    }
+
+    /// Sets the consistency level of each request.
+    pub fn consistency(mut self, consistency: Consistency) -> Self {
+        self.settings.consistency = Some(consistency);
+        self
+    }
+
+    /// Sets how many times a page request which fails with a transient error
+    /// is retried, and the delay before the first retry. The delay doubles on
+    /// each subsequent retry.
+    pub fn retries(mut self, max_retries: u32, delay: Duration) -> Self {
+        self.settings.max_retries = max_retries;
+        self.settings.retry_delay = delay;
+        self
+    }
+
+    fn query(&self) -> String {
+        let columns = match &self.columns {
+            None => "*".to_string(),
+            Some(columns) => columns
+                .iter()
+                .map(|c| quote_identifier(c))
+                .collect::<Vec<_>>()
+                .join(", "),
+        };
+        let token = format!(
+            "token({})",
+            self.partition_key
+                .iter()
+                .map(|c| quote_identifier(c))
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+        format!(
+            "SELECT {} FROM {}.{} WHERE {} > ? AND {} <= ?",
+            columns,
+            quote_identifier(&self.keyspace),
+            quote_identifier(&self.table),
+            token,
+            token
+        )
+    }
+
+    /// A checkpoint for a scan which has not yet started.
+    pub fn checkpoint(&self) -> ScanCheckpoint {
+        let mut hasher = Fnv64::default();
+        hasher.write(self.query().as_bytes());
+        ScanCheckpoint {
+            fingerprint: hasher.finish(),
+            ranges: split_ring(self.splits)
+                .into_iter()
+                .map(|(start, end)| ScanRange {
+                    start,
+                    end,
+                    progress: RangeProgress::Pending,
+                })
+                .collect(),
+        }
+    }
+
+    /// Scan the table from the beginning, converting each row with `FromRow`.
+    pub fn scan<T: FromRow>(&self, session: &Session) -> ScanStream<T> {
+        ScanStream::new(self, session, self.checkpoint())
+    }
+
+    /// Resume a scan from a checkpoint. Fails with `PagingCursorMismatch` if
+    /// the checkpoint came from a scan of a different table or columns.
+    pub fn resume<T: FromRow>(
+        &self,
+        session: &Session,
+        checkpoint: ScanCheckpoint,
+    ) -> Result<ScanStream<T>> {
+        if checkpoint.fingerprint != self.checkpoint().fingerprint {
+            return Err(ErrorKind::PagingCursorMismatch.into());
+        }
+        Ok(ScanStream::new(self, session, checkpoint))
+    }
+}
+
+impl ScanCheckpoint {
+    /// The token ranges of the scan and their progress.
+    pub fn ranges(&self) -> &[ScanRange] {
+        &self.ranges
+    }
+
+    /// Whether every range has been scanned.
+    pub fn is_done(&self) -> bool {
+        self.ranges
+            .iter()
+            .all(|range| range.progress == RangeProgress::Done)
+    }
+
+    /// Encode the checkpoint as a URL-safe string, e.g., for saving to disk.
+    pub fn encode(&self) -> String {
+        let mut bytes = vec![CHECKPOINT_VERSION];
+        bytes.extend_from_slice(&self.fingerprint.to_be_bytes());
+        for range in &self.ranges {
+            bytes.extend_from_slice(&range.start.to_be_bytes());
+            bytes.extend_from_slice(&range.end.to_be_bytes());
+            match &range.progress {
+                RangeProgress::Pending => bytes.push(0),
+                RangeProgress::InProgress(paging_state) => {
+                    bytes.push(1);
+                    bytes.extend_from_slice(&(paging_state.len() as u32).to_be_bytes());
+                    bytes.extend_from_slice(paging_state);
+                }
+                RangeProgress::Done => bytes.push(2),
+            }
+        }
+        URL_SAFE_NO_PAD.encode(bytes)
+    }
+
+    /// Decode a checkpoint previously encoded with `encode`.
+    pub fn decode(checkpoint: &str) -> Result<Self> {
+        let invalid = || Error::from(ErrorKind::InvalidPagingCursor("bad checkpoint".to_string()));
+        let bytes = URL_SAFE_NO_PAD
+            .decode(checkpoint)
+            .map_err(|e| ErrorKind::InvalidPagingCursor(e.to_string()))?;
+        let mut rest = &bytes[..];
+        let mut take = |n: usize| -> Result<&[u8]> {
+            if rest.len() < n {
+                return Err(invalid());
+            }
+            let (head, tail) = rest.split_at(n);
+            rest = tail;
+            Ok(head)
+        };
+        if take(1)? != [CHECKPOINT_VERSION] {
+            return Err(invalid());
+        }
+        let fingerprint = u64::from_be_bytes(take(8)?.try_into().unwrap());
+        let mut ranges = vec![];
+        loop {
+            let start = match take(8) {
+                Ok(start) => i64::from_be_bytes(start.try_into().unwrap()),
+                Err(_) => break,
+            };
+            let end = i64::from_be_bytes(take(8)?.try_into().unwrap());
+            let progress = match take(1)?[0] {
+                0 => RangeProgress::Pending,
+                1 => {
+                    let len = u32::from_be_bytes(take(4)?.try_into().unwrap());
+                    RangeProgress::InProgress(take(len as usize)?.to_vec())
+                }
+                2 => RangeProgress::Done,
+                _ => return Err(invalid()),
+            };
+            ranges.push(ScanRange {
+                start,
+                end,
+                progress,
+            });
+        }
+        Ok(ScanCheckpoint {
+            fingerprint,
+            ranges,
+        })
+    }
+}
+
+/// Fetch one page of a token range, retrying on transient failures.
+async fn fetch_page(
+    session: Session,
+    query: Arc<str>,
+    (start, end): (i64, i64),
+    paging_state: Option<Vec<u8>>,
+    settings: ScanSettings,
+) -> Result<CassResult> {
+    let mut delay = settings.retry_delay;
+    let mut attempt = 0;
+    loop {
+        let mut statement = session.statement(&*query);
+        statement.bind(0, start)?;
+        statement.bind(1, end)?;
+        statement.set_paging_size(settings.page_size)?;
+        if let Some(paging_state) = &paging_state {
+            statement.set_paging_state_token(paging_state)?;
+        }
+        if let Some(consistency) = settings.consistency {
+            statement.set_consistency(consistency)?;
+        }
+        match statement.execute().await {
+            Ok(result) => return Ok(result),
+            Err(err) if attempt < settings.max_retries && is_transient(&err) => {
+                attempt += 1;
+                util::delay(delay).await;
+                delay *= 2;
+            }
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+/// A page of a range which has been fetched but not yet fully yielded.
+struct Page<T> {
+    range: usize,
+    rows: VecDeque<T>,
+    paging_state: Option<Vec<u8>>,
+}
+
+/// The stream of rows of a table scan. Created by `TableScanner::scan`.
+///
+/// The stream ends after yielding the first error; `checkpoint` can then be
+/// used to resume the scan.
+#[must_use]
+pub struct ScanStream<T> {
+    session: Session,
+    query: Arc<str>,
+    settings: ScanSettings,
+    concurrency: usize,
+    checkpoint: ScanCheckpoint,
+    /// Ranges which have not been started, in reverse order.
+    pending: Vec<usize>,
+    /// Ranges which have a request in flight or a page buffered.
+    active: usize,
+    in_flight: FuturesUnordered<BoxFuture<'static, (usize, Result<CassResult>)>>,
+    pages: VecDeque<Page<T>>,
+    failed: bool,
+}
+
+impl<T> Unpin for ScanStream<T> {}
+
+impl<T> fmt::Debug for ScanStream<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ScanStream")
+            .field("query", &self.query)
+            .field("concurrency", &self.concurrency)
+            .field("pending", &self.pending.len())
+            .field("active", &self.active)
+            .field("pages", &self.pages.len())
+            .finish()
+    }
+}
+
+impl<T: FromRow> ScanStream<T> {
+    fn new(scanner: &TableScanner, session: &Session, checkpoint: ScanCheckpoint) -> Self {
+        let pending = (0..checkpoint.ranges.len())
+            .rev()
+            .filter(|&index| checkpoint.ranges[index].progress != RangeProgress::Done)
+            .collect();
+        ScanStream {
+            session: session.clone(),
+            query: scanner.query().into(),
+            settings: scanner.settings,
+            concurrency: scanner.concurrency,
+            checkpoint,
+            pending,
+            active: 0,
+            in_flight: FuturesUnordered::new(),
+            pages: VecDeque::new(),
+            failed: false,
+        }
+    }
+
+    /// The progress of the scan so far, reflecting all rows yielded up to the
+    /// end of the last completely-yielded page of each range.
+    pub fn checkpoint(&self) -> &ScanCheckpoint {
+        &self.checkpoint
+    }
+
+    fn fetch(&mut self, index: usize, paging_state: Option<Vec<u8>>) {
+        let range = &self.checkpoint.ranges[index];
+        let future = fetch_page(
+            self.session.clone(),
+            self.query.clone(),
+            (range.start, range.end),
+            paging_state,
+            self.settings,
+        );
+        self.in_flight
+            .push(future.map(move |result| (index, result)).boxed());
+    }
+
+    fn on_page(&mut self, range: usize, result: CassResult) -> Result<()> {
+        let mut rows = VecDeque::with_capacity(result.row_count() as usize);
+        let mut iter = result.iter();
+        while let Some(row) = iter.next() {
+            rows.push_back(T::from_row(&row)?);
+        }
+        self.pages.push_back(Page {
+            range,
+            rows,
+            paging_state: result.paging_state_token()?,
+        });
+        Ok(())
+    }
+
+    /// Record that all rows of the front page have been yielded, and request
+    /// the next page of its range.
+    fn finish_page(&mut self) {
+        if let Some(page) = self.pages.pop_front() {
+            self.checkpoint.ranges[page.range].progress = match page.paging_state {
+                Some(paging_state) => {
+                    self.fetch(page.range, Some(paging_state.clone()));
+                    RangeProgress::InProgress(paging_state)
+                }
+                None => {
+                    self.active -= 1;
+                    RangeProgress::Done
+                }
+            };
+        }
+    }
+
+    fn fail(&mut self, err: Error) -> Poll<Option<Result<T>>> {
+        self.failed = true;
+        self.in_flight = FuturesUnordered::new();
+        self.pages.clear();
+        Poll::Ready(Some(Err(err)))
+    }
+}
+
+impl<T: FromRow> Stream for ScanStream<T> {
+    type Item = Result<T>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Result<T>>> {
+        let this = &mut *self;
+        if this.failed {
+            return Poll::Ready(None);
+        }
+        loop {
+            while this.active < this.concurrency {
+                match this.pending.pop() {
+                    Some(index) => {
+                        let paging_state = match &this.checkpoint.ranges[index].progress {
+                            RangeProgress::InProgress(paging_state) => Some(paging_state.clone()),
+                            _ => None,
+                        };
+                        this.fetch(index, paging_state);
+                        this.active += 1;
+                    }
+                    None => break,
+                }
+            }
+            while let Poll::Ready(Some((range, result))) = this.in_flight.poll_next_unpin(cx) {
+                if let Err(err) = result.and_then(|result| this.on_page(range, result)) {
+                    return this.fail(err);
+                }
+            }
+            match this.pages.front_mut() {
+                Some(page) => match page.rows.pop_front() {
+                    Some(row) => {
+                        if page.rows.is_empty() {
+                            this.finish_page();
+                        }
+                        return Poll::Ready(Some(Ok(row)));
+                    }
+                    // An empty page; there may be more ranges to start.
+                    None => this.finish_page(),
+                },
+                None if this.in_flight.is_empty() && this.pending.is_empty() => {
+                    return Poll::Ready(None)
+                }
+                None => return Poll::Pending,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_ring() {
+        let ranges = split_ring(4);
+        assert_eq!(ranges.len(), 4);
+        assert_eq!(ranges[0].0, i64::MIN);
+        assert_eq!(ranges[3].1, i64::MAX);
+        for pair in ranges.windows(2) {
+            assert_eq!(pair[0].1, pair[1].0);
+        }
+        assert_eq!(split_ring(1), vec![(i64::MIN, i64::MAX)]);
+    }
+
+    #[test]
+    fn test_checkpoint_round_trip() {
+        let checkpoint = ScanCheckpoint {
+            fingerprint: 42,
+            ranges: vec![
+                ScanRange {
+                    start: i64::MIN,
+                    end: -1,
+                    progress: RangeProgress::Done,
+                },
+                ScanRange {
+                    start: -1,
+                    end: 0,
+                    progress: RangeProgress::InProgress(vec![1, 2, 3]),
+                },
+                ScanRange {
+                    start: 0,
+                    end: i64::MAX,
+                    progress: RangeProgress::Pending,
+                },
+            ],
+        };
+        assert_eq!(
+            ScanCheckpoint::decode(&checkpoint.encode()).unwrap(),
+            checkpoint
+        );
+        assert!(ScanCheckpoint::decode("AQ").is_err());
+    }
+}
@@ -1,4 +1,5 @@
 use crate::cassandra::data_type::ConstDataType;
+use crate::cassandra::error::*;
 
 use crate::cassandra::iterator::FieldIterator;
 use crate::cassandra::util::{Protected, ProtectedInner};
@@ -77,4 +78,12 @@ impl<'a> ColumnMeta<'a> {
             }
         }
     }
+
+    /// Gets a text metadata field, if it is set.
+    pub(crate) fn text_field(&self, name: &str) -> Result<Option<String>> {
+        match self.field_by_name(name) {
+            Some(value) if !value.is_null() => value.get_string().map(Some),
+            _ => Ok(None),
+        }
+    }
 }
@@ -9,6 +9,7 @@ use crate::cassandra::schema::aggregate_meta::AggregateMeta;
 use crate::cassandra::schema::function_meta::FunctionMeta;
 use crate::cassandra::schema::table_meta::TableMeta;
 use crate::cassandra::util::{Protected, ProtectedInner};
+use crate::cassandra::value::Value;
 
 use crate::cassandra_sys::cass_iterator_aggregates_from_keyspace_meta;
 use crate::cassandra_sys::cass_iterator_fields_from_keyspace_meta;
@@ -54,6 +55,13 @@ impl Protected<*const _CassKeyspaceMeta> for KeyspaceMeta<'_> {
 #[derive(Debug)]
 pub struct MetadataFieldValue<'a>(*const _CassValue, PhantomData<&'a _CassValue>);
 
+impl<'a> MetadataFieldValue<'a> {
+    /// The value of the field.
+    pub fn value(&self) -> Value<'a> {
+        Value::build(self.0)
+    }
+}
+
 impl<'a> KeyspaceMeta<'a> {
     /// Iterator over the aggregates in this keyspace
     pub fn aggregrates_iter(&self) -> AggregateIterator<'a> {
@@ -55,6 +55,20 @@ impl SchemaMeta {
         }
     }
 
+    /// Gets the keyspace metadata for the provided keyspace name, or `None` if
+    /// there is no such keyspace.
+    pub fn keyspace_by_name(&self, keyspace: &str) -> Option<KeyspaceMeta> {
+        unsafe {
+            let keyspace_ptr = keyspace.as_ptr() as *const c_char;
+            let value = cass_schema_meta_keyspace_by_name_n(self.0, keyspace_ptr, keyspace.len());
+            if value.is_null() {
+                None
+            } else {
+                Some(KeyspaceMeta::build(value))
+            }
+        }
+    }
+
     /// Returns an iterator over the keyspaces in this schema
     pub fn keyspace_iter(&self) -> KeyspaceIterator {
         unsafe { KeyspaceIterator::build(cass_iterator_keyspaces_from_schema_meta(self.0)) }
@@ -2,17 +2,22 @@
 #![allow(dead_code)]
 #![allow(missing_copy_implementations)]
 
+use crate::cassandra::concurrent::ConcurrentExecution;
 use crate::cassandra::custom_payload::CustomPayloadResponse;
 use crate::cassandra::error::*;
 use crate::cassandra::future::CassFuture;
+use crate::cassandra::host_events::HostListeners;
 use crate::cassandra::metrics::SessionMetrics;
 use crate::cassandra::prepared::PreparedStatement;
 use crate::cassandra::result::CassResult;
 use crate::cassandra::schema::schema_meta::SchemaMeta;
 use crate::cassandra::statement::Statement;
-use crate::cassandra::util::{Protected, ProtectedInner};
+use crate::cassandra::trace::{QueryTrace, DEFAULT_TRACE_ATTEMPTS, DEFAULT_TRACE_DELAY};
+use crate::cassandra::util::{self, Protected, ProtectedInner};
+use crate::cassandra::uuid::Uuid;
 use crate::{cassandra::batch::Batch, BatchType};
 
+use crate::cassandra_sys::cass_session_close;
 use crate::cassandra_sys::cass_session_execute;
 use crate::cassandra_sys::cass_session_execute_batch;
 use crate::cassandra_sys::cass_session_free;
@@ -22,12 +27,39 @@ use crate::cassandra_sys::cass_session_new;
 use crate::cassandra_sys::cass_session_prepare_n;
 use crate::cassandra_sys::CassSession as _Session;
 
+use futures::future::{self, Either};
+
 use std::mem;
 use std::os::raw::c_char;
+use std::sync::atomic::{AtomicU64, AtomicU8, AtomicUsize, Ordering};
 use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+/// The driver session, and what we track of its requests.
+#[derive(Debug)]
+pub struct SessionInner {
+    inner: *mut _Session,
+    /// The number of requests abandoned on timeout, or left in flight when
+    /// the session was closed.
+    abandoned: AtomicU64,
+    /// The number of requests whose futures have not yet completed or been
+    /// dropped.
+    in_flight: AtomicUsize,
+    /// Whether the session is open, being closed by `Session::close`, or
+    /// closed.
+    state: AtomicU8,
+    /// The keyspace the session was connected to, if any. A `USE` statement
+    /// executed on the session is not reflected here.
+    keyspace: Option<String>,
+    /// The host listeners of the cluster the session was connected from,
+    /// which the driver may call until the session is freed.
+    host_listeners: Option<Arc<HostListeners>>,
+}
 
-#[derive(Debug, Eq, PartialEq)]
-pub struct SessionInner(*mut _Session);
+const OPEN: u8 = 0;
+const CLOSING: u8 = 1;
+const CLOSED: u8 = 2;
 
 // The underlying C type has no thread-local state, and explicitly supports access
 // from multiple threads: https://datastax.github.io/cpp-driver/topics/#thread-safety
@@ -35,11 +67,30 @@ unsafe impl Send for SessionInner {}
 unsafe impl Sync for SessionInner {}
 
 impl SessionInner {
-    fn new(inner: *mut _Session) -> Arc<Self> {
-        Arc::new(Self(inner))
+    fn new(
+        inner: *mut _Session,
+        keyspace: Option<String>,
+        host_listeners: Option<Arc<HostListeners>>,
+    ) -> Arc<Self> {
+        Arc::new(Self {
+            inner,
+            abandoned: AtomicU64::new(0),
+            in_flight: AtomicUsize::new(0),
+            state: AtomicU8::new(OPEN),
+            keyspace,
+            host_listeners,
+        })
     }
 }
 
+impl PartialEq for SessionInner {
+    fn eq(&self, other: &Self) -> bool {
+        self.inner == other.inner
+    }
+}
+
+impl Eq for SessionInner {}
+
 /// A session object is used to execute queries and maintains cluster state through
 /// the control connection. The control connection is used to auto-discover nodes and
 /// monitor cluster changes (topology and schema). Each session also maintains multiple
@@ -51,7 +102,7 @@ pub struct Session(pub Arc<SessionInner>);
 
 impl ProtectedInner<*mut _Session> for SessionInner {
     fn inner(&self) -> *mut _Session {
-        self.0
+        self.inner
     }
 }
 
@@ -66,15 +117,46 @@ impl Protected<*mut _Session> for Session {
         if inner.is_null() {
             panic!("Unexpected null pointer")
         };
-        Session(SessionInner::new(inner))
+        Session(SessionInner::new(inner, None, None))
     }
 }
 
+/// A session pointer which may be sent to another thread to be freed.
+struct SendSession(*mut _Session);
+
+unsafe impl Send for SendSession {}
+
 impl Drop for SessionInner {
     /// Frees a session instance. If the session is still connected it will be synchronously
     /// closed before being deallocated.
+    ///
+    /// If `Session::close` gave up waiting for the session to close, freeing it would block
+    /// until it has closed, so it is freed on another thread instead.
+    ///
+    /// The host listeners are only released once the session is freed, since until then the
+    /// driver may still call them.
+    fn drop(&mut self) {
+        if self.state.load(Ordering::Acquire) == CLOSING {
+            let session = SendSession(self.inner);
+            let host_listeners = self.host_listeners.take();
+            thread::spawn(move || {
+                let session = session;
+                unsafe { cass_session_free(session.0) };
+                drop(host_listeners);
+            });
+        } else {
+            unsafe { cass_session_free(self.inner) }
+        }
+    }
+}
+
+/// Counts a request as in flight on its session until dropped.
+#[derive(Debug)]
+pub(crate) struct InFlight(Session);
+
+impl Drop for InFlight {
     fn drop(&mut self) {
-        unsafe { cass_session_free(self.0) }
+        (self.0).0.in_flight.fetch_sub(1, Ordering::AcqRel);
     }
 }
 
@@ -86,19 +168,106 @@ impl Default for Session {
 
 impl Session {
     pub(crate) fn new() -> Session {
-        unsafe { Session(SessionInner::new(cass_session_new())) }
+        Session::for_cluster(None, None)
+    }
+
+    /// Creates a session to be connected from a cluster, to `keyspace` if
+    /// given, keeping the cluster's host listeners, if any, alive until the
+    /// session is freed.
+    pub(crate) fn for_cluster(
+        keyspace: Option<&str>,
+        host_listeners: Option<Arc<HostListeners>>,
+    ) -> Session {
+        let keyspace = keyspace.map(str::to_string);
+        unsafe {
+            Session(SessionInner::new(
+                cass_session_new(),
+                keyspace,
+                host_listeners,
+            ))
+        }
+    }
+
+    /// The keyspace the session was connected to, if any.
+    pub(crate) fn keyspace(&self) -> Option<&str> {
+        self.0.keyspace.as_deref()
+    }
+
+    /// The number of requests on this session which were abandoned because
+    /// they timed out, with `CassFuture::wait_timeout` or
+    /// `CassFuture::with_deadline`, or because they were still in flight at
+    /// the deadline of `close`. An abandoned request may still have been
+    /// executed by the cluster.
+    pub fn abandoned_requests(&self) -> u64 {
+        self.0.abandoned.load(Ordering::Relaxed)
+    }
+
+    /// Record that a request on this session was abandoned.
+    pub(crate) fn record_abandoned(&self) {
+        self.0.abandoned.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// The number of requests on this session whose futures have not yet
+    /// completed or been dropped.
+    pub fn in_flight_requests(&self) -> usize {
+        self.0.in_flight.load(Ordering::Acquire)
+    }
+
+    /// Count a request as in flight until the returned guard is dropped.
+    pub(crate) fn track_request(&self) -> InFlight {
+        self.0.in_flight.fetch_add(1, Ordering::AcqRel);
+        InFlight(self.clone())
+    }
+
+    /// Closes the session, without blocking the calling thread.
+    ///
+    /// The session stops accepting new requests, which fail with
+    /// `LIB_NO_HOSTS_AVAILABLE`, and waits up to `timeout` for in-flight
+    /// requests to complete. Returns the number of requests still in flight at
+    /// the deadline, which are abandoned: the session no longer waits for
+    /// them, but they may still be executed by the cluster, and any results
+    /// they produce are discarded as it closes. The deadline is timed by the
+    /// shared timer, so no thread is held while waiting.
+    ///
+    /// Once the session is closed, dropping the last reference to it does not
+    /// block. If it did not close by the deadline, it finishes closing and is
+    /// freed in the background.
+    pub async fn close(&self, timeout: Duration) -> Result<usize> {
+        self.0
+            .state
+            .compare_exchange(OPEN, CLOSING, Ordering::AcqRel, Ordering::Acquire)
+            .map_err(|_| CassErrorCode::LIB_UNABLE_TO_CLOSE.to_error())?;
+        let close = <CassFuture<()>>::build_untracked(self.clone(), unsafe {
+            cass_session_close(self.inner())
+        });
+        match future::select(close, util::delay(timeout)).await {
+            Either::Left((result, _)) => {
+                self.0.state.store(CLOSED, Ordering::Release);
+                result.map(|()| 0)
+            }
+            Either::Right(((), _)) => {
+                let abandoned = self.in_flight_requests();
+                self.0
+                    .abandoned
+                    .fetch_add(abandoned as u64, Ordering::Relaxed);
+                Ok(abandoned)
+            }
+        }
     }
 
     /// Create a prepared statement with the given query.
     pub async fn prepare(&self, query: impl AsRef<str>) -> Result<PreparedStatement> {
         let query = query.as_ref();
-        let prepare_future = {
-            let query_ptr = query.as_ptr() as *const c_char;
-            CassFuture::build(self.clone(), unsafe {
-                cass_session_prepare_n(self.inner(), query_ptr, query.len())
-            })
-        };
-        prepare_future.await
+        Ok(self.prepare_future(query).await?.with_query(query))
+    }
+
+    /// Starts preparing a statement. The result must be given its query with
+    /// `PreparedStatement::with_query`.
+    pub(crate) fn prepare_future(&self, query: &str) -> CassFuture<PreparedStatement> {
+        let query_ptr = query.as_ptr() as *const c_char;
+        CassFuture::build(self.clone(), unsafe {
+            cass_session_prepare_n(self.inner(), query_ptr, query.len())
+        })
     }
 
     /// Creates a statement with the given query.
@@ -108,6 +277,22 @@ impl Session {
         Statement::new(self.clone(), query, param_count)
     }
 
+    /// Execute many statements, with at most `max_in_flight` requests in flight
+    /// at once. Statements are taken from `statements` as earlier ones
+    /// complete. Use the returned builder to choose an `ErrorMode` and track
+    /// progress, then `collect` the results in input order or stream them in
+    /// completion order.
+    pub fn execute_concurrent<I>(
+        &self,
+        statements: I,
+        max_in_flight: usize,
+    ) -> ConcurrentExecution<I::IntoIter>
+    where
+        I: IntoIterator<Item = Statement>,
+    {
+        ConcurrentExecution::new(statements.into_iter(), max_in_flight)
+    }
+
     /// Execute a batch statement.
     pub fn execute_batch(&self, batch: &Batch) -> CassFuture<CassResult> {
         let inner_future = unsafe { cass_session_execute_batch(self.inner(), batch.inner()) };
@@ -143,6 +328,52 @@ impl Session {
         <CassFuture<(CassResult, CustomPayloadResponse)>>::build(self.clone(), inner_future)
     }
 
+    /// Execute a statement and get the tracing id of the request.
+    ///
+    /// Tracing must have been enabled on the statement with `Statement::set_tracing`,
+    /// otherwise the tracing id is `None`. The result is returned either way. To
+    /// turn tracing on as well, use `Statement::execute_traced`.
+    pub fn execute_with_tracing_id(
+        &self,
+        statement: &Statement,
+    ) -> CassFuture<(CassResult, Option<Uuid>)> {
+        let inner_future = unsafe { cass_session_execute(self.inner(), statement.inner()) };
+        <CassFuture<(CassResult, Option<Uuid>)>>::build(self.clone(), inner_future)
+    }
+
+    /// Fetch the trace of a request previously executed with tracing enabled.
+    ///
+    /// Cassandra writes traces asynchronously, so the trace may not be complete
+    /// immediately after the request returns. This retries a few times with
+    /// increasing delays before failing with `TraceUnavailable`; see
+    /// `fetch_trace_with_retries` to control the retry behaviour.
+    pub async fn fetch_trace(&self, tracing_id: Uuid) -> Result<QueryTrace> {
+        self.fetch_trace_with_retries(tracing_id, DEFAULT_TRACE_ATTEMPTS, DEFAULT_TRACE_DELAY)
+            .await
+    }
+
+    /// Fetch the trace of a request, making at most `attempts` attempts to read a
+    /// complete trace. The delay between attempts starts at `delay` and doubles
+    /// after each attempt.
+    pub async fn fetch_trace_with_retries(
+        &self,
+        tracing_id: Uuid,
+        attempts: u32,
+        delay: Duration,
+    ) -> Result<QueryTrace> {
+        let mut delay = delay;
+        for attempt in 1..=attempts {
+            if let Some(trace) = QueryTrace::fetch(self, tracing_id).await? {
+                return Ok(trace);
+            }
+            if attempt < attempts {
+                util::delay(delay).await;
+                delay *= 2;
+            }
+        }
+        Err(ErrorKind::TraceUnavailable(tracing_id).into())
+    }
+
     /// Gets a snapshot of this session's schema metadata. The returned
     /// snapshot of the schema metadata is not updated. This function
     /// must be called again to retrieve any schema changes since the
@@ -121,7 +121,7 @@ impl Ssl {
     pub fn set_private_key(&mut self, key: &str, password: &str) -> Result<&mut Self> {
         unsafe {
             let key_ptr = key.as_ptr() as *const c_char;
-            let password_ptr = key.as_ptr() as *const c_char;
+            let password_ptr = password.as_ptr() as *const c_char;
             cass_ssl_set_private_key_n(self.0, key_ptr, key.len(), password_ptr, password.len())
                 .to_result(self)
         }
@@ -6,15 +6,20 @@ use crate::cassandra::collection::List;
 use crate::cassandra::collection::Map;
 use crate::cassandra::collection::Set;
 use crate::cassandra::consistency::Consistency;
+use crate::cassandra::cql_value::ToCqlValue;
 use crate::cassandra::custom_payload::CustomPayload;
 use crate::cassandra::error::*;
 use crate::cassandra::future::CassFuture;
 use crate::cassandra::inet::Inet;
+use crate::cassandra::lwt::{self, LwtOutcome};
+use crate::cassandra::paging::{self, PagingCursor, RowStream};
 use crate::cassandra::policy::retry::RetryPolicy;
 use crate::cassandra::result::CassResult;
+use crate::cassandra::row::FromRow;
 use crate::cassandra::tuple::Tuple;
+use crate::cassandra::type_check::{Parameter, Provided};
 use crate::cassandra::user_type::UserType;
-use crate::cassandra::util::{Protected, ProtectedInner, ProtectedWithSession};
+use crate::cassandra::util::{Fnv64, Protected, ProtectedInner, ProtectedWithSession};
 use crate::cassandra::uuid::Uuid;
 use crate::Session;
 
@@ -57,6 +62,7 @@ use crate::cassandra_sys::cass_statement_bind_uuid;
 use crate::cassandra_sys::cass_statement_bind_uuid_by_name_n;
 use crate::cassandra_sys::cass_statement_free;
 use crate::cassandra_sys::cass_statement_new_n;
+use crate::cassandra_sys::cass_statement_reset_parameters;
 use crate::cassandra_sys::cass_statement_set_consistency;
 use crate::cassandra_sys::cass_statement_set_custom_payload;
 use crate::cassandra_sys::cass_statement_set_keyspace_n;
@@ -72,8 +78,12 @@ use crate::cassandra_sys::cass_true;
 use crate::cassandra_sys::CassStatement as _Statement;
 use crate::cassandra_sys::CASS_UINT64_MAX;
 
+use std::borrow::Borrow;
+use std::collections::BTreeMap;
 use std::convert::TryInto;
+use std::hash::Hasher;
 use std::os::raw::c_char;
+use std::sync::Arc;
 use std::time::Duration;
 
 #[derive(Debug)]
@@ -93,7 +103,104 @@ impl StatementInner {
 /// <b>Note:</b> Parameters for regular queries are not supported by the binary protocol
 /// version 1.
 #[derive(Debug)]
-pub struct Statement(StatementInner, Session);
+pub struct Statement {
+    inner: StatementInner,
+    /// The session the statement executes on.
+    session: Session,
+    /// The query text and bound values, as far as we track them.
+    info: StatementInfo,
+}
+
+/// What we know about a statement beyond what the driver will tell us: the
+/// query text and a summary of the values bound to it.
+#[derive(Debug, Clone, Default)]
+struct StatementInfo {
+    query: Arc<str>,
+    /// The values bound so far, if `Statement::record_bindings` was called.
+    bindings: Option<BTreeMap<BindKey, Binding>>,
+    /// Whether a value was bound while bindings were not being recorded.
+    unrecorded: bool,
+    /// The estimated size of the values bound so far, each with its 4-byte
+    /// length.
+    bound_size: usize,
+    /// The number of parameters, if known.
+    parameter_count: Option<usize>,
+    /// The name and type of each parameter, if the statement was prepared.
+    parameters: Option<Arc<[Parameter]>>,
+}
+
+/// A summary of a bound value.
+#[derive(Debug, Clone, Copy)]
+struct Binding {
+    /// The CQL type the value was bound as.
+    type_name: &'static str,
+    /// The serialized value, or its hash.
+    digest: Digest,
+    /// The size of the serialized value.
+    size: usize,
+}
+
+/// The serialized form of a bound value, kept only so far as is needed for
+/// fingerprinting. Short values are kept as they are, so binding them costs
+/// only a copy, and are hashed only if a fingerprint is taken.
+#[derive(Debug, Clone, Copy)]
+enum Digest {
+    /// A short value.
+    Inline {
+        len: u8,
+        bytes: [u8; INLINE_BINDING_SIZE],
+    },
+    /// A hash of a longer value.
+    Hashed(u64),
+    /// A collection, tuple or user type, whose serialized form we do not see.
+    Opaque,
+}
+
+/// The longest value kept inline, enough for any fixed-size type.
+const INLINE_BINDING_SIZE: usize = 16;
+
+/// The assumed size of a bound collection, tuple or user type, whose
+/// serialized form we do not see.
+const UNKNOWN_BINDING_SIZE: usize = 64;
+
+/// The assumed overhead of each statement in a batch: its kind, the length of
+/// its query or id, and its value count.
+const STATEMENT_OVERHEAD: usize = 8;
+
+/// Where a value is bound.
+#[derive(Debug, Clone, Copy)]
+enum BindAt<'a> {
+    Index(usize),
+    Name(&'a str),
+}
+
+impl From<usize> for BindAt<'_> {
+    fn from(index: usize) -> Self {
+        BindAt::Index(index)
+    }
+}
+
+impl<'a> From<&'a str> for BindAt<'a> {
+    fn from(name: &'a str) -> Self {
+        BindAt::Name(name)
+    }
+}
+
+/// Where a recorded value was bound.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum BindKey {
+    Index(usize),
+    Name(String),
+}
+
+impl From<BindAt<'_>> for BindKey {
+    fn from(at: BindAt) -> Self {
+        match at {
+            BindAt::Index(index) => BindKey::Index(index),
+            BindAt::Name(name) => BindKey::Name(name.to_string()),
+        }
+    }
+}
 
 // The underlying C type has no thread-local state, and forbids only concurrent
 // mutation/free: https://datastax.github.io/cpp-driver/topics/#thread-safety
@@ -120,19 +227,23 @@ impl Protected<*mut _Statement> for StatementInner {
 impl ProtectedInner<*mut _Statement> for Statement {
     #[inline(always)]
     fn inner(&self) -> *mut _Statement {
-        self.0.inner()
+        self.inner.inner()
     }
 }
 
 impl ProtectedWithSession<*mut _Statement> for Statement {
     #[inline(always)]
     fn build(inner: *mut _Statement, session: Session) -> Self {
-        Statement(StatementInner::build(inner), session)
+        Statement {
+            inner: StatementInner::build(inner),
+            session,
+            info: StatementInfo::default(),
+        }
     }
 
     #[inline(always)]
     fn session(&self) -> &Session {
-        &self.1
+        &self.session
     }
 }
 
@@ -355,10 +466,242 @@ impl BindRustType<&BigDecimal> for Statement {
     }
 }
 
+/// Checks that a value may be bound to a parameter.
+fn check_parameter(index: usize, parameter: &Parameter, provided: &Provided) -> Result<()> {
+    if provided.is_accepted_by(&parameter.data_type) {
+        return Ok(());
+    }
+    Err(ErrorKind::BindTypeMismatch(
+        index,
+        parameter.name.clone(),
+        parameter.data_type.to_string(),
+        provided.to_string(),
+    )
+    .into())
+}
+
 impl Statement {
     /// Creates a new query statement.
     pub(crate) fn new(session: Session, query: &str, parameter_count: usize) -> Self {
-        Statement(StatementInner::new(query, parameter_count), session)
+        let info = StatementInfo {
+            query: query.into(),
+            parameter_count: Some(parameter_count),
+            ..Default::default()
+        };
+        Statement {
+            inner: StatementInner::new(query, parameter_count),
+            session,
+            info,
+        }
+    }
+
+    /// Records the query text of a statement bound from a prepared statement.
+    pub(crate) fn set_query(&mut self, query: Arc<str>) {
+        self.info.query = query;
+    }
+
+    /// Records the parameters of a statement bound from a prepared statement,
+    /// so that bound values can be checked against their types.
+    pub(crate) fn set_parameters(&mut self, parameters: Arc<[Parameter]>) {
+        self.info.parameter_count = Some(parameters.len());
+        self.info.parameters = Some(parameters);
+    }
+
+    /// Binds all parameters of the statement at once, in order.
+    ///
+    /// The number of values must match the number of parameters. If the
+    /// statement was prepared, each value's type is checked against the type of
+    /// its parameter as it is bound, as with the individual `bind_*` methods,
+    /// and a mismatch is reported with the index of the offending value.
+    ///
+    /// If any value fails to bind, every parameter is unbound again, so the
+    /// statement is never left partly bound, and can be bound afresh.
+    ///
+    /// ```no_run
+    /// # use cassandra_cpp::*;
+    /// # async fn test(session: &Session) -> Result<()> {
+    /// let mut statement =
+    ///     session.statement("INSERT INTO ks.users (id, name, age) VALUES (?, ?, ?)");
+    /// statement.bind_all(&[&7i64, &"alice", &Some(42i32)])?;
+    /// statement.execute().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn bind_all(&mut self, values: &[&dyn ToCqlValue]) -> Result<&mut Self> {
+        if let Some(expected) = self.info.parameter_count {
+            if values.len() != expected {
+                return Err(ErrorKind::BindArityMismatch(expected, values.len()).into());
+            }
+        }
+        for (index, value) in values.iter().enumerate() {
+            if let Err(err) = value.bind_to(self, index) {
+                self.reset_parameters(values.len())?;
+                return Err(err);
+            }
+        }
+        Ok(self)
+    }
+
+    /// Unbinds every parameter, and forgets what was recorded of them.
+    fn reset_parameters(&mut self, count: usize) -> Result<()> {
+        unsafe { cass_statement_reset_parameters(self.inner(), count).to_result(())? };
+        self.info.bound_size = 0;
+        self.info.unrecorded = false;
+        if let Some(bindings) = &mut self.info.bindings {
+            bindings.clear();
+        }
+        Ok(())
+    }
+
+    /// Records values bound from now on, so that the statement can be paged
+    /// with `execute_page`, whose cursors are tied to the query text and bound
+    /// values. Call this before binding any values.
+    ///
+    /// Recording copies or hashes each value as it is bound, so is off by
+    /// default. Collections, tuples and user types are not recorded, so a
+    /// statement binding one still cannot be paged with a cursor.
+    pub fn record_bindings(&mut self) -> &mut Self {
+        self.info.bindings.get_or_insert_with(BTreeMap::new);
+        self
+    }
+
+    /// Checks that a value may be bound to the parameters at `at`, if the
+    /// statement was prepared and so their types are known. The driver would
+    /// only report `LIB_INVALID_VALUE_TYPE`, and does not check the elements
+    /// of collections created without a data type at all.
+    fn check<'a>(&self, at: impl Into<BindAt<'a>>, provided: &Provided) -> Result<()> {
+        let parameters = match &self.info.parameters {
+            Some(parameters) => parameters,
+            None => return Ok(()),
+        };
+        match at.into() {
+            BindAt::Index(index) => match parameters.get(index) {
+                Some(parameter) => check_parameter(index, parameter, provided),
+                None => Ok(()),
+            },
+            BindAt::Name(name) => parameters
+                .iter()
+                .enumerate()
+                .filter(|(_, parameter)| parameter.is_named(name))
+                .try_for_each(|(index, parameter)| check_parameter(index, parameter, provided)),
+        }
+    }
+
+    /// Records a value bound to the statement, given the parts of its
+    /// serialized form.
+    fn record<'a>(
+        &mut self,
+        at: impl Into<BindAt<'a>>,
+        type_name: &'static str,
+        parts: &[&[u8]],
+    ) -> &mut Self {
+        let size = parts.iter().map(|part| part.len()).sum();
+        self.info.bound_size += 4 + size;
+        let bindings = match &mut self.info.bindings {
+            Some(bindings) => bindings,
+            None => {
+                self.info.unrecorded = true;
+                return self;
+            }
+        };
+        let digest = if size <= INLINE_BINDING_SIZE {
+            let mut bytes = [0; INLINE_BINDING_SIZE];
+            let mut len = 0;
+            for part in parts {
+                bytes[len..len + part.len()].copy_from_slice(part);
+                len += part.len();
+            }
+            Digest::Inline {
+                len: len as u8,
+                bytes,
+            }
+        } else {
+            let mut hasher = Fnv64::default();
+            for part in parts {
+                hasher.write(part);
+            }
+            Digest::Hashed(hasher.finish())
+        };
+        let binding = Binding {
+            type_name,
+            digest,
+            size,
+        };
+        bindings.insert(at.into().into(), binding);
+        self
+    }
+
+    /// Records a collection, tuple or user type bound to the statement. These
+    /// are not inspected, so only their position and type are recorded, and
+    /// the statement cannot be fingerprinted.
+    fn record_opaque<'a>(
+        &mut self,
+        at: impl Into<BindAt<'a>>,
+        type_name: &'static str,
+    ) -> &mut Self {
+        self.info.bound_size += 4 + UNKNOWN_BINDING_SIZE;
+        match &mut self.info.bindings {
+            Some(bindings) => {
+                let binding = Binding {
+                    type_name,
+                    digest: Digest::Opaque,
+                    size: UNKNOWN_BINDING_SIZE,
+                };
+                bindings.insert(at.into().into(), binding);
+            }
+            None => self.info.unrecorded = true,
+        }
+        self
+    }
+
+    /// An estimate of the size of the statement when serialized in a batch:
+    /// its query text and bound values, each with a 4-byte length. Collections,
+    /// tuples and user types are assumed to be 64 bytes, and a parameter bound
+    /// more than once is counted each time.
+    pub(crate) fn estimated_size(&self) -> usize {
+        STATEMENT_OVERHEAD + self.info.query.len() + self.info.bound_size
+    }
+
+    /// A fingerprint of the query text and the values bound so far, used to
+    /// check that a paging cursor belongs to this statement.
+    ///
+    /// Fails with `BindingsNotRecorded` if values were bound without
+    /// `record_bindings`, and with `PagingCursorUnsupported` if a collection,
+    /// tuple or user type is bound, since its value cannot be fingerprinted.
+    pub(crate) fn fingerprint(&self) -> Result<u64> {
+        if self.info.unrecorded {
+            return Err(ErrorKind::BindingsNotRecorded.into());
+        }
+        let mut hasher = Fnv64::default();
+        hasher.write(&(self.info.query.len() as u64).to_be_bytes());
+        hasher.write(self.info.query.as_bytes());
+        for (key, binding) in self.info.bindings.iter().flatten() {
+            match key {
+                BindKey::Index(index) => hasher.write(&(*index as u64).to_be_bytes()),
+                BindKey::Name(name) => {
+                    hasher.write(&(name.len() as u64).to_be_bytes());
+                    hasher.write(name.as_bytes());
+                }
+            }
+            hasher.write(binding.type_name.as_bytes());
+            hasher.write(&(binding.size as u64).to_be_bytes());
+            match binding.digest {
+                Digest::Inline { len, bytes } => hasher.write(&bytes[..len as usize]),
+                Digest::Hashed(hash) => hasher.write(&hash.to_be_bytes()),
+                Digest::Opaque => {
+                    let key = match key {
+                        BindKey::Index(index) => index.to_string(),
+                        BindKey::Name(name) => name.clone(),
+                    };
+                    return Err(ErrorKind::PagingCursorUnsupported(
+                        key,
+                        binding.type_name.to_string(),
+                    )
+                    .into());
+                }
+            }
+        }
+        Ok(hasher.finish())
     }
 
     /// Returns the session of which this statement is bound to.
@@ -368,14 +711,118 @@ impl Statement {
 
     /// Executes the statement.
     pub async fn execute(self) -> Result<CassResult> {
-        let (statement, session) = (self.0, self.1);
+        self.execute_future().await
+    }
+
+    /// Starts executing the statement, returning the driver future. Unlike
+    /// `execute`, this can be given a timeout with `CassFuture::wait_timeout`
+    /// or `CassFuture::with_deadline`.
+    pub fn execute_future(self) -> CassFuture<CassResult> {
+        let Statement {
+            inner: statement,
+            session,
+            ..
+        } = self;
+        let execute = unsafe { cass_session_execute(session.inner(), statement.inner()) };
+        <CassFuture<CassResult>>::build(session, execute)
+    }
+
+    /// Executes a conditional statement (a lightweight transaction, using `IF`),
+    /// and interprets its `[applied]` column.
+    ///
+    /// The serial consistency is left as it is, so unless one has been set
+    /// with `set_serial_consistency`, the cluster's default applies (`SERIAL`,
+    /// unless changed with `Cluster::set_serial_consistency`).
+    /// If the request times out during the compare-and-set, or the client gives
+    /// up waiting for it, the update may or may not have been applied, and
+    /// `LwtOutcome::Unknown` is returned; the caller should read the row at a
+    /// serial consistency to find out. Other errors are returned as `Err`.
+    ///
+    /// ```no_run
+    /// # use cassandra_cpp::*;
+    /// # async fn test(session: &Session) -> Result<()> {
+    /// let mut statement =
+    ///     session.statement("INSERT INTO ks.users (name, email) VALUES (?, ?) IF NOT EXISTS");
+    /// statement.bind(0, "alice")?;
+    /// statement.bind(1, "alice@example.com")?;
+    /// match statement.execute_lwt().await? {
+    ///     LwtOutcome::Applied => println!("created"),
+    ///     LwtOutcome::NotApplied(existing) => {
+    ///         let email = existing.get_column_by_name("email")?.get_string()?;
+    ///         println!("already taken by {}", email);
+    ///     }
+    ///     LwtOutcome::Unknown(err) => println!("may or may not have been created: {}", err),
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn execute_lwt(self) -> Result<LwtOutcome> {
+        lwt::outcome(self.execute().await)
+    }
+
+    /// Turns tracing on and executes the statement, returning the result along
+    /// with the tracing id of the request. The trace itself can then be retrieved
+    /// with [`Session::fetch_trace`].
+    ///
+    /// The tracing id is `None` if the driver did not receive one, e.g. if the
+    /// server did not trace the request; the request was still executed.
+    pub async fn execute_traced(mut self) -> Result<(CassResult, Option<Uuid>)> {
+        self.set_tracing(true)?;
+        let Statement {
+            inner: statement,
+            session,
+            ..
+        } = self;
         let fut = {
             let execute = unsafe { cass_session_execute(session.inner(), statement.inner()) };
-            <CassFuture<CassResult>>::build(session, execute)
+            <CassFuture<(CassResult, Option<Uuid>)>>::build(session, execute)
         };
         fut.await
     }
 
+    /// Executes the statement, returning a stream of its rows across all pages
+    /// of the result. Each row is converted with `FromRow`.
+    ///
+    /// Set the page size with `set_paging_size` first; otherwise the whole
+    /// result is returned as a single page.
+    ///
+    /// ```no_run
+    /// # use cassandra_cpp::*;
+    /// # use futures::TryStreamExt;
+    /// # async fn test(session: Session) -> Result<()> {
+    /// let mut statement = session.statement("SELECT key, value FROM examples.paging");
+    /// statement.set_paging_size(100)?;
+    /// let mut rows = statement.into_row_stream::<(String, String)>().prefetch(1);
+    /// while let Some((key, value)) = rows.try_next().await? {
+    ///     println!("{} = {}", key, value);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn into_row_stream<T: FromRow>(self) -> RowStream<T> {
+        RowStream::new(self)
+    }
+
+    /// Executes a single page of the statement, starting from `cursor` if
+    /// given, or from the beginning otherwise. Returns the rows of the page
+    /// along with a cursor for the next page, if there is one.
+    ///
+    /// Values must be bound after calling `record_bindings`, so that the
+    /// cursor can be tied to them; otherwise this fails with
+    /// `BindingsNotRecorded`. Collections, tuples and user types are never
+    /// recorded, so a statement binding one cannot be paged with a cursor, and
+    /// fails with `PagingCursorUnsupported`.
+    ///
+    /// Fails with `PagingCursorMismatch` if the cursor was produced by a
+    /// statement with a different query or bound values.
+    pub async fn execute_page<T: FromRow>(
+        self,
+        cursor: Option<&PagingCursor>,
+        page_size: i32,
+    ) -> Result<(Vec<T>, Option<PagingCursor>)> {
+        paging::execute_page(self, cursor, page_size).await
+    }
+
     //    ///Binds an arbitrary CassBindable type to a cassandra statement
     //    ///FIXME not yet implemented
     //    pub fn bind(&mut self, params: Vec<CassBindable>) {
@@ -496,7 +943,11 @@ impl Statement {
 
     /// Binds null to a query or bound statement at the specified index.
     pub fn bind_null(&mut self, index: usize) -> Result<&mut Self> {
-        unsafe { cass_statement_bind_null(self.inner(), index).to_result(self) }
+        unsafe {
+            cass_statement_bind_null(self.inner(), index)
+                .to_result(self)
+                .map(|s| s.record(index, "null", &[]))
+        }
     }
 
     /// Binds a null to all the values with the specified name.
@@ -506,55 +957,83 @@ impl Statement {
     pub fn bind_null_by_name(&mut self, name: &str) -> Result<&mut Self> {
         unsafe {
             let name_ptr = name.as_ptr() as *const c_char;
-            cass_statement_bind_null_by_name_n(self.inner(), name_ptr, name.len()).to_result(self)
+            cass_statement_bind_null_by_name_n(self.inner(), name_ptr, name.len())
+                .to_result(self)
+                .map(|s| s.record(name, "null", &[]))
         }
     }
 
     /// Binds a "tinyint" to a query or bound statement at the specified index.
     pub fn bind_int8(&mut self, index: usize, value: i8) -> Result<&mut Self> {
-        unsafe { cass_statement_bind_int8(self.inner(), index, value).to_result(self) }
+        self.check(index, &Provided::Scalar("tinyint"))?;
+        unsafe {
+            cass_statement_bind_int8(self.inner(), index, value)
+                .to_result(self)
+                .map(|s| s.record(index, "tinyint", &[&value.to_be_bytes()]))
+        }
     }
 
     /// Binds a "tinyint" to all the values with the specified name.
     pub fn bind_int8_by_name(&mut self, name: &str, value: i8) -> Result<&mut Self> {
+        self.check(name, &Provided::Scalar("tinyint"))?;
         unsafe {
             let name_ptr = name.as_ptr() as *const c_char;
             cass_statement_bind_int8_by_name_n(self.inner(), name_ptr, name.len(), value)
                 .to_result(self)
+                .map(|s| s.record(name, "tinyint", &[&value.to_be_bytes()]))
         }
     }
 
     /// Binds an "smallint" to a query or bound statement at the specified index.
     pub fn bind_int16(&mut self, index: usize, value: i16) -> Result<&mut Self> {
-        unsafe { cass_statement_bind_int16(self.inner(), index, value).to_result(self) }
+        self.check(index, &Provided::Scalar("smallint"))?;
+        unsafe {
+            cass_statement_bind_int16(self.inner(), index, value)
+                .to_result(self)
+                .map(|s| s.record(index, "smallint", &[&value.to_be_bytes()]))
+        }
     }
 
     /// Binds a "smallint" to all the values with the specified name.
     pub fn bind_int16_by_name(&mut self, name: &str, value: i16) -> Result<&mut Self> {
+        self.check(name, &Provided::Scalar("smallint"))?;
         unsafe {
             let name_ptr = name.as_ptr() as *const c_char;
             cass_statement_bind_int16_by_name_n(self.inner(), name_ptr, name.len(), value)
                 .to_result(self)
+                .map(|s| s.record(name, "smallint", &[&value.to_be_bytes()]))
         }
     }
 
     /// Binds an "int" to a query or bound statement at the specified index.
     pub fn bind_int32(&mut self, index: usize, value: i32) -> Result<&mut Self> {
-        unsafe { cass_statement_bind_int32(self.inner(), index, value).to_result(self) }
+        self.check(index, &Provided::Scalar("int"))?;
+        unsafe {
+            cass_statement_bind_int32(self.inner(), index, value)
+                .to_result(self)
+                .map(|s| s.record(index, "int", &[&value.to_be_bytes()]))
+        }
     }
 
     /// Binds an "int" to all the values with the specified name.
     pub fn bind_int32_by_name(&mut self, name: &str, value: i32) -> Result<&mut Self> {
+        self.check(name, &Provided::Scalar("int"))?;
         unsafe {
             let name_ptr = name.as_ptr() as *const c_char;
             cass_statement_bind_int32_by_name_n(self.inner(), name_ptr, name.len(), value)
                 .to_result(self)
+                .map(|s| s.record(name, "int", &[&value.to_be_bytes()]))
         }
     }
 
     /// Binds a "date" to a query or bound statement at the specified index.
     pub fn bind_uint32(&mut self, index: usize, value: u32) -> Result<&mut Self> {
-        unsafe { cass_statement_bind_uint32(self.inner(), index, value).to_result(self) }
+        self.check(index, &Provided::Scalar("date"))?;
+        unsafe {
+            cass_statement_bind_uint32(self.inner(), index, value)
+                .to_result(self)
+                .map(|s| s.record(index, "date", &[&value.to_be_bytes()]))
+        }
     }
 
     /// Binds a "date" to all the values with the specified name.
@@ -562,32 +1041,46 @@ impl Statement {
     /// This can only be used with statements created by
     /// cass_prepared_bind().
     pub fn bind_uint32_by_name(&mut self, name: &str, value: u32) -> Result<&mut Self> {
+        self.check(name, &Provided::Scalar("date"))?;
         unsafe {
             let name_ptr = name.as_ptr() as *const c_char;
             cass_statement_bind_uint32_by_name_n(self.inner(), name_ptr, name.len(), value)
                 .to_result(self)
+                .map(|s| s.record(name, "date", &[&value.to_be_bytes()]))
         }
     }
 
     /// Binds a "bigint", "counter", "timestamp" or "time" to a query or
     /// bound statement at the specified index.
     pub fn bind_int64(&mut self, index: usize, value: i64) -> Result<&mut Self> {
-        unsafe { cass_statement_bind_int64(self.inner(), index, value).to_result(self) }
+        self.check(index, &Provided::Scalar("bigint"))?;
+        unsafe {
+            cass_statement_bind_int64(self.inner(), index, value)
+                .to_result(self)
+                .map(|s| s.record(index, "bigint", &[&value.to_be_bytes()]))
+        }
     }
 
     /// Binds a "bigint", "counter", "timestamp" or "time" to all values
     /// with the specified name.
     pub fn bind_int64_by_name(&mut self, name: &str, value: i64) -> Result<&mut Self> {
+        self.check(name, &Provided::Scalar("bigint"))?;
         unsafe {
             let name_ptr = name.as_ptr() as *const c_char;
             cass_statement_bind_int64_by_name_n(self.inner(), name_ptr, name.len(), value)
                 .to_result(self)
+                .map(|s| s.record(name, "bigint", &[&value.to_be_bytes()]))
         }
     }
 
     /// Binds a "float" to a query or bound statement at the specified index.
     pub fn bind_float(&mut self, index: usize, value: f32) -> Result<&mut Self> {
-        unsafe { cass_statement_bind_float(self.inner(), index, value).to_result(self) }
+        self.check(index, &Provided::Scalar("float"))?;
+        unsafe {
+            cass_statement_bind_float(self.inner(), index, value)
+                .to_result(self)
+                .map(|s| s.record(index, "float", &[&value.to_bits().to_be_bytes()]))
+        }
     }
 
     /// Binds a "float" to all the values with the specified name.
@@ -595,16 +1088,23 @@ impl Statement {
     /// This can only be used with statements created by
     /// cass_prepared_bind().
     pub fn bind_float_by_name(&mut self, name: &str, value: f32) -> Result<&mut Self> {
+        self.check(name, &Provided::Scalar("float"))?;
         unsafe {
             let name_ptr = name.as_ptr() as *const c_char;
             cass_statement_bind_float_by_name_n(self.inner(), name_ptr, name.len(), value)
                 .to_result(self)
+                .map(|s| s.record(name, "float", &[&value.to_bits().to_be_bytes()]))
         }
     }
 
     /// Binds a "double" to a query or bound statement at the specified index.
     pub fn bind_double(&mut self, index: usize, value: f64) -> Result<&mut Self> {
-        unsafe { cass_statement_bind_double(self.inner(), index, value).to_result(self) }
+        self.check(index, &Provided::Scalar("double"))?;
+        unsafe {
+            cass_statement_bind_double(self.inner(), index, value)
+                .to_result(self)
+                .map(|s| s.record(index, "double", &[&value.to_bits().to_be_bytes()]))
+        }
     }
 
     /// Binds a "double" to all the values with the specified name.
@@ -612,15 +1112,18 @@ impl Statement {
     /// This can only be used with statements created by
     /// cass_prepared_bind().
     pub fn bind_double_by_name(&mut self, name: &str, value: f64) -> Result<&mut Self> {
+        self.check(name, &Provided::Scalar("double"))?;
         unsafe {
             let name_ptr = name.as_ptr() as *const c_char;
             cass_statement_bind_double_by_name_n(self.inner(), name_ptr, name.len(), value)
                 .to_result(self)
+                .map(|s| s.record(name, "double", &[&value.to_bits().to_be_bytes()]))
         }
     }
 
     /// Binds a "boolean" to a query or bound statement at the specified index.
     pub fn bind_bool(&mut self, index: usize, value: bool) -> Result<&mut Self> {
+        self.check(index, &Provided::Scalar("boolean"))?;
         unsafe {
             cass_statement_bind_bool(
                 self.inner(),
@@ -628,6 +1131,7 @@ impl Statement {
                 if value { cass_true } else { cass_false },
             )
             .to_result(self)
+            .map(|s| s.record(index, "boolean", &[&[value as u8]]))
         }
     }
 
@@ -636,6 +1140,7 @@ impl Statement {
     /// This can only be used with statements created by
     /// cass_prepared_bind().
     pub fn bind_bool_by_name(&mut self, name: &str, value: bool) -> Result<&mut Self> {
+        self.check(name, &Provided::Scalar("boolean"))?;
         unsafe {
             let name_ptr = name.as_ptr() as *const c_char;
             cass_statement_bind_bool_by_name_n(
@@ -645,16 +1150,19 @@ impl Statement {
                 if value { cass_true } else { cass_false },
             )
             .to_result(self)
+            .map(|s| s.record(name, "boolean", &[&[value as u8]]))
         }
     }
 
     /// Binds an "ascii", "text" or "varchar" to a query or bound statement
     /// at the specified index.
     pub fn bind_string(&mut self, index: usize, value: &str) -> Result<&mut Self> {
+        self.check(index, &Provided::Scalar("text"))?;
         unsafe {
             let value_ptr = value.as_ptr() as *const c_char;
             cass_statement_bind_string_n(self.inner(), index, value_ptr, value.len())
                 .to_result(self)
+                .map(|s| s.record(index, "text", &[value.as_bytes()]))
         }
     }
 
@@ -664,6 +1172,7 @@ impl Statement {
     /// This can only be used with statements created by
     /// cass_prepared_bind().
     pub fn bind_string_by_name(&mut self, name: &str, value: &str) -> Result<&mut Self> {
+        self.check(name, &Provided::Scalar("text"))?;
         unsafe {
             let name_ptr = name.as_ptr() as *const c_char;
 
@@ -680,14 +1189,17 @@ impl Statement {
                 value.len(),
             )
             .to_result(self)
+            .map(|s| s.record(name, "text", &[value.as_bytes()]))
         }
     }
 
     /// Binds a "blob", "varint" or "custom" to a query or bound statement at the specified index.
     pub fn bind_bytes(&mut self, index: usize, value: Vec<u8>) -> Result<&mut Self> {
+        self.check(index, &Provided::Scalar("blob"))?;
         unsafe {
             cass_statement_bind_bytes(self.inner(), index, value.as_ptr(), value.len())
                 .to_result(self)
+                .map(|s| s.record(index, "blob", &[&value]))
         }
     }
 
@@ -697,6 +1209,7 @@ impl Statement {
     /// This can only be used with statements created by
     /// cass_prepared_bind().
     pub fn bind_bytes_by_name(&mut self, name: &str, mut value: Vec<u8>) -> Result<&mut Self> {
+        self.check(name, &Provided::Scalar("blob"))?;
         unsafe {
             let name_ptr = name.as_ptr() as *const c_char;
             cass_statement_bind_bytes_by_name_n(
@@ -707,12 +1220,18 @@ impl Statement {
                 value.len(),
             )
             .to_result(self)
+            .map(|s| s.record(name, "blob", &[&value]))
         }
     }
 
     /// Binds a "uuid" or "timeuuid" to a query or bound statement at the specified index.
     pub fn bind_uuid(&mut self, index: usize, value: Uuid) -> Result<&mut Self> {
-        unsafe { cass_statement_bind_uuid(self.inner(), index, value.inner()).to_result(self) }
+        self.check(index, &Provided::Scalar("uuid"))?;
+        unsafe {
+            cass_statement_bind_uuid(self.inner(), index, value.inner())
+                .to_result(self)
+                .map(|s| s.record(index, "uuid", &[uuid::Uuid::from(value).as_bytes()]))
+        }
     }
 
     /// Binds a "uuid" or "timeuuid" to all the values
@@ -721,29 +1240,39 @@ impl Statement {
     /// This can only be used with statements created by
     /// cass_prepared_bind().
     pub fn bind_uuid_by_name(&mut self, name: &str, value: Uuid) -> Result<&mut Self> {
+        self.check(name, &Provided::Scalar("uuid"))?;
         unsafe {
             let name_ptr = name.as_ptr() as *const c_char;
             cass_statement_bind_uuid_by_name_n(self.inner(), name_ptr, name.len(), value.inner())
                 .to_result(self)
+                .map(|s| s.record(name, "uuid", &[uuid::Uuid::from(value).as_bytes()]))
         }
     }
 
     /// Binds an "inet" to a query or bound statement at the specified index.
     pub fn bind_inet(&mut self, index: usize, value: Inet) -> Result<&mut Self> {
-        unsafe { cass_statement_bind_inet(self.inner(), index, value.inner()).to_result(self) }
+        self.check(index, &Provided::Scalar("inet"))?;
+        unsafe {
+            cass_statement_bind_inet(self.inner(), index, value.inner())
+                .to_result(self)
+                .map(|s| s.record(index, "inet", &[value.bytes()]))
+        }
     }
 
     /// Binds an "inet" to all the values with the specified name.
     pub fn bind_inet_by_name(&mut self, name: &str, value: Inet) -> Result<&mut Self> {
+        self.check(name, &Provided::Scalar("inet"))?;
         unsafe {
             let name_ptr = name.as_ptr() as *const c_char;
             cass_statement_bind_inet_by_name_n(self.inner(), name_ptr, name.len(), value.inner())
                 .to_result(self)
+                .map(|s| s.record(name, "inet", &[value.bytes()]))
         }
     }
 
     /// Binds a "BigDecimal" to a query or bound statement at the specified index.
     pub fn bind_decimal(&mut self, index: usize, value: &BigDecimal) -> Result<&mut Self> {
+        self.check(index, &Provided::Scalar("decimal"))?;
         let dec_parts = value.as_bigint_and_exponent();
         let varint = dec_parts.0.to_signed_bytes_be();
         let scale: i32 = match dec_parts.1.try_into() {
@@ -756,11 +1285,13 @@ impl Statement {
         unsafe {
             cass_statement_bind_decimal(self.inner(), index, varint.as_ptr(), varint.len(), scale)
                 .to_result(self)
+                .map(|s| s.record(index, "decimal", &[&scale.to_be_bytes(), &varint]))
         }
     }
 
     /// Binds an "BigDecimal" to all the values with the specified name.
     pub fn bind_decimal_by_name(&mut self, name: &str, value: &BigDecimal) -> Result<&mut Self> {
+        self.check(name, &Provided::Scalar("decimal"))?;
         let dec_parts = value.as_bigint_and_exponent();
         let varint = dec_parts.0.to_signed_bytes_be();
         let scale: i32 = match dec_parts.1.try_into() {
@@ -781,12 +1312,21 @@ impl Statement {
                 scale,
             )
             .to_result(self)
+            .map(|s| s.record(name, "decimal", &[&scale.to_be_bytes(), &varint]))
         }
     }
 
     /// Bind a "map" to a query or bound statement at the specified index.
-    pub fn bind_map(&mut self, index: usize, map: Map) -> Result<&mut Self> {
-        unsafe { cass_statement_bind_collection(self.inner(), index, map.inner()).to_result(self) }
+    ///
+    /// The map is copied when bound, so may be passed by reference.
+    pub fn bind_map(&mut self, index: usize, map: impl Borrow<Map>) -> Result<&mut Self> {
+        let map = map.borrow();
+        self.check(index, &map.provided())?;
+        unsafe {
+            cass_statement_bind_collection(self.inner(), index, map.inner())
+                .to_result(self)
+                .map(|s| s.record_opaque(index, "map"))
+        }
     }
 
     /// Bind a "map" to all the values with the
@@ -794,7 +1334,9 @@ impl Statement {
     ///
     /// This can only be used with statements created by
     /// cass_prepared_bind().
-    pub fn bind_map_by_name(&mut self, name: &str, map: Map) -> Result<&mut Self> {
+    pub fn bind_map_by_name(&mut self, name: &str, map: impl Borrow<Map>) -> Result<&mut Self> {
+        let map = map.borrow();
+        self.check(name, &map.provided())?;
         unsafe {
             let name_ptr = name.as_ptr() as *const c_char;
             cass_statement_bind_collection_by_name_n(
@@ -804,12 +1346,19 @@ impl Statement {
                 map.inner(),
             )
             .to_result(self)
+            .map(|s| s.record_opaque(name, "map"))
         }
     }
     /// Bind a "set" to a query or bound statement at the specified index.
-    pub fn bind_set(&mut self, index: usize, collection: Set) -> Result<&mut Self> {
+    ///
+    /// The set is copied when bound, so may be passed by reference.
+    pub fn bind_set(&mut self, index: usize, collection: impl Borrow<Set>) -> Result<&mut Self> {
+        let collection = collection.borrow();
+        self.check(index, &collection.provided())?;
         unsafe {
-            cass_statement_bind_collection(self.inner(), index, collection.inner()).to_result(self)
+            cass_statement_bind_collection(self.inner(), index, collection.inner())
+                .to_result(self)
+                .map(|s| s.record_opaque(index, "set"))
         }
     }
 
@@ -818,7 +1367,13 @@ impl Statement {
     ///
     /// This can only be used with statements created by
     /// cass_prepared_bind().
-    pub fn bind_set_by_name(&mut self, name: &str, collection: Set) -> Result<&mut Self> {
+    pub fn bind_set_by_name(
+        &mut self,
+        name: &str,
+        collection: impl Borrow<Set>,
+    ) -> Result<&mut Self> {
+        let collection = collection.borrow();
+        self.check(name, &collection.provided())?;
         unsafe {
             let name_ptr = name.as_ptr() as *const c_char;
             cass_statement_bind_collection_by_name_n(
@@ -828,13 +1383,20 @@ impl Statement {
                 collection.inner(),
             )
             .to_result(self)
+            .map(|s| s.record_opaque(name, "set"))
         }
     }
 
     /// Bind a "list" to a query or bound statement at the specified index.
-    pub fn bind_list(&mut self, index: usize, collection: List) -> Result<&mut Self> {
+    ///
+    /// The list is copied when bound, so may be passed by reference.
+    pub fn bind_list(&mut self, index: usize, collection: impl Borrow<List>) -> Result<&mut Self> {
+        let collection = collection.borrow();
+        self.check(index, &collection.provided())?;
         unsafe {
-            cass_statement_bind_collection(self.inner(), index, collection.inner()).to_result(self)
+            cass_statement_bind_collection(self.inner(), index, collection.inner())
+                .to_result(self)
+                .map(|s| s.record_opaque(index, "list"))
         }
     }
 
@@ -843,7 +1405,13 @@ impl Statement {
     ///
     /// This can only be used with statements created by
     /// cass_prepared_bind().
-    pub fn bind_list_by_name(&mut self, name: &str, collection: List) -> Result<&mut Self> {
+    pub fn bind_list_by_name(
+        &mut self,
+        name: &str,
+        collection: impl Borrow<List>,
+    ) -> Result<&mut Self> {
+        let collection = collection.borrow();
+        self.check(name, &collection.provided())?;
         unsafe {
             let name_ptr = name.as_ptr() as *const c_char;
             cass_statement_bind_collection_by_name_n(
@@ -853,35 +1421,57 @@ impl Statement {
                 collection.inner(),
             )
             .to_result(self)
+            .map(|s| s.record_opaque(name, "list"))
         }
     }
 
     /// Bind a "tuple" to a query or bound statement at the specified index.
-    pub fn bind_tuple(&mut self, index: usize, value: Tuple) -> Result<&mut Self> {
-        unsafe { cass_statement_bind_tuple(self.inner(), index, value.inner()).to_result(self) }
+    ///
+    /// The tuple is copied when bound, so may be passed by reference.
+    pub fn bind_tuple(&mut self, index: usize, value: impl Borrow<Tuple>) -> Result<&mut Self> {
+        let value = value.borrow();
+        self.check(index, &value.provided())?;
+        unsafe {
+            cass_statement_bind_tuple(self.inner(), index, value.inner())
+                .to_result(self)
+                .map(|s| s.record_opaque(index, "tuple"))
+        }
     }
 
     /// Bind a "tuple" to all the values with the specified name.
     ///
     /// This can only be used with statements created by
     /// cass_prepared_bind().
-    pub fn bind_tuple_by_name(&mut self, name: &str, value: Tuple) -> Result<&mut Self> {
+    pub fn bind_tuple_by_name(
+        &mut self,
+        name: &str,
+        value: impl Borrow<Tuple>,
+    ) -> Result<&mut Self> {
+        let value = value.borrow();
+        self.check(name, &value.provided())?;
         unsafe {
             let name_ptr = name.as_ptr() as *const c_char;
             cass_statement_bind_tuple_by_name_n(self.inner(), name_ptr, name.len(), value.inner())
                 .to_result(self)
+                .map(|s| s.record_opaque(name, "tuple"))
         }
     }
 
     /// Bind a user defined type to a query or bound statement at the
     /// specified index.
     pub fn bind_user_type(&mut self, index: usize, value: &UserType) -> Result<&mut Self> {
-        unsafe { cass_statement_bind_user_type(self.inner(), index, value.inner()).to_result(self) }
+        self.check(index, &value.provided())?;
+        unsafe {
+            cass_statement_bind_user_type(self.inner(), index, value.inner())
+                .to_result(self)
+                .map(|s| s.record_opaque(index, "udt"))
+        }
     }
 
     /// Bind a user defined type to a query or bound statement with the
     /// specified name.
     pub fn bind_user_type_by_name(&mut self, name: &str, value: &UserType) -> Result<&mut Self> {
+        self.check(name, &value.provided())?;
         unsafe {
             let name_ptr = name.as_ptr() as *const c_char;
             cass_statement_bind_user_type_by_name_n(
@@ -891,6 +1481,7 @@ impl Statement {
                 value.inner(),
             )
             .to_result(self)
+            .map(|s| s.record_opaque(name, "udt"))
         }
     }
 }
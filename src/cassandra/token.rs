@@ -0,0 +1,596 @@
+use crate::cassandra::error::*;
+use crate::cassandra::inet::Inet;
+use crate::cassandra::iterator::LendingIterator;
+use crate::cassandra::row::Row;
+use crate::cassandra::schema::keyspace_meta::KeyspaceMeta;
+use crate::cassandra::uuid::Uuid;
+use crate::cassandra::value::Value;
+use crate::Session;
+
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::convert::TryInto;
+use std::net::IpAddr;
+
+/// A value which can form (part of) a partition key, serialized as Cassandra
+/// does when computing the partition token.
+pub trait KeyComponent {
+    /// The serialized form of the value.
+    fn to_key_bytes(&self) -> Vec<u8>;
+}
+
+macro_rules! impl_key_component_be {
+    ($($t:ty),*) => {
+        $(
+            impl KeyComponent for $t {
+                fn to_key_bytes(&self) -> Vec<u8> {
+                    self.to_be_bytes().to_vec()
+                }
+            }
+        )*
+    };
+}
+
+impl_key_component_be!(i8, i16, i32, i64, u32);
+
+impl KeyComponent for bool {
+    fn to_key_bytes(&self) -> Vec<u8> {
+        vec![*self as u8]
+    }
+}
+
+impl KeyComponent for f32 {
+    fn to_key_bytes(&self) -> Vec<u8> {
+        self.to_bits().to_be_bytes().to_vec()
+    }
+}
+
+impl KeyComponent for f64 {
+    fn to_key_bytes(&self) -> Vec<u8> {
+        self.to_bits().to_be_bytes().to_vec()
+    }
+}
+
+impl KeyComponent for str {
+    fn to_key_bytes(&self) -> Vec<u8> {
+        self.as_bytes().to_vec()
+    }
+}
+
+impl KeyComponent for String {
+    fn to_key_bytes(&self) -> Vec<u8> {
+        self.as_bytes().to_vec()
+    }
+}
+
+impl KeyComponent for [u8] {
+    fn to_key_bytes(&self) -> Vec<u8> {
+        self.to_vec()
+    }
+}
+
+impl KeyComponent for Vec<u8> {
+    fn to_key_bytes(&self) -> Vec<u8> {
+        self.clone()
+    }
+}
+
+impl KeyComponent for Uuid {
+    fn to_key_bytes(&self) -> Vec<u8> {
+        uuid::Uuid::from(*self).as_bytes().to_vec()
+    }
+}
+
+impl KeyComponent for uuid::Uuid {
+    fn to_key_bytes(&self) -> Vec<u8> {
+        self.as_bytes().to_vec()
+    }
+}
+
+impl KeyComponent for Inet {
+    fn to_key_bytes(&self) -> Vec<u8> {
+        match IpAddr::from(self) {
+            IpAddr::V4(address) => address.octets().to_vec(),
+            IpAddr::V6(address) => address.octets().to_vec(),
+        }
+    }
+}
+
+impl<T: KeyComponent + ?Sized> KeyComponent for &T {
+    fn to_key_bytes(&self) -> Vec<u8> {
+        (**self).to_key_bytes()
+    }
+}
+
+/// Serialize a partition key as Cassandra does: a single component as is,
+/// and each component of a composite key as its length (a 16-bit big-endian
+/// integer), its bytes, and a zero byte.
+pub fn partition_key_bytes(components: &[&dyn KeyComponent]) -> Vec<u8> {
+    match components {
+        [component] => component.to_key_bytes(),
+        _ => {
+            let mut bytes = vec![];
+            for component in components {
+                let component = component.to_key_bytes();
+                bytes.extend_from_slice(&(component.len() as u16).to_be_bytes());
+                bytes.extend_from_slice(&component);
+                bytes.push(0);
+            }
+            bytes
+        }
+    }
+}
+
+/// Compute the `Murmur3Partitioner` token of a partition key.
+///
+/// ```
+/// # use cassandra_cpp::*;
+/// assert_eq!(partition_token(&[&1i32]), -4069959284402364209);
+/// let token = partition_token(&[&"sensor-1", &20240101i32]);
+/// ```
+pub fn partition_token(components: &[&dyn KeyComponent]) -> i64 {
+    murmur3_token(&partition_key_bytes(components))
+}
+
+/// Finalization mix of MurmurHash3.
+fn fmix(mut k: u64) -> u64 {
+    k ^= k >> 33;
+    k = k.wrapping_mul(0xff51_afd7_ed55_8ccd);
+    k ^= k >> 33;
+    k = k.wrapping_mul(0xc4ce_b9fe_1a85_ec53);
+    k ^= k >> 33;
+    k
+}
+
+/// Compute the `Murmur3Partitioner` token of a serialized partition key.
+///
+/// This is the first half of the x64 128-bit MurmurHash3 with a zero seed, as
+/// implemented by Cassandra. Cassandra's implementation differs from the
+/// reference one in sign-extending the trailing bytes, and maps the minimum
+/// token (which it reserves) to the maximum.
+pub fn murmur3_token(key: &[u8]) -> i64 {
+    const C1: u64 = 0x87c3_7b91_1142_53d5;
+    const C2: u64 = 0x4cf5_ad43_2745_937f;
+
+    let mut h1: u64 = 0;
+    let mut h2: u64 = 0;
+    let mut blocks = key.chunks_exact(16);
+    for block in &mut blocks {
+        let mut k1 = u64::from_le_bytes(block[..8].try_into().unwrap());
+        let mut k2 = u64::from_le_bytes(block[8..].try_into().unwrap());
+
+        k1 = k1.wrapping_mul(C1).rotate_left(31).wrapping_mul(C2);
+        h1 ^= k1;
+        h1 = h1
+            .rotate_left(27)
+            .wrapping_add(h2)
+            .wrapping_mul(5)
+            .wrapping_add(0x52dc_e729);
+
+        k2 = k2.wrapping_mul(C2).rotate_left(33).wrapping_mul(C1);
+        h2 ^= k2;
+        h2 = h2
+            .rotate_left(31)
+            .wrapping_add(h1)
+            .wrapping_mul(5)
+            .wrapping_add(0x3849_5ab5);
+    }
+
+    // Java bytes are signed, so Cassandra sign-extends each trailing byte.
+    let tail = blocks.remainder();
+    let byte = |i: usize| tail[i] as i8 as i64 as u64;
+    if tail.len() > 8 {
+        let mut k2 = 0;
+        for i in (8..tail.len()).rev() {
+            k2 ^= byte(i) << ((i - 8) * 8);
+        }
+        h2 ^= k2.wrapping_mul(C2).rotate_left(33).wrapping_mul(C1);
+    }
+    if !tail.is_empty() {
+        let mut k1 = 0;
+        for i in (0..tail.len().min(8)).rev() {
+            k1 ^= byte(i) << (i * 8);
+        }
+        h1 ^= k1.wrapping_mul(C1).rotate_left(31).wrapping_mul(C2);
+    }
+
+    h1 ^= key.len() as u64;
+    h2 ^= key.len() as u64;
+    h1 = h1.wrapping_add(h2);
+    h2 = h2.wrapping_add(h1);
+    h1 = fmix(h1);
+    h2 = fmix(h2);
+    h1 = h1.wrapping_add(h2);
+
+    match h1 as i64 {
+        i64::MIN => i64::MAX,
+        token => token,
+    }
+}
+
+/// The replication strategy of a keyspace.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Replication {
+    /// `SimpleStrategy`: the given number of consecutive hosts on the ring.
+    Simple {
+        /// The number of replicas.
+        replication_factor: usize,
+    },
+    /// `NetworkTopologyStrategy`: the given number of replicas in each data
+    /// center, spread across racks where possible.
+    NetworkTopology(HashMap<String, usize>),
+    /// `EverywhereStrategy`: every host.
+    Everywhere,
+    /// `LocalStrategy`: only the host the data was written on.
+    Local,
+}
+
+impl Replication {
+    /// Read the replication settings of a keyspace.
+    pub fn from_keyspace(keyspace: &KeyspaceMeta) -> Result<Self> {
+        let mut options = HashMap::new();
+        if let Some(field) = keyspace.field_by_name("replication") {
+            let value = field.value();
+            if !value.is_null() {
+                let mut iter = value.get_map()?;
+                while let Some((key, value)) = iter.next() {
+                    options.insert(key.get_string()?, value.get_string()?);
+                }
+            }
+        }
+        Replication::from_options(&options)
+    }
+
+    /// Interpret the options of a keyspace's replication map, e.g.,
+    /// `{'class': 'SimpleStrategy', 'replication_factor': '3'}`.
+    pub fn from_options(options: &HashMap<String, String>) -> Result<Self> {
+        let parse = |value: &String| {
+            value.parse::<usize>().map_err(|_| {
+                Error::from(ErrorKind::UnsupportedReplication(format!(
+                    "bad replication factor {:?}",
+                    value
+                )))
+            })
+        };
+        let class = options.get("class").map(String::as_str).unwrap_or_default();
+        match class.rsplit('.').next().unwrap_or_default() {
+            "SimpleStrategy" => {
+                let replication_factor = match options.get("replication_factor") {
+                    Some(value) => parse(value)?,
+                    None => 1,
+                };
+                Ok(Replication::Simple { replication_factor })
+            }
+            "NetworkTopologyStrategy" => options
+                .iter()
+                .filter(|(key, _)| key.as_str() != "class")
+                .map(|(dc, value)| Ok((dc.clone(), parse(value)?)))
+                .collect::<Result<_>>()
+                .map(Replication::NetworkTopology),
+            "EverywhereStrategy" => Ok(Replication::Everywhere),
+            "LocalStrategy" => Ok(Replication::Local),
+            _ => Err(ErrorKind::UnsupportedReplication(class.to_string()).into()),
+        }
+    }
+}
+
+/// A host in a `TokenRing`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RingHost {
+    /// The address clients connect to.
+    pub address: Inet,
+    /// The data center of the host.
+    pub data_center: String,
+    /// The rack of the host.
+    pub rack: String,
+}
+
+/// The token ring of a cluster: which host owns which tokens. Used to find
+/// the replicas of a partition key.
+#[derive(Debug, Clone)]
+pub struct TokenRing {
+    hosts: Vec<RingHost>,
+    /// The host the ring was read from.
+    local: usize,
+    /// Every token, and the index of the host owning it, in token order.
+    ring: Vec<(i64, usize)>,
+}
+
+/// Get a column which may be null.
+fn nullable<'a, T>(
+    row: &Row<'a>,
+    name: &str,
+    get: impl FnOnce(&Value<'a>) -> Result<T>,
+) -> Result<Option<T>> {
+    let value = row.get_column_by_name(name)?;
+    if value.is_null() {
+        Ok(None)
+    } else {
+        get(&value).map(Some)
+    }
+}
+
+/// Read a host and its tokens from a row of `system.local` or `system.peers`.
+fn read_host(row: &Row, fallback_address: &str) -> Result<(RingHost, Vec<i64>)> {
+    let unspecified = |address: &Inet| IpAddr::from(address).is_unspecified();
+    let address = match nullable(row, "rpc_address", Value::get_inet)? {
+        Some(address) if !unspecified(&address) => address,
+        _ => row.get_column_by_name(fallback_address)?.get_inet()?,
+    };
+    let mut tokens = vec![];
+    if let Some(mut iter) = nullable(row, "tokens", Value::get_set)? {
+        while let Some(token) = iter.next() {
+            let token = token.get_str()?;
+            tokens.push(token.parse().map_err(|_| {
+                Error::from(ErrorKind::UnsupportedReplication(format!(
+                    "token {:?} is not a Murmur3 token",
+                    token
+                )))
+            })?);
+        }
+    }
+    let host = RingHost {
+        address,
+        data_center: nullable(row, "data_center", Value::get_string)?.unwrap_or_default(),
+        rack: nullable(row, "rack", Value::get_string)?.unwrap_or_default(),
+    };
+    Ok((host, tokens))
+}
+
+impl TokenRing {
+    /// Read the token ring from the `system.local` and `system.peers` tables
+    /// of the host the session's request is sent to.
+    pub async fn fetch(session: &Session) -> Result<Self> {
+        let local = session
+            .execute(
+                "SELECT rpc_address, broadcast_address, data_center, rack, tokens \
+                 FROM system.local",
+            )
+            .await?;
+        let peers = session
+            .execute("SELECT rpc_address, peer, data_center, rack, tokens FROM system.peers")
+            .await?;
+
+        let mut hosts = vec![];
+        if let Some(row) = local.first_row() {
+            hosts.push(read_host(&row, "broadcast_address")?);
+        }
+        let mut iter = peers.iter();
+        while let Some(row) = iter.next() {
+            hosts.push(read_host(&row, "peer")?);
+        }
+        Ok(TokenRing::new(hosts))
+    }
+
+    /// Build a ring from a list of hosts and their tokens. The first host is
+    /// taken to be the local host, for `LocalStrategy`.
+    pub fn new(hosts: Vec<(RingHost, Vec<i64>)>) -> Self {
+        let mut ring = vec![];
+        let hosts = hosts
+            .into_iter()
+            .enumerate()
+            .map(|(index, (host, tokens))| {
+                ring.extend(tokens.into_iter().map(|token| (token, index)));
+                host
+            })
+            .collect();
+        ring.sort_unstable();
+        TokenRing {
+            hosts,
+            local: 0,
+            ring,
+        }
+    }
+
+    /// All hosts in the ring.
+    pub fn hosts(&self) -> &[RingHost] {
+        &self.hosts
+    }
+
+    /// The index into the ring of the first token at or after `token`.
+    fn position(&self, token: i64) -> usize {
+        match self.ring.binary_search_by_key(&token, |(t, _)| *t) {
+            Ok(position) => position,
+            Err(position) if position == self.ring.len() => 0,
+            Err(position) => position,
+        }
+    }
+
+    /// The hosts owning tokens, walking round the ring from `token`.
+    fn walk(&self, token: i64) -> impl Iterator<Item = usize> + '_ {
+        let start = self.position(token);
+        (0..self.ring.len()).map(move |offset| self.ring[(start + offset) % self.ring.len()].1)
+    }
+
+    /// The host owning `token`, i.e., the primary replica.
+    pub fn primary(&self, token: i64) -> Option<&RingHost> {
+        self.walk(token).next().map(|index| &self.hosts[index])
+    }
+
+    /// The replicas of `token` under the given replication strategy, primary
+    /// replica first.
+    pub fn replicas(&self, token: i64, replication: &Replication) -> Vec<&RingHost> {
+        let indices = match replication {
+            Replication::Simple { replication_factor } => {
+                let mut replicas = vec![];
+                for index in self.walk(token) {
+                    if replicas.len() == *replication_factor {
+                        break;
+                    }
+                    if !replicas.contains(&index) {
+                        replicas.push(index);
+                    }
+                }
+                replicas
+            }
+            Replication::NetworkTopology(factors) => self.network_topology(token, factors),
+            Replication::Everywhere => {
+                let mut replicas = vec![];
+                for index in self.walk(token) {
+                    if !replicas.contains(&index) {
+                        replicas.push(index);
+                    }
+                }
+                replicas
+            }
+            Replication::Local if self.hosts.is_empty() => vec![],
+            Replication::Local => vec![self.local],
+        };
+        indices
+            .into_iter()
+            .map(|index| &self.hosts[index])
+            .collect()
+    }
+
+    /// The replicas of a partition key under the given replication strategy.
+    pub fn replicas_for_key(
+        &self,
+        components: &[&dyn KeyComponent],
+        replication: &Replication,
+    ) -> Vec<&RingHost> {
+        self.replicas(partition_token(components), replication)
+    }
+
+    /// Replica placement of `NetworkTopologyStrategy`: walk the ring, taking
+    /// hosts in racks not yet used in their data center, then fill any
+    /// remaining places with the skipped hosts, in ring order.
+    fn network_topology(&self, token: i64, factors: &HashMap<String, usize>) -> Vec<usize> {
+        // The number of replicas wanted in each data center, capped at its size,
+        // and the racks in it.
+        let mut racks: HashMap<&str, HashSet<&str>> = HashMap::new();
+        let mut sizes: HashMap<&str, usize> = HashMap::new();
+        for host in &self.hosts {
+            racks
+                .entry(&host.data_center)
+                .or_default()
+                .insert(&host.rack);
+            *sizes.entry(&host.data_center).or_default() += 1;
+        }
+        let wanted: BTreeMap<&str, usize> = factors
+            .iter()
+            .filter_map(|(dc, factor)| {
+                let size = *sizes.get(dc.as_str())?;
+                Some((dc.as_str(), (*factor).min(size)))
+            })
+            .filter(|(_, factor)| *factor > 0)
+            .collect();
+
+        let mut replicas = vec![];
+        let mut placed: HashMap<&str, usize> = HashMap::new();
+        let mut seen_racks: HashMap<&str, HashSet<&str>> = HashMap::new();
+        let mut skipped: HashMap<&str, VecDeque<usize>> = HashMap::new();
+        let done = |placed: &HashMap<&str, usize>, dc: &str| {
+            placed.get(dc).copied().unwrap_or_default() >= wanted[dc]
+        };
+
+        for index in self.walk(token) {
+            if wanted.keys().all(|dc| done(&placed, dc)) {
+                break;
+            }
+            let host = &self.hosts[index];
+            let dc = host.data_center.as_str();
+            if !wanted.contains_key(dc) || done(&placed, dc) || replicas.contains(&index) {
+                continue;
+            }
+            let seen = seen_racks.entry(dc).or_default();
+            let all_racks = racks[dc].len();
+            if seen.len() == all_racks {
+                replicas.push(index);
+                *placed.entry(dc).or_default() += 1;
+            } else if seen.contains(host.rack.as_str()) {
+                let skipped = skipped.entry(dc).or_default();
+                if !skipped.contains(&index) {
+                    skipped.push_back(index);
+                }
+            } else {
+                seen.insert(&host.rack);
+                replicas.push(index);
+                *placed.entry(dc).or_default() += 1;
+                if seen.len() == all_racks {
+                    // Every rack now has a replica; use the hosts we skipped.
+                    let skipped = skipped.entry(dc).or_default();
+                    while !done(&placed, dc) {
+                        match skipped.pop_front() {
+                            Some(index) => {
+                                replicas.push(index);
+                                *placed.entry(dc).or_default() += 1;
+                            }
+                            None => break,
+                        }
+                    }
+                }
+            }
+        }
+        replicas
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_murmur3_token() {
+        assert_eq!(partition_token(&[&1i32]), -4069959284402364209);
+        assert_eq!(partition_token(&[&2i32]), -3248873570005575792);
+        assert_eq!(murmur3_token(b"hello"), -3758069500696749310);
+        // Exercises both halves of the tail, with sign extension.
+        assert_eq!(
+            murmur3_token(b"\xff\x80abcdefghijklmnopqrstuvwxyz"),
+            -7087312444009462753
+        );
+    }
+
+    #[test]
+    fn test_partition_key_bytes() {
+        assert_eq!(partition_key_bytes(&[&"ab"]), b"ab".to_vec());
+        assert_eq!(
+            partition_key_bytes(&[&"ab", &1i32]),
+            vec![0, 2, b'a', b'b', 0, 0, 4, 0, 0, 0, 1, 0]
+        );
+    }
+
+    fn host(address: &str, data_center: &str, rack: &str) -> RingHost {
+        RingHost {
+            address: address.parse().unwrap(),
+            data_center: data_center.to_string(),
+            rack: rack.to_string(),
+        }
+    }
+
+    fn addresses(hosts: Vec<&RingHost>) -> Vec<String> {
+        hosts.iter().map(|host| host.address.to_string()).collect()
+    }
+
+    #[test]
+    fn test_replicas() {
+        let ring = TokenRing::new(vec![
+            (host("10.0.0.1", "dc1", "r1"), vec![-100, 300]),
+            (host("10.0.0.2", "dc1", "r1"), vec![0]),
+            (host("10.0.0.3", "dc1", "r2"), vec![100]),
+            (host("10.0.1.1", "dc2", "r1"), vec![200]),
+        ]);
+        assert_eq!(ring.primary(-50).unwrap().address.to_string(), "10.0.0.2");
+        assert_eq!(ring.primary(301).unwrap().address.to_string(), "10.0.0.1");
+
+        let simple = Replication::Simple {
+            replication_factor: 2,
+        };
+        assert_eq!(
+            addresses(ring.replicas(50, &simple)),
+            vec!["10.0.0.3", "10.0.1.1"]
+        );
+
+        // 10.0.0.2 is skipped in favour of 10.0.0.3, which is in another rack.
+        let factors = vec![("dc1".to_string(), 2), ("dc2".to_string(), 1)];
+        let topology = Replication::NetworkTopology(factors.into_iter().collect());
+        assert_eq!(
+            addresses(ring.replicas(-50, &topology)),
+            vec!["10.0.0.2", "10.0.0.3", "10.0.1.1"]
+        );
+        assert_eq!(
+            addresses(ring.replicas(250, &topology)),
+            vec!["10.0.0.1", "10.0.0.3", "10.0.1.1"]
+        );
+        assert_eq!(ring.replicas(0, &Replication::Everywhere).len(), 4);
+    }
+}
@@ -0,0 +1,185 @@
+use crate::cassandra::consistency::Consistency;
+use crate::cassandra::error::*;
+use crate::cassandra::inet::Inet;
+use crate::cassandra::row::Row;
+use crate::cassandra::statement::BindRustType;
+use crate::cassandra::uuid::Uuid;
+use crate::cassandra::value::Value;
+use crate::{LendingIterator, Session};
+
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::time::Duration;
+
+/// Number of attempts `Session::fetch_trace` makes to read a complete trace.
+pub(crate) const DEFAULT_TRACE_ATTEMPTS: u32 = 5;
+
+/// Initial delay between attempts to read a trace; doubled after each attempt.
+pub(crate) const DEFAULT_TRACE_DELAY: Duration = Duration::from_millis(3);
+
+static SELECT_SESSION: &str = "SELECT client, command, coordinator, duration, parameters, \
+                               request, started_at FROM system_traces.sessions \
+                               WHERE session_id = ?";
+static SELECT_EVENTS: &str = "SELECT event_id, activity, source, source_elapsed, thread \
+                              FROM system_traces.events WHERE session_id = ?";
+
+/// The trace of a single request, as recorded by Cassandra in the
+/// `system_traces` keyspace.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueryTrace {
+    /// The tracing id of the request.
+    pub tracing_id: Uuid,
+    /// The address of the client which issued the request, if recorded.
+    pub client: Option<Inet>,
+    /// The type of request, e.g., `QUERY` or `EXECUTE`.
+    pub command: String,
+    /// The node which coordinated the request.
+    pub coordinator: Inet,
+    /// How long the coordinator took to handle the request.
+    pub duration: Duration,
+    /// The request parameters, e.g., the query string and consistency level.
+    pub parameters: HashMap<String, String>,
+    /// A short description of the request.
+    pub request: String,
+    /// When the request started, in milliseconds since the epoch.
+    pub started_at: i64,
+    /// The events recorded while handling the request.
+    pub events: Vec<TraceEvent>,
+}
+
+/// A single event within a `QueryTrace`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TraceEvent {
+    /// The id of this event (a time-based UUID).
+    pub event_id: Uuid,
+    /// What happened.
+    pub activity: String,
+    /// The node on which it happened.
+    pub source: Inet,
+    /// Time elapsed on the source node since it started handling the request.
+    pub source_elapsed: Duration,
+    /// The thread which recorded the event.
+    pub thread: String,
+}
+
+/// Get a column which may be null.
+fn nullable<'a, T, F>(row: &Row<'a>, name: &str, get: F) -> Result<Option<T>>
+where
+    F: FnOnce(&Value<'a>) -> Result<T>,
+{
+    let value = row.get_column_by_name(name)?;
+    if value.is_null() {
+        Ok(None)
+    } else {
+        get(&value).map(Some)
+    }
+}
+
+/// Convert a count of microseconds, as stored in the trace tables.
+fn micros(value: i32) -> Duration {
+    Duration::from_micros(value.max(0) as u64)
+}
+
+impl QueryTrace {
+    /// Read the trace from the `system_traces` tables. Returns `None` if the
+    /// trace is not (yet) complete.
+    pub(crate) async fn fetch(session: &Session, tracing_id: Uuid) -> Result<Option<QueryTrace>> {
+        let mut statement = session.statement(SELECT_SESSION);
+        statement.bind(0, tracing_id)?;
+        statement.set_consistency(Consistency::ONE)?;
+        let result = statement.execute().await?;
+        let mut trace = match result.first_row() {
+            None => return Ok(None),
+            Some(row) => {
+                // The duration is written once the coordinator has finished
+                // with the request; until then the trace is incomplete.
+                let duration = match nullable(&row, "duration", Value::get_i32)? {
+                    None => return Ok(None),
+                    Some(duration) => micros(duration),
+                };
+                let mut parameters = HashMap::new();
+                if let Some(mut iter) = nullable(&row, "parameters", Value::get_map)? {
+                    while let Some((key, value)) = iter.next() {
+                        parameters.insert(key.get_string()?, value.get_string()?);
+                    }
+                }
+                QueryTrace {
+                    tracing_id,
+                    client: nullable(&row, "client", Value::get_inet)?,
+                    command: nullable(&row, "command", Value::get_string)?.unwrap_or_default(),
+                    coordinator: row.get_column_by_name("coordinator")?.get_inet()?,
+                    duration,
+                    parameters,
+                    request: nullable(&row, "request", Value::get_string)?.unwrap_or_default(),
+                    started_at: nullable(&row, "started_at", Value::get_i64)?.unwrap_or_default(),
+                    events: vec![],
+                }
+            }
+        };
+
+        let mut statement = session.statement(SELECT_EVENTS);
+        statement.bind(0, tracing_id)?;
+        statement.set_consistency(Consistency::ONE)?;
+        let result = statement.execute().await?;
+        let mut iter = result.iter();
+        while let Some(row) = iter.next() {
+            trace.events.push(TraceEvent {
+                event_id: row.get_column_by_name("event_id")?.get_uuid()?,
+                activity: nullable(&row, "activity", Value::get_string)?.unwrap_or_default(),
+                source: row.get_column_by_name("source")?.get_inet()?,
+                source_elapsed: micros(
+                    nullable(&row, "source_elapsed", Value::get_i32)?.unwrap_or_default(),
+                ),
+                thread: nullable(&row, "thread", Value::get_string)?.unwrap_or_default(),
+            });
+        }
+        Ok(Some(trace))
+    }
+
+    /// Render the events of this trace as a text timeline, grouped by the node
+    /// on which they happened and ordered by elapsed time on that node.
+    ///
+    /// ```text
+    /// Trace 5f1a...: Execute CQL3 query on 127.0.0.1 (1234 us)
+    ///   127.0.0.1
+    ///          52 us  Parsing SELECT * FROM examples.basic [Native-Transport-Requests-1]
+    ///         110 us  Preparing statement [Native-Transport-Requests-1]
+    /// ```
+    pub fn timeline(&self) -> String {
+        let mut sources: Vec<(Inet, Vec<&TraceEvent>)> = vec![];
+        for event in &self.events {
+            match sources
+                .iter_mut()
+                .find(|(source, _)| *source == event.source)
+            {
+                Some((_, events)) => events.push(event),
+                None => sources.push((event.source, vec![event])),
+            }
+        }
+
+        let mut out = String::new();
+        // Writing to a `String` cannot fail.
+        let _ = writeln!(
+            out,
+            "Trace {}: {} on {:?} ({} us)",
+            self.tracing_id,
+            self.request,
+            self.coordinator,
+            self.duration.as_micros()
+        );
+        for (source, mut events) in sources {
+            events.sort_by_key(|event| event.source_elapsed);
+            let _ = writeln!(out, "  {:?}", source);
+            for event in events {
+                let _ = writeln!(
+                    out,
+                    "    {:>8} us  {} [{}]",
+                    event.source_elapsed.as_micros(),
+                    event.activity,
+                    event.thread
+                );
+            }
+        }
+        out
+    }
+}
@@ -3,6 +3,7 @@ use crate::cassandra::data_type::ConstDataType;
 use crate::cassandra::data_type::DataType;
 use crate::cassandra::error::*;
 use crate::cassandra::inet::Inet;
+use crate::cassandra::type_check::{ElementTypes, Provided};
 use crate::cassandra::user_type::UserType;
 use crate::cassandra::util::{Protected, ProtectedInner};
 use crate::cassandra::uuid::Uuid;
@@ -71,10 +72,15 @@ impl Tuple {
     }
 
     /// Gets the data type of a tuple.
-    pub fn data_type(&mut self) -> ConstDataType {
+    pub fn data_type(&self) -> ConstDataType {
         unsafe { ConstDataType::build(cass_tuple_data_type(self.0)) }
     }
 
+    /// What is known about the type of the tuple, for checking it when bound.
+    pub(crate) fn provided(&self) -> Provided {
+        Provided::from_data_type(&self.data_type(), &ElementTypes::default())
+    }
+
     /// Sets an null in a tuple at the specified index.
     pub fn set_null(&mut self, index: usize) -> Result<&mut Self> {
         unsafe { cass_tuple_set_null(self.0, index).to_result(self) }
@@ -0,0 +1,405 @@
+use crate::cassandra::data_type::ConstDataType;
+use crate::cassandra::value::ValueType;
+
+use std::fmt;
+
+/// The type of a statement parameter, or of a value whose full type is known,
+/// described down to its collection elements, tuple items and UDT fields.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct CqlType {
+    value_type: ValueType,
+    /// The name of a UDT.
+    name: Option<String>,
+    /// The element types of a collection (key and value for a map), the item
+    /// types of a tuple, or the field names and types of a UDT. Empty for a
+    /// collection or tuple created without a data type.
+    sub_types: Vec<(Option<String>, CqlType)>,
+}
+
+impl CqlType {
+    /// Describe a data type.
+    pub(crate) fn from_data_type(data_type: &ConstDataType) -> Self {
+        let value_type = data_type.get_type();
+        let is_udt = value_type == ValueType::UDT;
+        let sub_types = (0..data_type.sub_type_count())
+            .filter_map(|index| {
+                let sub_type = data_type.sub_data_type(index)?;
+                let name = if is_udt {
+                    data_type.sub_type_name(index).ok().map(str::to_string)
+                } else {
+                    None
+                };
+                Some((name, CqlType::from_data_type(&sub_type)))
+            })
+            .collect();
+        CqlType {
+            value_type,
+            // A UDT created on the client may have no name.
+            name: if is_udt {
+                data_type
+                    .type_name()
+                    .ok()
+                    .filter(|name| !name.is_empty())
+                    .map(str::to_string)
+            } else {
+                None
+            },
+            sub_types,
+        }
+    }
+
+    /// The value type at the top level.
+    pub(crate) fn value_type(&self) -> ValueType {
+        self.value_type
+    }
+
+    /// Whether a value of type `other` may be bound where this type is
+    /// expected. Collections and tuples without sub-types match any of the
+    /// same kind.
+    fn accepts(&self, other: &CqlType) -> bool {
+        if self.value_type == ValueType::UNKNOWN || other.value_type == ValueType::UNKNOWN {
+            return true;
+        }
+        if normalize(self.value_type) != normalize(other.value_type) {
+            return false;
+        }
+        if self.sub_types.is_empty() || other.sub_types.is_empty() {
+            return true;
+        }
+        self.sub_types.len() == other.sub_types.len()
+            && self.sub_types.iter().zip(&other.sub_types).all(
+                |((name, expected), (other_name, provided))| {
+                    names_match(name, other_name) && expected.accepts(provided)
+                },
+            )
+    }
+}
+
+impl fmt::Display for CqlType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match (self.value_type, &self.name) {
+            (ValueType::UDT, Some(name)) => return write!(f, "{}", name),
+            (ValueType::UDT, None) => {
+                write!(f, "udt<")?;
+                for (index, (name, sub_type)) in self.sub_types.iter().enumerate() {
+                    if index > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{} {}", name.as_deref().unwrap_or("?"), sub_type)?;
+                }
+                return write!(f, ">");
+            }
+            _ => {}
+        }
+        write!(f, "{}", cql_name(self.value_type))?;
+        if !self.sub_types.is_empty() {
+            write!(f, "<")?;
+            for (index, (_, sub_type)) in self.sub_types.iter().enumerate() {
+                if index > 0 {
+                    write!(f, ", ")?;
+                }
+                write!(f, "{}", sub_type)?;
+            }
+            write!(f, ">")?;
+        }
+        Ok(())
+    }
+}
+
+/// A parameter of a prepared statement.
+#[derive(Debug, Clone)]
+pub(crate) struct Parameter {
+    pub(crate) name: String,
+    pub(crate) data_type: CqlType,
+}
+
+impl Parameter {
+    /// Whether binding by `name` binds this parameter. As in the driver, names
+    /// are case-insensitive unless double-quoted.
+    pub(crate) fn is_named(&self, name: &str) -> bool {
+        if name.len() >= 2 && name.starts_with('"') && name.ends_with('"') {
+            self.name == name[1..name.len() - 1]
+        } else {
+            self.name.eq_ignore_ascii_case(name)
+        }
+    }
+}
+
+/// What is known on the client about the type of a value being bound to a
+/// statement or appended to a collection.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Provided {
+    /// A scalar, by the CQL type it was bound as, e.g. `"bigint"`, which is
+    /// also accepted for `counter`, `timestamp` and `time`. `"null"` is
+    /// accepted for anything.
+    Scalar(&'static str),
+    /// A collection created without a data type, with the distinct types of
+    /// the elements appended to it.
+    Collection(ValueType, ElementTypes),
+    /// A value whose full type is known: a UDT, a tuple, or a collection
+    /// created from a data type.
+    Typed(CqlType),
+}
+
+impl Provided {
+    /// Describe a UDT, tuple or collection by its data type, or for a
+    /// collection created without one, by the elements appended to it.
+    pub(crate) fn from_data_type(data_type: &ConstDataType, elements: &ElementTypes) -> Self {
+        let cql_type = CqlType::from_data_type(data_type);
+        match cql_type.value_type {
+            ValueType::LIST | ValueType::SET | ValueType::MAP if cql_type.sub_types.is_empty() => {
+                Provided::Collection(cql_type.value_type, elements.clone())
+            }
+            _ => Provided::Typed(cql_type),
+        }
+    }
+
+    /// Whether this value may be bound where `expected` is expected.
+    pub(crate) fn is_accepted_by(&self, expected: &CqlType) -> bool {
+        match self {
+            Provided::Scalar(name) => scalar_accepts(name, expected.value_type),
+            Provided::Collection(value_type, elements) => {
+                if expected.value_type == ValueType::UNKNOWN {
+                    return true;
+                }
+                if *value_type != expected.value_type {
+                    return false;
+                }
+                elements.slots.iter().enumerate().all(|(slot, types)| {
+                    match expected.sub_types.get(slot) {
+                        Some((_, element_type)) => {
+                            types.iter().all(|t| t.is_accepted_by(element_type))
+                        }
+                        None => true,
+                    }
+                })
+            }
+            Provided::Typed(cql_type) => expected.accepts(cql_type),
+        }
+    }
+}
+
+impl fmt::Display for Provided {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Provided::Scalar(name) => write!(f, "{}", name),
+            Provided::Collection(value_type, elements) => {
+                write!(f, "{}<", cql_name(*value_type))?;
+                let slot_count = if *value_type == ValueType::MAP { 2 } else { 1 };
+                for slot in 0..slot_count {
+                    if slot > 0 {
+                        write!(f, ", ")?;
+                    }
+                    match elements.slots.get(slot) {
+                        Some(types) if !types.is_empty() => {
+                            for (index, t) in types.iter().enumerate() {
+                                if index > 0 {
+                                    write!(f, " | ")?;
+                                }
+                                write!(f, "{}", t)?;
+                            }
+                        }
+                        _ => write!(f, "?")?,
+                    }
+                }
+                write!(f, ">")
+            }
+            Provided::Typed(cql_type) => write!(f, "{}", cql_type),
+        }
+    }
+}
+
+/// The distinct types of the elements appended to a collection created
+/// without a data type, in which the driver does not check them.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct ElementTypes {
+    appended: usize,
+    /// For each sub-type (key and value for a map), the types appended.
+    slots: Vec<Vec<Provided>>,
+}
+
+impl ElementTypes {
+    /// Record an element appended to a collection with `slot_count` sub-types,
+    /// which are filled in turn.
+    pub(crate) fn record(&mut self, slot_count: usize, element: Provided) {
+        let slot = self.appended % slot_count;
+        self.appended += 1;
+        if self.slots.len() <= slot {
+            self.slots.resize(slot + 1, Vec::new());
+        }
+        if !self.slots[slot].contains(&element) {
+            self.slots[slot].push(element);
+        }
+    }
+}
+
+/// Whether a scalar bound as the CQL type `name` may be bound to a parameter
+/// of `value_type`. These are the types the driver accepts for each bind
+/// function.
+pub(crate) fn scalar_accepts(name: &str, value_type: ValueType) -> bool {
+    use ValueType::*;
+    if value_type == UNKNOWN {
+        return true;
+    }
+    match name {
+        "null" => true,
+        "bigint" => matches!(value_type, BIGINT | COUNTER | TIMESTAMP | TIME),
+        "blob" => matches!(value_type, BLOB | VARINT | CUSTOM),
+        "boolean" => value_type == BOOLEAN,
+        "date" => value_type == DATE,
+        "decimal" => value_type == DECIMAL,
+        "double" => value_type == DOUBLE,
+        "float" => value_type == FLOAT,
+        "inet" => value_type == INET,
+        "int" => value_type == INT,
+        "smallint" => value_type == SMALL_INT,
+        "text" => matches!(value_type, TEXT | VARCHAR | ASCII),
+        "tinyint" => value_type == TINY_INT,
+        "uuid" => matches!(value_type, UUID | TIMEUUID),
+        _ => false,
+    }
+}
+
+/// `text` and `varchar` are the same type.
+fn normalize(value_type: ValueType) -> ValueType {
+    match value_type {
+        ValueType::VARCHAR => ValueType::TEXT,
+        other => other,
+    }
+}
+
+fn names_match(name: &Option<String>, other: &Option<String>) -> bool {
+    match (name, other) {
+        (Some(name), Some(other)) => name == other,
+        _ => true,
+    }
+}
+
+/// The CQL name of a value type.
+fn cql_name(value_type: ValueType) -> &'static str {
+    use ValueType::*;
+    match value_type {
+        UNKNOWN => "unknown",
+        CUSTOM => "custom",
+        ASCII => "ascii",
+        BIGINT => "bigint",
+        BLOB => "blob",
+        BOOLEAN => "boolean",
+        COUNTER => "counter",
+        DECIMAL => "decimal",
+        DOUBLE => "double",
+        FLOAT => "float",
+        INT => "int",
+        TEXT => "text",
+        TIMESTAMP => "timestamp",
+        UUID => "uuid",
+        VARCHAR => "varchar",
+        VARINT => "varint",
+        TIMEUUID => "timeuuid",
+        INET => "inet",
+        DATE => "date",
+        TIME => "time",
+        SMALL_INT => "smallint",
+        TINY_INT => "tinyint",
+        DURATION => "duration",
+        LIST => "list",
+        MAP => "map",
+        SET => "set",
+        UDT => "udt",
+        TUPLE => "tuple",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scalar(value_type: ValueType) -> CqlType {
+        CqlType {
+            value_type,
+            name: None,
+            sub_types: vec![],
+        }
+    }
+
+    fn collection(value_type: ValueType, sub_types: Vec<CqlType>) -> CqlType {
+        CqlType {
+            value_type,
+            name: None,
+            sub_types: sub_types.into_iter().map(|t| (None, t)).collect(),
+        }
+    }
+
+    fn udt(name: &str, fields: Vec<(&str, CqlType)>) -> CqlType {
+        CqlType {
+            value_type: ValueType::UDT,
+            name: Some(name.to_string()),
+            sub_types: fields
+                .into_iter()
+                .map(|(n, t)| (Some(n.to_string()), t))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_scalars() {
+        let timestamp = scalar(ValueType::TIMESTAMP);
+        assert!(Provided::Scalar("bigint").is_accepted_by(&timestamp));
+        assert!(Provided::Scalar("null").is_accepted_by(&timestamp));
+        assert!(!Provided::Scalar("int").is_accepted_by(&timestamp));
+        assert!(Provided::Scalar("text").is_accepted_by(&scalar(ValueType::VARCHAR)));
+    }
+
+    #[test]
+    fn test_collection_elements() {
+        let map = collection(
+            ValueType::MAP,
+            vec![scalar(ValueType::TEXT), scalar(ValueType::INT)],
+        );
+        let mut elements = ElementTypes::default();
+        elements.record(2, Provided::Scalar("text"));
+        elements.record(2, Provided::Scalar("int"));
+        let provided = Provided::Collection(ValueType::MAP, elements.clone());
+        assert!(provided.is_accepted_by(&map));
+        assert_eq!(provided.to_string(), "map<text, int>");
+
+        elements.record(2, Provided::Scalar("text"));
+        elements.record(2, Provided::Scalar("bigint"));
+        let provided = Provided::Collection(ValueType::MAP, elements);
+        assert!(!provided.is_accepted_by(&map));
+        assert_eq!(provided.to_string(), "map<text, int | bigint>");
+        assert_eq!(map.to_string(), "map<text, int>");
+
+        let empty = Provided::Collection(ValueType::LIST, ElementTypes::default());
+        assert!(!empty.is_accepted_by(&map));
+        assert!(empty.is_accepted_by(&collection(ValueType::LIST, vec![])));
+    }
+
+    #[test]
+    fn test_nested() {
+        let address = udt(
+            "address",
+            vec![
+                ("street", scalar(ValueType::TEXT)),
+                ("zip", scalar(ValueType::INT)),
+            ],
+        );
+        let addresses = collection(ValueType::LIST, vec![address.clone()]);
+
+        let mut elements = ElementTypes::default();
+        elements.record(1, Provided::Typed(address.clone()));
+        assert!(Provided::Collection(ValueType::LIST, elements).is_accepted_by(&addresses));
+
+        let other = udt(
+            "address",
+            vec![
+                ("street", scalar(ValueType::TEXT)),
+                ("zip", scalar(ValueType::TEXT)),
+            ],
+        );
+        let mut elements = ElementTypes::default();
+        elements.record(1, Provided::Typed(other));
+        assert!(!Provided::Collection(ValueType::LIST, elements).is_accepted_by(&addresses));
+        assert_eq!(addresses.to_string(), "list<address>");
+    }
+}
@@ -0,0 +1,255 @@
+use crate::cassandra::cluster_config::{ClusterConfig, Credentials, LoadBalancing, TlsConfig};
+use crate::cassandra::error::*;
+
+use std::convert::TryFrom;
+use std::time::Duration;
+
+const SCHEME: &str = "cassandra://";
+
+fn invalid(reason: impl std::fmt::Display) -> Error {
+    ErrorKind::InvalidConfig(format!("Invalid URI: {}", reason)).into()
+}
+
+/// Parse a connection URI of the form
+/// `cassandra://[user[:password]@]host[:port][,host[:port]...][/keyspace][?option=value&...]`
+/// into a configuration. See `Cluster::from_uri` for the options.
+pub(crate) fn parse(uri: &str) -> Result<ClusterConfig> {
+    let rest = uri
+        .strip_prefix(SCHEME)
+        .ok_or_else(|| invalid(format!("expected scheme {}", SCHEME)))?;
+    let (rest, query) = match rest.find('?') {
+        Some(index) => (&rest[..index], Some(&rest[index + 1..])),
+        None => (rest, None),
+    };
+    let (authority, keyspace) = match rest.find('/') {
+        Some(index) => (&rest[..index], Some(&rest[index + 1..])),
+        None => (rest, None),
+    };
+    // The user information ends at the last `@`, since hosts cannot contain one.
+    let (user_info, hosts) = match authority.rfind('@') {
+        Some(index) => (Some(&authority[..index]), &authority[index + 1..]),
+        None => (None, authority),
+    };
+
+    let mut config = ClusterConfig::default();
+    if let Some(user_info) = user_info {
+        let (username, password) = match user_info.find(':') {
+            Some(index) => (&user_info[..index], &user_info[index + 1..]),
+            None => (user_info, ""),
+        };
+        config.credentials = Some(Credentials {
+            username: decode(username)?,
+            password: decode(password)?,
+        });
+    }
+    for host in hosts.split(',') {
+        let (host, port) = split_port(host)?;
+        if host.is_empty() {
+            return Err(invalid("empty host"));
+        }
+        config.contact_points.push(host.to_string());
+        match (port, config.port) {
+            (Some(port), Some(previous)) if port != previous => {
+                return Err(invalid("all hosts must use the same port"))
+            }
+            (Some(port), _) => config.port = Some(port),
+            (None, _) => {}
+        }
+    }
+    if let Some(keyspace) = keyspace {
+        let keyspace = decode(keyspace)?;
+        if !keyspace.is_empty() {
+            config.keyspace = Some(keyspace);
+        }
+    }
+
+    let mut tls = None;
+    for pair in query.into_iter().flat_map(|q| q.split('&')) {
+        if pair.is_empty() {
+            continue;
+        }
+        let (key, value) = match pair.find('=') {
+            Some(index) => (&pair[..index], decode(&pair[index + 1..])?),
+            None => (pair, String::new()),
+        };
+        let parse_error = |e: String| invalid(format!("{}: {}", key, e));
+        match key {
+            "consistency" => config.consistency = Some(value.parse().map_err(parse_error)?),
+            "serial_consistency" => {
+                config.serial_consistency = Some(value.parse().map_err(parse_error)?)
+            }
+            "dc" => {
+                config.load_balancing = Some(LoadBalancing::DcAware {
+                    local_dc: value,
+                    used_hosts_per_remote_dc: 0,
+                    allow_remote_dcs_for_local_cl: false,
+                })
+            }
+            "tls" => {
+                let enabled = value
+                    .parse::<bool>()
+                    .map_err(|e| parse_error(e.to_string()))?;
+                if enabled {
+                    tls.get_or_insert_with(TlsConfig::default);
+                } else {
+                    tls = None;
+                }
+            }
+            "tls_ca" => tls
+                .get_or_insert_with(TlsConfig::default)
+                .trusted_certs
+                .push(value.into()),
+            "tls_verify" => {
+                let verify = value
+                    .split(',')
+                    .map(str::parse)
+                    .collect::<::std::result::Result<_, _>>()
+                    .map_err(parse_error)?;
+                tls.get_or_insert_with(TlsConfig::default).verify = Some(verify);
+            }
+            "connect_timeout" => {
+                config.connect_timeout_ms = Some(duration_ms(&value).map_err(parse_error)?)
+            }
+            "request_timeout" => {
+                config.request_timeout_ms = Some(duration_ms(&value).map_err(parse_error)?)
+            }
+            _ => return Err(invalid(format!("unknown option {}", key))),
+        }
+    }
+    config.tls = tls;
+    Ok(config)
+}
+
+/// Split an optional port from a host, which may be a bracketed IPv6 address.
+fn split_port(host: &str) -> Result<(&str, Option<u16>)> {
+    let (host, port) = if let Some(rest) = host.strip_prefix('[') {
+        let end = rest
+            .find(']')
+            .ok_or_else(|| invalid(format!("unterminated IPv6 address {}", host)))?;
+        match &rest[end + 1..] {
+            "" => (&rest[..end], None),
+            port => match port.strip_prefix(':') {
+                Some(port) => (&rest[..end], Some(port)),
+                None => return Err(invalid(format!("bad host {}", host))),
+            },
+        }
+    } else {
+        match host.find(':') {
+            Some(index) => (&host[..index], Some(&host[index + 1..])),
+            None => (host, None),
+        }
+    };
+    let port = match port {
+        Some(port) => Some(
+            port.parse()
+                .map_err(|_| invalid(format!("bad port {}", port)))?,
+        ),
+        None => None,
+    };
+    Ok((host, port))
+}
+
+/// Parse a duration with a unit of `ms`, `s` or `m`, or milliseconds if it has
+/// none, into milliseconds. Fails if that is more than fits in a `u64`.
+fn duration_ms(value: &str) -> ::std::result::Result<u64, String> {
+    let split = value
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(value.len());
+    let (number, unit) = value.split_at(split);
+    let number: u64 = number
+        .parse()
+        .map_err(|_| format!("bad duration {}", value))?;
+    let too_long = || format!("duration {} is too long", value);
+    let duration = match unit {
+        "" | "ms" => Duration::from_millis(number),
+        "s" => Duration::from_secs(number),
+        "m" => Duration::from_secs(number.checked_mul(60).ok_or_else(too_long)?),
+        _ => return Err(format!("bad duration unit {}", unit)),
+    };
+    u64::try_from(duration.as_millis()).map_err(|_| too_long())
+}
+
+/// Percent-decode a component of the URI.
+fn decode(s: &str) -> Result<String> {
+    let bytes = s.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        match bytes[index] {
+            b'%' => {
+                let byte = s
+                    .get(index + 1..index + 3)
+                    .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+                    .ok_or_else(|| invalid(format!("bad percent-encoding in {}", s)))?;
+                decoded.push(byte);
+                index += 3;
+            }
+            byte => {
+                decoded.push(byte);
+                index += 1;
+            }
+        }
+    }
+    String::from_utf8(decoded).map_err(|_| invalid(format!("{} is not valid UTF-8", s)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cassandra::consistency::Consistency;
+
+    #[test]
+    fn test_parse() {
+        let config = parse(
+            "cassandra://user:p%40ss@h1,h2:9042/ks?consistency=LOCAL_QUORUM&dc=eu-west\
+             &tls=true&request_timeout=5s",
+        )
+        .unwrap();
+        assert_eq!(config.contact_points, vec!["h1", "h2"]);
+        assert_eq!(config.port, Some(9042));
+        let credentials = config.credentials.unwrap();
+        assert_eq!(credentials.username, "user");
+        assert_eq!(credentials.password, "p@ss");
+        assert_eq!(config.keyspace.as_deref(), Some("ks"));
+        assert_eq!(config.consistency, Some(Consistency::LOCAL_QUORUM));
+        assert_eq!(
+            config.load_balancing,
+            Some(LoadBalancing::DcAware {
+                local_dc: "eu-west".to_string(),
+                used_hosts_per_remote_dc: 0,
+                allow_remote_dcs_for_local_cl: false,
+            })
+        );
+        assert_eq!(config.tls, Some(TlsConfig::default()));
+        assert_eq!(config.request_timeout_ms, Some(5000));
+
+        let config = parse("cassandra://[::1]:9043").unwrap();
+        assert_eq!(config.contact_points, vec!["::1"]);
+        assert_eq!(config.port, Some(9043));
+        assert_eq!(config.keyspace, None);
+        assert_eq!(config.credentials, None);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse("http://h1").is_err());
+        assert!(parse("cassandra://h1:9042,h2:9043").is_err());
+        assert!(parse("cassandra://h1:port").is_err());
+        assert!(parse("cassandra://h1,,h2").is_err());
+        assert!(parse("cassandra://h1?consistency=SOME").is_err());
+        assert!(parse("cassandra://h1?request_timeout=5h").is_err());
+        assert!(parse("cassandra://h1?unknown=1").is_err());
+        assert!(parse("cassandra://u%4@h1").is_err());
+    }
+
+    #[test]
+    fn test_duration_ms() {
+        assert_eq!(duration_ms("250"), Ok(250));
+        assert_eq!(duration_ms("250ms"), Ok(250));
+        assert_eq!(duration_ms("2m"), Ok(120_000));
+        assert!(duration_ms("s").is_err());
+        assert_eq!(duration_ms("18446744073709551615"), Ok(u64::MAX));
+        assert!(duration_ms("18446744073709551615s").is_err());
+        assert!(duration_ms("307445734561825861m").is_err());
+    }
+}
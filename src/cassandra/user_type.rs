@@ -3,6 +3,7 @@ use crate::cassandra::data_type::ConstDataType;
 use crate::cassandra::error::*;
 use crate::cassandra::inet::Inet;
 use crate::cassandra::tuple::Tuple;
+use crate::cassandra::type_check::{ElementTypes, Provided};
 use crate::cassandra::util::{Protected, ProtectedInner};
 use crate::cassandra::uuid::Uuid;
 
@@ -84,6 +85,12 @@ impl UserType {
         unsafe { ConstDataType::build(cass_user_type_data_type(self.0)) }
     }
 
+    /// What is known about the type of the user defined type, for checking it
+    /// when bound.
+    pub(crate) fn provided(&self) -> Provided {
+        Provided::from_data_type(&self.data_type(), &ElementTypes::default())
+    }
+
     /// Sets a null in a user defined type at the specified index.
     pub fn set_null(&mut self, index: usize) -> Result<&mut Self> {
         unsafe { cass_user_type_set_null(self.0, index).to_result(self) }
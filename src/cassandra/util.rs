@@ -2,6 +2,18 @@
 
 use crate::Session;
 
+use parking_lot::{Condvar, Mutex, MutexGuard};
+
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::hash::Hasher;
+use std::mem;
+use std::pin::Pin;
+use std::sync::OnceLock;
+use std::task::{Context, Poll, Waker};
+use std::thread;
+use std::time::{Duration, Instant};
+
 /// `ProtectedInner` is a trait for types that hold an inner value `T` and
 /// provide a method to access it.
 pub(crate) trait ProtectedInner<T> {
@@ -22,6 +34,162 @@ pub(crate) trait ProtectedWithSession<T>: ProtectedInner<T> {
     fn session(&self) -> &Session;
 }
 
+/// A future which completes once a fixed duration has elapsed.
+///
+/// This crate does not depend on any particular async runtime, so rather than
+/// use a runtime's timer, delays are timed by a single helper thread shared by
+/// the whole process, which sleeps until the earliest pending deadline. A
+/// `Delay` is only registered with it once polled, and is deregistered when
+/// dropped, so a delay which is raced against something else (e.g., a request
+/// deadline) costs nothing once abandoned.
+#[derive(Debug)]
+pub(crate) struct Delay {
+    deadline: Instant,
+    /// The key of this delay in the timer, once registered.
+    key: Option<TimerKey>,
+}
+
+/// A pending deadline, made unique by a sequence number.
+type TimerKey = (Instant, u64);
+
+/// Wait for `duration` without blocking the calling thread.
+pub(crate) fn delay(duration: Duration) -> Delay {
+    let now = Instant::now();
+    Delay {
+        // A duration too long to represent is as good as forever.
+        deadline: now
+            .checked_add(duration)
+            .unwrap_or_else(|| now + Duration::from_secs(u32::MAX.into())),
+        key: None,
+    }
+}
+
+impl Future for Delay {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<()> {
+        if Instant::now() >= self.deadline {
+            self.deregister();
+            return Poll::Ready(());
+        }
+        let timer = Timer::get();
+        let mut state = timer.state.lock();
+        let key = match self.key {
+            Some(key) => key,
+            None => {
+                state.sequence += 1;
+                let key = (self.deadline, state.sequence);
+                self.key = Some(key);
+                key
+            }
+        };
+        state.pending.insert(key, cx.waker().clone());
+        if state.pending.keys().next() == Some(&key) {
+            // The timer thread is sleeping until a later deadline.
+            timer.wake.notify_one();
+        }
+        Poll::Pending
+    }
+}
+
+impl Delay {
+    fn deregister(&mut self) {
+        if let Some(key) = self.key.take() {
+            Timer::get().state.lock().pending.remove(&key);
+        }
+    }
+}
+
+impl Drop for Delay {
+    fn drop(&mut self) {
+        self.deregister();
+    }
+}
+
+/// The timer shared by every `Delay`.
+#[derive(Debug, Default)]
+struct Timer {
+    state: Mutex<TimerState>,
+    /// Notified when a deadline earlier than all others is added.
+    wake: Condvar,
+}
+
+#[derive(Debug, Default)]
+struct TimerState {
+    sequence: u64,
+    /// The waker of each registered delay, earliest first.
+    pending: BTreeMap<TimerKey, Waker>,
+}
+
+impl Timer {
+    /// The timer, starting its thread the first time it is needed.
+    fn get() -> &'static Timer {
+        static TIMER: OnceLock<Timer> = OnceLock::new();
+        TIMER.get_or_init(|| {
+            // The thread waits for initialization to finish when it calls
+            // `get` in turn.
+            thread::Builder::new()
+                .name("cassandra-timer".to_string())
+                .spawn(|| Timer::get().run())
+                .expect("failed to spawn the timer thread");
+            Timer::default()
+        })
+    }
+
+    /// Wake each delay as its deadline passes, forever.
+    fn run(&self) {
+        let mut state = self.state.lock();
+        loop {
+            let now = Instant::now();
+            match state.pending.keys().next() {
+                None => self.wake.wait(&mut state),
+                Some(&(deadline, _)) if deadline > now => {
+                    self.wake.wait_until(&mut state, deadline);
+                }
+                Some(_) => {
+                    let later = state.pending.split_off(&(now, u64::MAX));
+                    let expired = mem::replace(&mut state.pending, later);
+                    MutexGuard::unlocked(&mut state, || {
+                        expired.into_values().for_each(Waker::wake)
+                    });
+                }
+            }
+        }
+    }
+}
+
+/// Quote a CQL identifier, preserving its case.
+pub(crate) fn quote_identifier(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+/// The 64-bit FNV-1a hash.
+///
+/// Unlike `DefaultHasher`, its output is specified and stable across releases
+/// and platforms, so it is suitable for values which are handed out and later
+/// checked, such as paging cursors.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Fnv64(u64);
+
+impl Default for Fnv64 {
+    fn default() -> Self {
+        Fnv64(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Hasher for Fnv64 {
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
 /// Enhance a nullary enum as follows:
 ///
 /// * `Display` / `to_string`
@@ -83,6 +251,23 @@ macro_rules! enhance_nullary_enum {
             }
         }
 
+        /// Serialized as its name, as for `Display`.
+        #[cfg(feature = "serde")]
+        impl ::serde::Serialize for $this_name {
+            fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error> {
+                serializer.collect_str(self)
+            }
+        }
+
+        /// Deserialized from its name, as for `FromStr`.
+        #[cfg(feature = "serde")]
+        impl<'de> ::serde::Deserialize<'de> for $this_name {
+            fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> ::std::result::Result<Self, D::Error> {
+                let name = <::std::borrow::Cow<'de, str>>::deserialize(deserializer)?;
+                name.parse().map_err(::serde::de::Error::custom)
+            }
+        }
+
         impl $crate::cassandra::util::ProtectedInner<$that_name> for $this_name {
             fn inner(&self) -> $that_name {
                 match *self {
@@ -112,3 +297,28 @@ macro_rules! enhance_nullary_enum {
         }
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::future::{self, Either};
+
+    #[test]
+    fn test_delay() {
+        let started = Instant::now();
+        futures::executor::block_on(future::join_all(
+            [30, 10, 20].map(|ms| delay(Duration::from_millis(ms))),
+        ));
+        assert!(started.elapsed() >= Duration::from_millis(30));
+
+        // A delay which loses a race is deregistered when dropped.
+        let race = future::select(delay(Duration::from_secs(3600)), delay(Duration::ZERO));
+        let long = match futures::executor::block_on(race) {
+            Either::Right(((), long)) => long,
+            Either::Left(_) => panic!("the long delay finished first"),
+        };
+        let key = long.key.expect("registered when polled");
+        drop(long);
+        assert!(!Timer::get().state.lock().pending.contains_key(&key));
+    }
+}
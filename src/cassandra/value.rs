@@ -116,6 +116,52 @@ enhance_nullary_enum!(ValueType, CassValueType_, {
     (TUPLE, CASS_VALUE_TYPE_TUPLE, "TUPLE"),
 }, omit { CASS_VALUE_TYPE_LAST_ENTRY });
 
+/// An owned copy of a value, which can outlive the result it was read from.
+/// Obtained with `Value::get_owned`, or for a whole row with `FromRow` as a
+/// `Vec<OwnedValue>`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum OwnedValue {
+    /// A null value.
+    Null,
+    /// A `boolean`.
+    Boolean(bool),
+    /// A `tinyint`.
+    TinyInt(i8),
+    /// A `smallint`.
+    SmallInt(i16),
+    /// An `int`.
+    Int(i32),
+    /// A `bigint` or `counter`.
+    BigInt(i64),
+    /// A `timestamp`, in milliseconds since the Unix epoch.
+    Timestamp(i64),
+    /// A `date`, in days since the Unix epoch offset by 2^31.
+    Date(u32),
+    /// A `time`, in nanoseconds since midnight.
+    Time(i64),
+    /// A `float`.
+    Float(f32),
+    /// A `double`.
+    Double(f64),
+    /// A `decimal`.
+    Decimal(BigDecimal),
+    /// An `ascii`, `text` or `varchar`.
+    Text(String),
+    /// A `uuid` or `timeuuid`.
+    Uuid(Uuid),
+    /// An `inet`.
+    Inet(Inet),
+    /// A `blob`, or the serialized form of a `varint`, `duration` or custom
+    /// value.
+    Bytes(Vec<u8>),
+    /// The elements of a `list`, `set` or `tuple`.
+    List(Vec<OwnedValue>),
+    /// The entries of a `map`.
+    Map(Vec<(OwnedValue, OwnedValue)>),
+    /// The fields of a user-defined type, by name.
+    UserType(Vec<(String, OwnedValue)>),
+}
+
 /// A single primitive value or a collection of values.
 //
 // Borrowed immutably.
@@ -471,6 +517,61 @@ impl<'a> Value<'a> {
         unsafe { cass_value_get_uuid(self.0, &mut output).to_result(Uuid::build(output)) }
     }
 
+    /// Copies this value, whatever its type, so that it can outlive the
+    /// result it belongs to.
+    pub fn get_owned(&self) -> Result<OwnedValue> {
+        if self.is_null() {
+            return Ok(OwnedValue::Null);
+        }
+        Ok(match self.get_type() {
+            ValueType::BOOLEAN => OwnedValue::Boolean(self.get_bool()?),
+            ValueType::TINY_INT => OwnedValue::TinyInt(self.get_i8()?),
+            ValueType::SMALL_INT => OwnedValue::SmallInt(self.get_i16()?),
+            ValueType::INT => OwnedValue::Int(self.get_i32()?),
+            ValueType::BIGINT | ValueType::COUNTER => OwnedValue::BigInt(self.get_i64()?),
+            ValueType::TIMESTAMP => OwnedValue::Timestamp(self.get_i64()?),
+            ValueType::DATE => OwnedValue::Date(self.get_u32()?),
+            ValueType::TIME => OwnedValue::Time(self.get_i64()?),
+            ValueType::FLOAT => OwnedValue::Float(self.get_f32()?),
+            ValueType::DOUBLE => OwnedValue::Double(self.get_f64()?),
+            ValueType::DECIMAL => OwnedValue::Decimal(self.get_decimal()?),
+            ValueType::ASCII | ValueType::TEXT | ValueType::VARCHAR => {
+                OwnedValue::Text(self.get_string()?)
+            }
+            ValueType::UUID | ValueType::TIMEUUID => OwnedValue::Uuid(self.get_uuid()?),
+            ValueType::INET => OwnedValue::Inet(self.get_inet()?),
+            ValueType::BLOB
+            | ValueType::VARINT
+            | ValueType::DURATION
+            | ValueType::CUSTOM
+            | ValueType::UNKNOWN => OwnedValue::Bytes(self.get_bytes()?.to_vec()),
+            ValueType::SET | ValueType::LIST | ValueType::TUPLE => {
+                let mut items = Vec::new();
+                let mut iter = self.get_set()?;
+                while let Some(item) = iter.next() {
+                    items.push(item.get_owned()?);
+                }
+                OwnedValue::List(items)
+            }
+            ValueType::MAP => {
+                let mut entries = Vec::new();
+                let mut iter = self.get_map()?;
+                while let Some((key, value)) = iter.next() {
+                    entries.push((key.get_owned()?, value.get_owned()?));
+                }
+                OwnedValue::Map(entries)
+            }
+            ValueType::UDT => {
+                let mut fields = Vec::new();
+                let mut iter = self.get_user_type()?;
+                while let Some((name, value)) = iter.next() {
+                    fields.push((name, value.get_owned()?));
+                }
+                OwnedValue::UserType(fields)
+            }
+        })
+    }
+
     /// Get this value as a BigDecimal
     pub fn get_decimal(&self) -> Result<BigDecimal> {
         let mut varint = std::ptr::null();
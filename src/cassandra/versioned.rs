@@ -0,0 +1,223 @@
+use crate::cassandra::consistency::Consistency;
+use crate::cassandra::error::*;
+use crate::cassandra::lwt::LwtOutcome;
+use crate::cassandra::prepared::PreparedStatement;
+use crate::cassandra::row::Row;
+use crate::cassandra::statement::{BindRustType, Statement};
+use crate::cassandra::util;
+use crate::Session;
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// The prepared select and update of a `VersionedTable`.
+#[derive(Debug)]
+struct Prepared {
+    select: PreparedStatement,
+    update: PreparedStatement,
+}
+
+/// Read-modify-write of rows guarded by a version column (optimistic
+/// concurrency control).
+///
+/// Each update reads the row's columns and version at `SERIAL` consistency,
+/// lets a closure compute the new values, and writes them with
+///
+/// ```text
+/// UPDATE <table> SET <col> = :<col>, ..., version = :new_version
+/// WHERE <key> = :<key> AND ... IF version = :expected_version
+/// ```
+///
+/// If another writer changed the row in between, the update is not applied,
+/// and the whole read-modify-write is retried after a backoff, up to a maximum
+/// number of attempts. A row which does not exist, or has a null version, is
+/// treated as having a null version; its first update creates it with version
+/// 1.
+///
+/// Key and value columns are bound by name, using the column names as given.
+///
+/// ```no_run
+/// # use cassandra_cpp::*;
+/// # async fn test(session: &Session) -> Result<()> {
+/// let accounts = VersionedTable::new(session, "ks.accounts", &["id"], &["balance"]);
+/// let version = accounts
+///     .update(
+///         |key| {
+///             key.bind_by_name("id", 7)?;
+///             Ok(())
+///         },
+///         |current, update| {
+///             let balance = match current {
+///                 Some(row) => row.get_column_by_name("balance")?.get_i64()?,
+///                 None => 0,
+///             };
+///             update.bind_by_name("balance", balance + 10)?;
+///             Ok(())
+///         },
+///     )
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct VersionedTable {
+    session: Session,
+    table: String,
+    key_columns: Vec<String>,
+    columns: Vec<String>,
+    version_column: String,
+    max_attempts: u32,
+    backoff: Duration,
+    prepared: Mutex<Option<Arc<Prepared>>>,
+}
+
+impl VersionedTable {
+    /// Create a helper updating `columns` of the rows of `table` with the given
+    /// primary key columns. The table name may be qualified with a keyspace.
+    ///
+    /// By default the version column is `version`, and an update is attempted
+    /// at most 5 times, with a backoff starting at 10ms and doubling each time.
+    pub fn new(session: &Session, table: &str, key_columns: &[&str], columns: &[&str]) -> Self {
+        VersionedTable {
+            session: session.clone(),
+            table: table.to_string(),
+            key_columns: key_columns.iter().map(|c| c.to_string()).collect(),
+            columns: columns.iter().map(|c| c.to_string()).collect(),
+            version_column: "version".to_string(),
+            max_attempts: 5,
+            backoff: Duration::from_millis(10),
+            prepared: Mutex::new(None),
+        }
+    }
+
+    /// Sets the name of the version column, which must be a `bigint`.
+    pub fn version_column(mut self, version_column: &str) -> Self {
+        self.version_column = version_column.to_string();
+        self
+    }
+
+    /// Sets the most attempts made at an update before failing with
+    /// `VersionConflict`.
+    pub fn max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// Sets the delay before the first retry after a conflict. The delay
+    /// doubles on each subsequent retry.
+    pub fn backoff(mut self, backoff: Duration) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
+    /// The query reading a row.
+    pub fn select_query(&self) -> String {
+        format!(
+            "SELECT {}, {} FROM {} WHERE {}",
+            self.columns.join(", "),
+            self.version_column,
+            self.table,
+            self.key_condition()
+        )
+    }
+
+    /// The conditional query writing a row.
+    pub fn update_query(&self) -> String {
+        let assignments = self
+            .columns
+            .iter()
+            .map(|column| format!("{} = :{}", column, column))
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!(
+            "UPDATE {} SET {}, {} = :new_version WHERE {} IF {} = :expected_version",
+            self.table,
+            assignments,
+            self.version_column,
+            self.key_condition(),
+            self.version_column
+        )
+    }
+
+    fn key_condition(&self) -> String {
+        self.key_columns
+            .iter()
+            .map(|column| format!("{} = :{}", column, column))
+            .collect::<Vec<_>>()
+            .join(" AND ")
+    }
+
+    /// Prepare the queries, or get them if already prepared.
+    async fn prepared(&self) -> Result<Arc<Prepared>> {
+        if let Some(prepared) = &*self.prepared.lock().unwrap() {
+            return Ok(prepared.clone());
+        }
+        let prepared = Arc::new(Prepared {
+            select: self.session.prepare(self.select_query()).await?,
+            update: self.session.prepare(self.update_query()).await?,
+        });
+        *self.prepared.lock().unwrap() = Some(prepared.clone());
+        Ok(prepared)
+    }
+
+    /// Read, modify and conditionally write a row, retrying on conflict.
+    ///
+    /// `bind_key` binds the primary key columns by name; it is called for both
+    /// the select and the update. `modify` is given the current row, if it
+    /// exists, and binds the new value of each column by name. It may be called
+    /// several times if there are conflicts.
+    ///
+    /// Returns the row's new version. Fails with `VersionConflict` if every
+    /// attempt conflicted. If the outcome of a write is unknown because it timed
+    /// out, the error is returned, and the row may or may not have been updated.
+    pub async fn update<K, F>(&self, bind_key: K, mut modify: F) -> Result<i64>
+    where
+        K: Fn(&mut Statement) -> Result<()>,
+        F: FnMut(Option<&Row>, &mut Statement) -> Result<()>,
+    {
+        let prepared = self.prepared().await?;
+        let mut backoff = self.backoff;
+        for attempt in 1..=self.max_attempts {
+            let mut select = prepared.select.bind();
+            bind_key(&mut select)?;
+            select.set_consistency(Consistency::SERIAL)?;
+            let current = select.execute().await?;
+            // Rows borrow the result and cannot be held across the write.
+            let (update, new_version) = {
+                let row = current.first_row();
+                let expected = match &row {
+                    Some(row) => {
+                        let version = row.get_column_by_name(&self.version_column)?;
+                        if version.is_null() {
+                            None
+                        } else {
+                            Some(version.get_i64()?)
+                        }
+                    }
+                    None => None,
+                };
+                let mut update = prepared.update.bind();
+                bind_key(&mut update)?;
+                modify(row.as_ref(), &mut update)?;
+                let new_version = expected.unwrap_or(0) + 1;
+                update.bind_by_name("new_version", new_version)?;
+                match expected {
+                    Some(expected) => update.bind_by_name("expected_version", expected)?,
+                    None => update.bind_null_by_name("expected_version")?,
+                };
+                (update, new_version)
+            };
+
+            match update.execute_lwt().await? {
+                LwtOutcome::Applied => return Ok(new_version),
+                LwtOutcome::Unknown(err) => return Err(err),
+                LwtOutcome::NotApplied(_) if attempt < self.max_attempts => {
+                    util::delay(backoff).await;
+                    backoff *= 2;
+                }
+                LwtOutcome::NotApplied(_) => {}
+            }
+        }
+        Err(ErrorKind::VersionConflict(self.max_attempts).into())
+    }
+}
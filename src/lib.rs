@@ -14,14 +14,31 @@ extern crate error_chain;
 
 use cassandra_cpp_sys as cassandra_sys;
 
+pub use crate::cassandra::authenticator::{AuthExchange, Authenticator};
 pub use crate::cassandra::batch::{Batch, BatchType};
+pub use crate::cassandra::batch_builder::BatchBuilder;
+pub use crate::cassandra::blocking::{BlockingSession, BlockingStatement};
+pub use crate::cassandra::bulk::{
+    BindRecord, BulkLoadReport, BulkLoader, BulkRetryPolicy, DeadLetterSink,
+};
 pub use crate::cassandra::cluster::{Cluster, CqlProtocol};
+pub use crate::cassandra::cluster_config::{
+    ClusterConfig, Credentials, HostFilter, LatencyAwareRouting, LoadBalancing, ReconnectPolicy,
+    RetryPolicyKind, TimestampGenKind, TlsConfig,
+};
 pub use crate::cassandra::collection::{CassCollection, List, Map, Set};
+pub use crate::cassandra::concurrent::{
+    ConcurrentExecution, ConcurrentStream, ErrorMode, Progress,
+};
 pub use crate::cassandra::consistency::Consistency;
+pub use crate::cassandra::cql_value::ToCqlValue;
+pub use crate::cassandra::credentials::{CredentialProvider, EnvCredentials, FileCredentials};
 pub use crate::cassandra::custom_payload::CustomPayload;
 pub use crate::cassandra::data_type::DataType;
 // pub use cassandra::write_type::*;
 pub use crate::cassandra::field::Field;
+pub use crate::cassandra::future::{CassFuture, Deadline};
+pub use crate::cassandra::host_events::{HostEvent, HostEventKind, HostEvents};
 pub use crate::cassandra::inet::Inet;
 pub use crate::cassandra::iterator::LendingIterator;
 // pub use cassandra::util::*;
@@ -30,6 +47,8 @@ pub use crate::cassandra::iterator::{
     AggregateIterator, ColumnIterator, FieldIterator, FunctionIterator, KeyspaceIterator,
     MapIterator, SetIterator, TableIterator, UserTypeIterator,
 };
+pub use crate::cassandra::keyset::{ClusteringOrder, KeysetCursor, KeysetPager};
+pub use crate::cassandra::lease::{Lease, LeaseGuard, LeaseLost};
 #[cfg(feature = "log")]
 pub use crate::cassandra::log::set_log_logger;
 #[cfg(feature = "slog")]
@@ -38,11 +57,17 @@ pub use crate::cassandra::log::set_logger;
 #[cfg(feature = "slog")]
 pub use crate::cassandra::log::set_slog_logger;
 pub use crate::cassandra::log::{set_level, LogLevel};
+pub use crate::cassandra::lwt::{ExistingRow, LwtOutcome};
+pub use crate::cassandra::paging::{PagingCursor, RowStream};
 pub use crate::cassandra::policy::retry::RetryPolicy;
 pub use crate::cassandra::prepared::PreparedStatement;
 pub use crate::cassandra::result::CassResult;
 pub use crate::cassandra::row::AsRustType;
+pub use crate::cassandra::row::FromRow;
 pub use crate::cassandra::row::Row;
+pub use crate::cassandra::scan::{
+    RangeProgress, ScanCheckpoint, ScanRange, ScanStream, TableScanner,
+};
 pub use crate::cassandra::schema::aggregate_meta::AggregateMeta;
 pub use crate::cassandra::schema::column_meta::ColumnMeta;
 pub use crate::cassandra::schema::function_meta::FunctionMeta;
@@ -57,10 +82,16 @@ pub use crate::cassandra::statement::BindRustType;
 pub use crate::cassandra::statement::Statement;
 // pub use cassandra::custom_payload::CustomPayload;
 pub use crate::cassandra::time::TimestampGen;
+pub use crate::cassandra::token::{
+    murmur3_token, partition_key_bytes, partition_token, KeyComponent, Replication, RingHost,
+    TokenRing,
+};
+pub use crate::cassandra::trace::{QueryTrace, TraceEvent};
 pub use crate::cassandra::tuple::Tuple;
 pub use crate::cassandra::user_type::UserType;
 pub use crate::cassandra::uuid::{Uuid, UuidGen};
-pub use crate::cassandra::value::{Value, ValueType};
+pub use crate::cassandra::value::{OwnedValue, Value, ValueType};
+pub use crate::cassandra::versioned::VersionedTable;
 
 pub use crate::cassandra::error::*;
 
@@ -68,32 +99,52 @@ pub use crate::cassandra::error::*;
 mod cassandra {
     #[macro_use]
     pub mod util;
+    pub mod authenticator;
     pub mod batch;
+    pub mod batch_builder;
+    pub mod blocking;
+    pub mod bulk;
     pub mod cluster;
+    pub mod cluster_config;
     pub mod collection;
+    pub mod concurrent;
     pub mod consistency;
+    pub mod cql_value;
+    pub mod credentials;
     pub mod custom_payload;
     pub mod data_type;
     pub mod error;
     pub mod field;
     pub mod future;
+    pub mod host_events;
     pub mod inet;
     pub mod iterator;
+    pub mod keyset;
+    pub mod lease;
     pub mod log;
+    pub mod lwt;
     pub mod metrics;
+    pub mod paging;
     pub mod policy;
     pub mod prepared;
+    pub mod query_meta;
     pub mod result;
     pub mod row;
+    pub mod scan;
     pub mod schema;
     pub mod session;
     pub mod ssl;
     pub mod statement;
     pub mod time;
+    pub mod token;
+    pub mod trace;
     pub mod tuple;
+    pub mod type_check;
+    pub mod uri;
     pub mod user_type;
     pub mod uuid;
     pub mod value;
+    pub mod versioned;
     pub mod write_type;
 }
 
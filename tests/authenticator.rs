@@ -0,0 +1,77 @@
+//! Test custom SASL authentication.
+
+mod help;
+
+use cassandra_cpp::*;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+/// The `PLAIN` mechanism, counting the exchanges started.
+struct Plain {
+    exchanges: Arc<AtomicUsize>,
+}
+
+impl Authenticator for Plain {
+    fn initial_response(&self, exchange: &mut AuthExchange) -> Result<Vec<u8>> {
+        self.exchanges.fetch_add(1, Ordering::SeqCst);
+        exchange.set_state(exchange.class_name().to_string());
+        Ok(b"\0cassandra\0cassandra".to_vec())
+    }
+
+    fn on_success(&self, exchange: &mut AuthExchange, _token: &[u8]) -> Result<()> {
+        assert!(exchange.state::<String>().is_some());
+        Ok(())
+    }
+}
+
+/// An authenticator which always fails.
+struct Refuse;
+
+impl Authenticator for Refuse {
+    fn initial_response(&self, _exchange: &mut AuthExchange) -> Result<Vec<u8>> {
+        Err(ErrorKind::AuthenticationFailed("refused".to_string()).into())
+    }
+}
+
+fn test_cluster(authenticator: impl Authenticator + 'static) -> Result<Cluster> {
+    let mut cluster = Cluster::default();
+    cluster.set_contact_points("127.0.0.1")?;
+    cluster.set_load_balance_round_robin();
+    cluster.set_authenticator(authenticator)?;
+    Ok(cluster)
+}
+
+#[tokio::test]
+async fn test_authenticator() -> Result<()> {
+    // The authenticator is only used if the test cluster requires
+    // authentication; either way, installing it must not stop us connecting.
+    let mut cluster = test_cluster(Plain {
+        exchanges: Arc::new(AtomicUsize::new(0)),
+    })?;
+    let session = cluster.connect().await?;
+    let result = session
+        .execute("SELECT release_version FROM system.local")
+        .await?;
+    assert_eq!(result.row_count(), 1);
+    Ok(())
+}
+
+/// Run with `cargo test -- --ignored` against a cluster using
+/// `PasswordAuthenticator`, as described in the README.
+#[tokio::test]
+#[ignore = "needs a cluster using PasswordAuthenticator"]
+async fn test_authenticator_exchanges() -> Result<()> {
+    let exchanges = Arc::new(AtomicUsize::new(0));
+    let mut cluster = test_cluster(Plain {
+        exchanges: exchanges.clone(),
+    })?;
+    let session = cluster.connect().await?;
+    session
+        .execute("SELECT release_version FROM system.local")
+        .await?;
+    assert!(exchanges.load(Ordering::SeqCst) > 0);
+
+    let mut cluster = test_cluster(Refuse)?;
+    assert!(cluster.connect().await.is_err());
+    Ok(())
+}
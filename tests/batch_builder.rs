@@ -0,0 +1,73 @@
+mod help;
+
+use cassandra_cpp::*;
+
+static CREATE_TABLE: &str = "CREATE TABLE IF NOT EXISTS examples.batch_builder \
+     (sensor text, at bigint, value text, PRIMARY KEY (sensor, at));";
+static INSERT_QUERY: &str =
+    "INSERT INTO examples.batch_builder (sensor, at, value) VALUES (?, ?, ?);";
+
+#[tokio::test]
+async fn test_batch_builder() -> Result<()> {
+    let session = help::create_test_session().await;
+    help::create_example_keyspace(&session).await;
+    session.execute(CREATE_TABLE).await?;
+    session.execute("TRUNCATE examples.batch_builder").await?;
+
+    let insert = session.prepare(INSERT_QUERY).await?;
+    let mut builder = BatchBuilder::new(&session);
+    builder.max_statements(10).concurrency(4);
+    for at in 0..100i64 {
+        let sensor = format!("sensor-{}", at % 4);
+        let mut statement = insert.bind();
+        statement.bind(0, sensor.as_str())?;
+        statement.bind(1, at)?;
+        statement.bind(2, "x")?;
+        builder.add(statement, &[&sensor])?;
+    }
+    assert_eq!(builder.len(), 100);
+    // 25 statements per partition, in batches of at most 10.
+    assert_eq!(builder.batch_count(), 12);
+    assert_eq!(builder.execute().await?, 12);
+
+    let result = session
+        .execute("SELECT COUNT(*) FROM examples.batch_builder")
+        .await?;
+    let count = result.first_row().unwrap().get_column(0)?.get_i64()?;
+    assert_eq!(count, 100);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_batch_builder_size_limit() -> Result<()> {
+    let session = help::create_test_session().await;
+    help::create_example_keyspace(&session).await;
+    session.execute(CREATE_TABLE).await?;
+
+    let insert = session.prepare(INSERT_QUERY).await?;
+    let value = "x".repeat(1000);
+    let mut builder = BatchBuilder::new(&session);
+    builder.max_size(5 * 1024);
+    for at in 0..20i64 {
+        let mut statement = insert.bind();
+        statement.bind(0, "large")?;
+        statement.bind(1, at)?;
+        statement.bind(2, value.as_str())?;
+        builder.add(statement, &[&"large"])?;
+    }
+    // Each statement is over 1000 bytes, so at most 4 fit in a batch.
+    assert_eq!(builder.batch_count(), 5);
+    assert_eq!(builder.build()?.len(), 5);
+
+    // Statements must belong to the builder's session.
+    let other = help::create_test_session().await;
+    let mut builder = BatchBuilder::new(&other);
+    let statement = insert.bind();
+    match builder.add(statement, &[&"large"]) {
+        Err(Error(ErrorKind::BatchSessionMismatch(..), _)) => {}
+        other => panic!("Unexpected result {:?}", other.map(|_| ())),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,69 @@
+mod help;
+
+use cassandra_cpp::*;
+
+static CREATE_TABLE: &str = "CREATE TABLE IF NOT EXISTS examples.bind_all \
+     (id bigint PRIMARY KEY, name text, age int);";
+
+#[tokio::test]
+async fn test_bind_all() -> Result<()> {
+    let session = help::create_test_session().await;
+    help::create_example_keyspace(&session).await;
+    session.execute(CREATE_TABLE).await?;
+    session.execute("TRUNCATE examples.bind_all").await?;
+
+    let mut insert =
+        session.statement("INSERT INTO examples.bind_all (id, name, age) VALUES (?, ?, ?)");
+    insert.bind_all(&[&1i64, &"alice", &Some(42i32)])?;
+    insert.execute().await?;
+
+    let prepared = session
+        .prepare("INSERT INTO examples.bind_all (id, name, age) VALUES (?, ?, ?)")
+        .await?;
+    let name = "bob".to_string();
+    prepared
+        .bind_with(&[&2i64, &name, &None::<i32>])?
+        .execute()
+        .await?;
+
+    let mut select = session.statement("SELECT name, age FROM examples.bind_all WHERE id = ?");
+    select.bind_all(&[&2i64])?;
+    let result = select.execute().await?;
+    let row = result.first_row().unwrap();
+    assert_eq!(row.get_column(0)?.get_str()?, "bob");
+    assert!(row.get_column(1)?.is_null());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_bind_all_errors() -> Result<()> {
+    let session = help::create_test_session().await;
+    help::create_example_keyspace(&session).await;
+    session.execute(CREATE_TABLE).await?;
+
+    let mut statement =
+        session.statement("INSERT INTO examples.bind_all (id, name, age) VALUES (?, ?, ?)");
+    match statement.bind_all(&[&1i64, &"alice"]) {
+        Err(Error(ErrorKind::BindArityMismatch(3, 2), _)) => {}
+        other => panic!("expected arity mismatch, got {:?}", other.map(|_| ())),
+    }
+
+    let prepared = session
+        .prepare("INSERT INTO examples.bind_all (id, name, age) VALUES (?, ?, ?)")
+        .await?;
+    match prepared.bind_with(&[&1i64, &"alice", &"42"]) {
+        Err(Error(ErrorKind::BindTypeMismatch(2, name, expected, provided), _)) => {
+            assert_eq!(name, "age");
+            assert_eq!(expected, "int");
+            assert_eq!(provided, "text");
+        }
+        other => panic!("expected type mismatch, got {:?}", other.map(|_| ())),
+    }
+
+    // A failed bind_all leaves nothing bound, so the statement can be reused.
+    let mut statement = prepared.bind();
+    assert!(statement.bind_all(&[&3i64, &"carol", &"42"]).is_err());
+    statement.bind_all(&[&3i64, &"carol", &42i32])?;
+    statement.execute().await?;
+    Ok(())
+}
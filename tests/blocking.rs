@@ -0,0 +1,54 @@
+//! Test the blocking API, without an async runtime.
+
+use cassandra_cpp::*;
+use std::time::Duration;
+
+fn connect() -> BlockingSession {
+    let mut cluster = Cluster::default();
+    cluster.set_contact_points("127.0.0.1").unwrap();
+    cluster.set_load_balance_round_robin();
+    BlockingSession::connect(&mut cluster).expect("Failed to connect to Cassandra")
+}
+
+#[test]
+fn test_blocking_round_trip() -> Result<()> {
+    let session = connect().with_timeout(Duration::from_secs(10));
+    session.execute(
+        "CREATE KEYSPACE IF NOT EXISTS examples WITH replication = { 'class': \
+         'SimpleStrategy', 'replication_factor': '1' };",
+    )?;
+    session.execute(
+        "CREATE TABLE IF NOT EXISTS examples.blocking (key text PRIMARY KEY, value int);",
+    )?;
+
+    let mut statement =
+        session.statement("INSERT INTO examples.blocking (key, value) VALUES (?, ?);");
+    statement.bind(0, "one")?;
+    statement.bind(1, 1i32)?;
+    statement.execute()?;
+
+    let prepared = session.prepare("SELECT value FROM examples.blocking WHERE key = ?;")?;
+    let mut statement = session.bind(&prepared);
+    statement.bind(0, "one")?;
+    let result = statement.execute()?;
+    let row = result.first_row().expect("Missing row");
+    let value: i32 = row.get(0)?;
+    assert_eq!(value, 1);
+    Ok(())
+}
+
+#[test]
+fn test_blocking_timeout() -> Result<()> {
+    let session = connect();
+    let statement = session
+        .statement("SELECT * FROM system.local;")
+        .with_timeout(Duration::from_micros(1));
+    // The request may complete before the timeout is checked.
+    if let Err(e) = statement.execute() {
+        match e.kind() {
+            ErrorKind::Timeout(_) => (),
+            _ => panic!("Unexpected error {}", e),
+        }
+    }
+    Ok(())
+}
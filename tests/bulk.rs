@@ -0,0 +1,118 @@
+mod help;
+
+use cassandra_cpp::*;
+use futures::stream;
+use std::time::Duration;
+
+const NUM_RECORDS: i32 = 500;
+
+static CREATE_TABLE: &str =
+    "CREATE TABLE IF NOT EXISTS examples.bulk (id int PRIMARY KEY, value text);";
+
+#[derive(Debug)]
+struct Record {
+    id: i32,
+    value: Option<String>,
+}
+
+/// A record which may bind its key with the wrong type.
+#[derive(Debug)]
+enum MaybeBad {
+    Good(Record),
+    Bad(Record),
+}
+
+impl BindRecord for Record {
+    fn bind_to(&self, statement: &mut Statement) -> Result<()> {
+        statement.bind(0, self.id)?;
+        match &self.value {
+            Some(value) => statement.bind(1, value.as_str())?,
+            None => statement.bind_null(1)?,
+        };
+        Ok(())
+    }
+}
+
+impl BindRecord for MaybeBad {
+    fn bind_to(&self, statement: &mut Statement) -> Result<()> {
+        match self {
+            MaybeBad::Good(record) => record.bind_to(statement),
+            MaybeBad::Bad(record) => {
+                statement.bind(0, record.id.to_string().as_str())?;
+                Ok(())
+            }
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_bulk_load() -> Result<()> {
+    let session = help::create_test_session().await;
+    help::create_example_keyspace(&session).await;
+    session.execute(CREATE_TABLE).await?;
+    session.execute("TRUNCATE examples.bulk").await?;
+
+    let insert = session
+        .prepare("INSERT INTO examples.bulk (id, value) VALUES (?, ?)")
+        .await?;
+    let records = (0..NUM_RECORDS).map(|id| Record {
+        id,
+        value: Some(id.to_string()),
+    });
+    let mut dead_letters = vec![];
+    let report = BulkLoader::new(insert)
+        .initial_concurrency(4)
+        .max_concurrency(64)
+        .dead_letter(&mut dead_letters)
+        .load(stream::iter(records))
+        .await;
+    assert_eq!(report.written, NUM_RECORDS as u64);
+    assert_eq!(report.failed, 0);
+    assert!(dead_letters.is_empty());
+    assert!(report.peak_concurrency > 4);
+    assert!(report.throughput() > 0.0);
+
+    let result = session
+        .execute("SELECT COUNT(*) FROM examples.bulk")
+        .await?;
+    let count = result.first_row().unwrap().get_column(0)?.get_i64()?;
+    assert_eq!(count, NUM_RECORDS as i64);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_bulk_load_dead_letters() -> Result<()> {
+    let session = help::create_test_session().await;
+    help::create_example_keyspace(&session).await;
+    session.execute(CREATE_TABLE).await?;
+
+    let insert = session
+        .prepare("INSERT INTO examples.bulk (id, value) VALUES (?, ?)")
+        .await?;
+    let records = (0..10).map(|id| {
+        let record = Record { id, value: None };
+        if id == 3 {
+            MaybeBad::Bad(record)
+        } else {
+            MaybeBad::Good(record)
+        }
+    });
+    let mut dead_letters = vec![];
+    let report = BulkLoader::new(insert)
+        .retry_policy(BulkRetryPolicy::new(2, Duration::from_millis(10)))
+        .dead_letter(&mut dead_letters)
+        .load(stream::iter(records))
+        .await;
+    assert_eq!(report.written, 9);
+    assert_eq!(report.failed, 1);
+    // Binding errors are not retried.
+    assert_eq!(report.retries, 0);
+    assert_eq!(dead_letters.len(), 1);
+    match &dead_letters[0] {
+        (MaybeBad::Bad(record), _) => assert_eq!(record.id, 3),
+        other => panic!("Unexpected dead letter {:?}", other),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,112 @@
+//! Execute requests with completion callbacks, as a C++ event loop might,
+//! without any async runtime. Each callback starts the next request.
+
+use cassandra_cpp::*;
+use std::sync::mpsc::{self, Sender};
+use std::time::Duration;
+
+type Done = Sender<Result<Vec<(Uuid, i64)>>>;
+
+fn connect() -> Session {
+    let mut cluster = Cluster::default();
+    cluster.set_contact_points("127.0.0.1").unwrap();
+    cluster.set_load_balance_round_robin();
+    BlockingSession::connect(&mut cluster)
+        .expect("Failed to connect to Cassandra")
+        .into_session()
+}
+
+fn execute_query<F>(session: &Session, query: &str, callback: F)
+where
+    F: FnOnce(Result<CassResult>) + Send + 'static,
+{
+    session
+        .statement(query)
+        .execute_future()
+        .on_complete(callback);
+}
+
+fn on_create_keyspace(session: Session, done: Done) {
+    execute_query(
+        &session.clone(),
+        "CREATE TABLE IF NOT EXISTS examples.callbacks (key timeuuid PRIMARY KEY, value bigint);",
+        move |result| match result {
+            Ok(_) => on_create_table(session, done),
+            Err(e) => done.send(Err(e)).unwrap(),
+        },
+    );
+}
+
+fn on_create_table(session: Session, done: Done) {
+    let key = UuidGen::default().gen_time();
+    let mut statement =
+        session.statement("INSERT INTO examples.callbacks (key, value) VALUES (?, ?);");
+    if let Err(e) = statement
+        .bind_uuid(0, key)
+        .and_then(|s| s.bind_int64(1, key.timestamp() as i64))
+    {
+        return done.send(Err(e)).unwrap();
+    }
+    statement
+        .execute_future()
+        .on_complete(move |result| match result {
+            Ok(_) => on_insert(session, done),
+            Err(e) => done.send(Err(e)).unwrap(),
+        });
+}
+
+fn on_insert(session: Session, done: Done) {
+    execute_query(
+        &session,
+        "SELECT key, value FROM examples.callbacks;",
+        move |result| {
+            done.send(result.and_then(|result| {
+                let mut rows = vec![];
+                let mut iter = result.iter();
+                while let Some(row) = iter.next() {
+                    rows.push((row.get(0)?, row.get(1)?));
+                }
+                Ok(rows)
+            }))
+            .unwrap()
+        },
+    );
+}
+
+#[test]
+fn test_callbacks() -> Result<()> {
+    let session = connect();
+    let (done, finished) = mpsc::channel();
+    let callback_session = session.clone();
+    execute_query(
+        &session,
+        "CREATE KEYSPACE IF NOT EXISTS examples WITH replication = { 'class': \
+         'SimpleStrategy', 'replication_factor': '1' };",
+        move |result| match result {
+            Ok(_) => on_create_keyspace(callback_session, done),
+            Err(e) => done.send(Err(e)).unwrap(),
+        },
+    );
+
+    // Code running in parallel with the requests would go here.
+    let rows = finished
+        .recv_timeout(Duration::from_secs(30))
+        .expect("Callbacks did not complete")?;
+    assert!(!rows.is_empty());
+    for (key, value) in rows {
+        assert_eq!(key.timestamp() as i64, value);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_callback_error() {
+    let session = connect();
+    let (done, finished) = mpsc::channel();
+    execute_query(
+        &session,
+        "SELECT * FROM no_such_keyspace.t;",
+        move |result| done.send(result.is_err()).unwrap(),
+    );
+    assert!(finished.recv_timeout(Duration::from_secs(30)).unwrap());
+}
@@ -0,0 +1,50 @@
+mod help;
+
+use cassandra_cpp::*;
+use std::time::Duration;
+
+#[tokio::test]
+async fn test_close() -> Result<()> {
+    let session = help::create_test_session().await;
+    session.execute("SELECT * FROM system.local;").await?;
+    assert_eq!(session.in_flight_requests(), 0);
+
+    let abandoned = session.close(Duration::from_secs(10)).await?;
+    assert_eq!(abandoned, 0);
+
+    // The closed session no longer accepts requests.
+    let err = session
+        .execute("SELECT * FROM system.local;")
+        .await
+        .expect_err("Should have failed");
+    match err.kind() {
+        ErrorKind::CassError(CassErrorCode::LIB_NO_HOSTS_AVAILABLE, _) => (),
+        _ => panic!("Unexpected error {}", err),
+    }
+
+    // It can only be closed once.
+    assert!(session.close(Duration::from_secs(1)).await.is_err());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_close_with_in_flight_requests() -> Result<()> {
+    let session = help::create_test_session().await;
+    let pending: Vec<_> = (0..50)
+        .map(|_| {
+            session
+                .statement("SELECT * FROM system_schema.columns;")
+                .execute_future()
+        })
+        .collect();
+    assert_eq!(session.in_flight_requests(), 50);
+
+    let abandoned = session.close(Duration::from_millis(1)).await?;
+    assert!(abandoned <= 50);
+    assert_eq!(session.abandoned_requests(), abandoned as u64);
+    drop(pending);
+    assert_eq!(session.in_flight_requests(), 0);
+    // Dropping the session must not block, even if it has not finished closing.
+    drop(session);
+    Ok(())
+}
@@ -0,0 +1,75 @@
+use cassandra_cpp::*;
+
+fn config() -> ClusterConfig {
+    ClusterConfig {
+        contact_points: vec!["127.0.0.1".to_string()],
+        load_balancing: Some(LoadBalancing::RoundRobin),
+        request_timeout_ms: Some(10_000),
+        core_connections_per_host: Some(2),
+        max_connections_per_host: Some(4),
+        pending_requests_high_water_mark: Some(512),
+        pending_requests_low_water_mark: Some(256),
+        retry_policy: Some(RetryPolicyKind::DowngradingConsistency),
+        log_retries: Some(true),
+        timestamp_gen: Some(TimestampGenKind::Monotonic),
+        host_filters: vec![
+            HostFilter::WhitelistHosts {
+                hosts: vec!["127.0.0.1".to_string()],
+            },
+            HostFilter::BlacklistDcs {
+                dcs: vec!["analytics".to_string()],
+            },
+        ],
+        ..ClusterConfig::default()
+    }
+}
+
+#[tokio::test]
+async fn test_connect_from_config() -> Result<()> {
+    let mut cluster = Cluster::from_config(&config())?;
+    let session = cluster.connect().await?;
+    let result = session.execute("SELECT * FROM system.local;").await?;
+    assert_eq!(result.row_count(), 1);
+    Ok(())
+}
+
+#[test]
+fn test_invalid_config() {
+    let invalid = ClusterConfig {
+        write_bytes_high_water_mark: Some(1024),
+        write_bytes_low_water_mark: Some(2048),
+        ..config()
+    };
+    let err = Cluster::from_config(&invalid).expect_err("Should have failed");
+    match err.kind() {
+        ErrorKind::InvalidConfig(reason) => {
+            assert!(reason.contains("write_bytes_low_water_mark"), "{}", reason)
+        }
+        _ => panic!("Unexpected error {}", err),
+    }
+
+    let missing_tls_file = ClusterConfig {
+        tls: Some(TlsConfig {
+            trusted_certs: vec!["/no/such/cert.pem".into()],
+            ..TlsConfig::default()
+        }),
+        ..config()
+    };
+    assert!(Cluster::from_config(&missing_tls_file).is_err());
+}
+
+#[test]
+fn test_invalid_host_filter() {
+    let mut cluster = Cluster::default();
+    assert!(cluster
+        .set_blacklist_filtering(["10.0.0.1", "node1"])
+        .is_ok());
+    let err = cluster
+        .set_blacklist_filtering(["10.0.0.1,10.0.0.2"])
+        .expect_err("Should have failed");
+    match err.kind() {
+        ErrorKind::InvalidConfig(_) => (),
+        _ => panic!("Unexpected error {}", err),
+    }
+    assert!(cluster.set_whitelist_dc_filtering(["dc1,dc2"]).is_err());
+}
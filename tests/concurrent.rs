@@ -0,0 +1,110 @@
+mod help;
+
+use cassandra_cpp::*;
+use futures::StreamExt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+const NUM_STATEMENTS: i32 = 200;
+
+static CREATE_TABLE: &str =
+    "CREATE TABLE IF NOT EXISTS examples.concurrent (id int PRIMARY KEY, value text);";
+
+#[tokio::test]
+async fn test_execute_concurrent() -> Result<()> {
+    let session = help::create_test_session().await;
+    help::create_example_keyspace(&session).await;
+    session.execute(CREATE_TABLE).await?;
+
+    let insert = session
+        .prepare("INSERT INTO examples.concurrent (id, value) VALUES (?, ?)")
+        .await?;
+    let statements = (0..NUM_STATEMENTS).map(|id| {
+        let mut statement = insert.bind();
+        statement.bind(0, id).unwrap();
+        statement.bind(1, id.to_string().as_str()).unwrap();
+        statement
+    });
+    let completed = Arc::new(AtomicUsize::new(0));
+    let max_in_flight = Arc::new(AtomicUsize::new(0));
+    let (c, m) = (completed.clone(), max_in_flight.clone());
+    let results = session
+        .execute_concurrent(statements, 8)
+        .on_progress(move |progress| {
+            c.store(progress.completed, Ordering::SeqCst);
+            m.fetch_max(progress.in_flight, Ordering::SeqCst);
+            assert_eq!(progress.total, Some(NUM_STATEMENTS as usize));
+        })
+        .collect()
+        .await?;
+    assert_eq!(results.len(), NUM_STATEMENTS as usize);
+    assert!(results.iter().all(Result::is_ok));
+    assert_eq!(completed.load(Ordering::SeqCst), NUM_STATEMENTS as usize);
+    // The limit is saturated from the start.
+    assert_eq!(max_in_flight.load(Ordering::SeqCst), 8);
+
+    // Results come back in input order.
+    let select = session
+        .prepare("SELECT value FROM examples.concurrent WHERE id = ?")
+        .await?;
+    let statements = (0..NUM_STATEMENTS).map(|id| {
+        let mut statement = select.bind();
+        statement.bind(0, id).unwrap();
+        statement
+    });
+    let results = session.execute_concurrent(statements, 16).collect().await?;
+    for (id, result) in results.into_iter().enumerate() {
+        let result = result?;
+        let value = result.first_row().unwrap().get_column(0)?.get_string()?;
+        assert_eq!(value, id.to_string());
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_execute_concurrent_errors() -> Result<()> {
+    let session = help::create_test_session().await;
+    help::create_example_keyspace(&session).await;
+    session.execute(CREATE_TABLE).await?;
+
+    let statements = |session: &Session| {
+        let session = session.clone();
+        (0..20).map(move |id| match id {
+            5 | 10 => session.statement("SELECT * FROM examples.no_such_table"),
+            _ => session.statement("SELECT * FROM examples.concurrent LIMIT 1"),
+        })
+    };
+
+    match session
+        .execute_concurrent(statements(&session), 4)
+        .collect()
+        .await
+    {
+        Err(_) => {}
+        Ok(_) => panic!("Expected failure"),
+    }
+
+    let results = session
+        .execute_concurrent(statements(&session), 4)
+        .error_mode(ErrorMode::CollectAll)
+        .collect()
+        .await?;
+    assert_eq!(results.len(), 20);
+    for (id, result) in results.iter().enumerate() {
+        assert_eq!(result.is_err(), id == 5 || id == 10);
+    }
+
+    // The stream yields every result with its index.
+    let mut indices: Vec<usize> = session
+        .execute_concurrent(statements(&session), 4)
+        .error_mode(ErrorMode::CollectAll)
+        .into_stream()
+        .map(|(index, _)| index)
+        .collect()
+        .await;
+    indices.sort_unstable();
+    assert_eq!(indices, (0..20).collect::<Vec<_>>());
+
+    Ok(())
+}
@@ -0,0 +1,38 @@
+//! Test host event listeners.
+
+use cassandra_cpp::*;
+use futures::StreamExt;
+use std::sync::mpsc;
+use std::time::Duration;
+
+#[tokio::test]
+async fn test_host_events() -> Result<()> {
+    let mut cluster = Cluster::default();
+    cluster.set_contact_points("127.0.0.1")?;
+    cluster.set_load_balance_round_robin();
+    let mut events = cluster.host_events()?;
+    let (sender, receiver) = mpsc::channel();
+    cluster.on_host_event(move |event| {
+        let _ = sender.send(event);
+    })?;
+    let session = cluster.connect().await?;
+    session.execute("SELECT * FROM system.local").await?;
+
+    // A single healthy node may produce no events, but any that are seen must
+    // be for that node, and reach both listeners.
+    let expected: Inet = "127.0.0.1".parse()?;
+    if let Ok(Some(event)) = tokio::time::timeout(Duration::from_secs(1), events.next()).await {
+        assert_eq!(event.address, expected);
+        assert_eq!(
+            receiver.recv_timeout(Duration::from_secs(1)).ok(),
+            Some(event)
+        );
+    }
+
+    // The stream ends once the cluster and its sessions are gone.
+    drop(session);
+    drop(cluster);
+    let rest = tokio::time::timeout(Duration::from_secs(10), events.count()).await;
+    assert!(rest.is_ok(), "stream did not end");
+    Ok(())
+}
@@ -0,0 +1,87 @@
+mod help;
+
+use cassandra_cpp::*;
+
+static CREATE_MIXED: &str = "CREATE TABLE IF NOT EXISTS examples.keyset_mixed \
+     (sensor text, day int, ts bigint, value text, PRIMARY KEY (sensor, day, ts)) \
+     WITH CLUSTERING ORDER BY (day ASC, ts DESC);";
+static CREATE_DESC: &str = "CREATE TABLE IF NOT EXISTS examples.keyset_desc \
+     (sensor text, day int, ts bigint, value text, PRIMARY KEY (sensor, day, ts)) \
+     WITH CLUSTERING ORDER BY (day DESC, ts DESC);";
+
+async fn populate(session: &Session, table: &str) -> Result<Vec<(i32, i64, String)>> {
+    session
+        .execute(&format!("TRUNCATE examples.{}", table))
+        .await?;
+    let insert = session
+        .prepare(format!(
+            "INSERT INTO examples.{} (sensor, day, ts, value) VALUES (?, ?, ?, ?)",
+            table
+        ))
+        .await?;
+    for day in 0..3 {
+        for ts in 0..7 {
+            let mut statement = insert.bind();
+            statement.bind(0, "sensor-1")?;
+            statement.bind(1, day)?;
+            statement.bind(2, ts as i64)?;
+            statement.bind(3, format!("{}/{}", day, ts).as_str())?;
+            statement.execute().await?;
+        }
+    }
+
+    // The full partition, in clustering order.
+    let mut statement = session.statement(format!(
+        "SELECT day, ts, value FROM examples.{} WHERE sensor = 'sensor-1'",
+        table
+    ));
+    statement.set_paging_size(-1)?;
+    let result = statement.execute().await?;
+    let mut rows = vec![];
+    let mut iter = result.iter();
+    while let Some(row) = iter.next() {
+        rows.push(FromRow::from_row(&row)?);
+    }
+    Ok(rows)
+}
+
+async fn read_pages(session: &Session, table: &str) -> Result<Vec<(i32, i64, String)>> {
+    let schema = session.get_schema_meta();
+    let keyspace = schema.get_keyspace_by_name("examples");
+    let table = keyspace.table_by_name(table).expect("table");
+    let pager = KeysetPager::new(&table, 4)?.columns(&["day", "ts", "value"]);
+
+    let mut rows = vec![];
+    let mut cursor: Option<KeysetCursor> = None;
+    loop {
+        let (page, next) = pager
+            .execute_page::<(i32, i64, String), _>(session, cursor.as_ref(), |s| {
+                s.bind(0, "sensor-1")?;
+                Ok(())
+            })
+            .await?;
+        assert!(page.len() <= 4);
+        rows.extend(page);
+        match next {
+            None => break,
+            // Round-trip the cursor through its string form, as an API would.
+            Some(next) => cursor = Some(next.to_string().parse()?),
+        }
+    }
+    Ok(rows)
+}
+
+#[tokio::test]
+async fn test_keyset_paging() -> Result<()> {
+    let session = help::create_test_session().await;
+    help::create_example_keyspace(&session).await;
+
+    for (create, table) in &[(CREATE_MIXED, "keyset_mixed"), (CREATE_DESC, "keyset_desc")] {
+        session.execute(create).await?;
+        let expected = populate(&session, table).await?;
+        assert_eq!(expected.len(), 21);
+        assert_eq!(read_pages(&session, table).await?, expected);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,80 @@
+mod help;
+
+use cassandra_cpp::*;
+use std::time::Duration;
+
+static TABLE: &str = "examples.leases";
+
+#[tokio::test]
+async fn test_lease() -> Result<()> {
+    let session = help::create_test_session().await;
+    help::create_example_keyspace(&session).await;
+    Lease::create_table(&session, TABLE).await?;
+    session.execute("TRUNCATE examples.leases").await?;
+
+    let ttl = Duration::from_secs(10);
+    let lease = Lease::acquire(&session, TABLE, "job", "pod-1", ttl)
+        .await?
+        .expect("acquired");
+    assert!(Lease::acquire(&session, TABLE, "job", "pod-2", ttl)
+        .await?
+        .is_none());
+    // The owner may acquire its lease again.
+    assert!(Lease::acquire(&session, TABLE, "job", "pod-1", ttl)
+        .await?
+        .is_some());
+
+    assert!(lease.renew().await?);
+    assert!(lease.release().await?);
+    assert!(!lease.renew().await?);
+    assert!(!lease.release().await?);
+
+    let lease = Lease::acquire(&session, TABLE, "job", "pod-2", ttl)
+        .await?
+        .expect("acquired after release");
+    assert_eq!(lease.owner(), "pod-2");
+    assert!(lease.release().await?);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_lease_guard() -> Result<()> {
+    let session = help::create_test_session().await;
+    help::create_example_keyspace(&session).await;
+    Lease::create_table(&session, TABLE).await?;
+
+    let ttl = Duration::from_secs(3);
+    let lease = Lease::acquire(&session, TABLE, "guarded", "pod-1", ttl)
+        .await?
+        .expect("acquired");
+    let guard = lease.guard();
+
+    // The guard keeps the lease beyond its TTL.
+    tokio::time::sleep(ttl * 2).await;
+    assert!(guard.is_held());
+    assert!(Lease::acquire(&session, TABLE, "guarded", "pod-2", ttl)
+        .await?
+        .is_none());
+
+    // Releasing the guard releases the lease.
+    let lost = guard.lost();
+    assert!(guard.release().await?);
+    assert_eq!(lost.await, None);
+
+    // A guard notices when its lease is taken away.
+    let lease = Lease::acquire(&session, TABLE, "guarded", "pod-1", ttl)
+        .await?
+        .expect("acquired");
+    let guard = lease.guard();
+    session
+        .execute("DELETE FROM examples.leases WHERE name = 'guarded'")
+        .await?;
+    let lost = tokio::time::timeout(ttl * 2, guard.lost())
+        .await
+        .expect("lease lost");
+    assert_eq!(lost, Some(LeaseLost::Taken));
+    assert!(!guard.is_held());
+
+    Ok(())
+}
@@ -0,0 +1,71 @@
+mod help;
+
+use cassandra_cpp::*;
+
+static CREATE_TABLE: &str = "CREATE TABLE IF NOT EXISTS examples.lwt \
+     (name text PRIMARY KEY, email text, version int);";
+
+#[tokio::test]
+async fn test_execute_lwt() -> Result<()> {
+    let session = help::create_test_session().await;
+    help::create_example_keyspace(&session).await;
+    session.execute(CREATE_TABLE).await?;
+    session.execute("TRUNCATE examples.lwt").await?;
+
+    let insert = "INSERT INTO examples.lwt (name, email, version) VALUES (?, ?, 1) IF NOT EXISTS";
+    let mut statement = session.statement(insert);
+    statement.bind(0, "alice")?;
+    statement.bind(1, "alice@example.com")?;
+    assert!(statement.execute_lwt().await?.is_applied());
+
+    let mut statement = session.statement(insert);
+    statement.bind(0, "alice")?;
+    statement.bind(1, "other@example.com")?;
+    match statement.execute_lwt().await? {
+        LwtOutcome::NotApplied(existing) => {
+            assert!(existing.exists());
+            let email = existing.get_column_by_name("email")?.get_string()?;
+            assert_eq!(email, "alice@example.com");
+        }
+        other => panic!("Unexpected outcome {:?}", other),
+    }
+
+    // A conditional update with an explicit serial consistency.
+    let update = "UPDATE examples.lwt SET version = ? WHERE name = ? IF version = ?";
+    let mut statement = session.statement(update);
+    statement.bind(0, 2)?;
+    statement.bind(1, "alice")?;
+    statement.bind(2, 1)?;
+    statement.set_serial_consistency(Consistency::LOCAL_SERIAL)?;
+    assert!(statement.execute_lwt().await?.is_applied());
+
+    let mut statement = session.statement(update);
+    statement.bind(0, 3)?;
+    statement.bind(1, "alice")?;
+    statement.bind(2, 1)?;
+    match statement.execute_lwt().await? {
+        LwtOutcome::NotApplied(existing) => {
+            assert_eq!(existing.get_column_by_name("version")?.get_i32()?, 2);
+        }
+        other => panic!("Unexpected outcome {:?}", other),
+    }
+
+    // The row does not exist, so no values are returned.
+    let mut statement = session.statement(update);
+    statement.bind(0, 2)?;
+    statement.bind(1, "bob")?;
+    statement.bind(2, 1)?;
+    match statement.execute_lwt().await? {
+        LwtOutcome::NotApplied(existing) => assert!(!existing.exists()),
+        other => panic!("Unexpected outcome {:?}", other),
+    }
+
+    // A statement without a condition is rejected.
+    let statement = session.statement("SELECT * FROM examples.lwt");
+    match statement.execute_lwt().await {
+        Err(Error(ErrorKind::NotConditional, _)) => {}
+        other => panic!("Unexpected result {:?}", other),
+    }
+
+    Ok(())
+}
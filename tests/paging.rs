@@ -1,6 +1,7 @@
 mod help;
 
 use cassandra_cpp::*;
+use futures::TryStreamExt;
 
 static NUM_CONCURRENT_REQUESTS: usize = 100;
 const PAGE_SIZE: i32 = 10;
@@ -84,3 +85,121 @@ async fn test_paging() -> Result<()> {
 
     Ok(())
 }
+
+async fn stream_from_paging(session: &Session, prefetch: usize) -> Result<Vec<(String, String)>> {
+    let mut statement = session.statement(SELECT_QUERY);
+    statement.set_paging_size(PAGE_SIZE)?;
+    statement
+        .into_row_stream::<(String, String)>()
+        .prefetch(prefetch)
+        .try_collect()
+        .await
+}
+
+#[tokio::test]
+async fn test_row_stream() -> Result<()> {
+    let session = help::create_test_session().await;
+    help::create_example_keyspace(&session).await;
+
+    session.execute(CREATE_TABLE).await?;
+    session.execute("USE examples").await?;
+    insert_into_paging(&session).await?;
+
+    for prefetch in 0..3 {
+        let mut results = stream_from_paging(&session, prefetch).await?;
+        results.sort();
+        results.dedup();
+        assert_eq!(results.len(), NUM_CONCURRENT_REQUESTS);
+        assert!(results.iter().all(|(key, value)| key == value));
+    }
+
+    // Untyped rows are copied whole.
+    let mut statement = session.statement(SELECT_QUERY);
+    statement.set_paging_size(PAGE_SIZE)?;
+    let rows: Vec<Vec<OwnedValue>> = statement.into_row_stream().try_collect().await?;
+    assert_eq!(rows.len(), NUM_CONCURRENT_REQUESTS);
+    assert!(rows.iter().all(|row| match row.as_slice() {
+        [OwnedValue::Text(key), OwnedValue::Text(value)] => key == value,
+        _ => false,
+    }));
+
+    // A conversion failure ends the stream with an error.
+    let mut statement = session.statement(SELECT_QUERY);
+    statement.set_paging_size(PAGE_SIZE)?;
+    let result: Result<Vec<(i32,)>> = statement.into_row_stream::<(i32,)>().try_collect().await;
+    assert!(result.is_err());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_paging_cursor() -> Result<()> {
+    let session = help::create_test_session().await;
+    help::create_example_keyspace(&session).await;
+
+    session.execute(CREATE_TABLE).await?;
+    insert_into_paging(&session).await?;
+
+    let query = "SELECT key, value FROM examples.paging WHERE key > ? ALLOW FILTERING";
+    let mut results: Vec<(String, String)> = vec![];
+    // Round-trip the cursor through its string form, as an API would.
+    let mut encoded: Option<String> = None;
+    loop {
+        let mut statement = session.statement(query);
+        statement.record_bindings().bind(0, "")?;
+        let cursor: Option<PagingCursor> = encoded.as_deref().map(str::parse).transpose()?;
+        let (rows, next) = statement.execute_page(cursor.as_ref(), PAGE_SIZE).await?;
+        assert!(rows.len() <= PAGE_SIZE as usize);
+        results.extend(rows);
+        encoded = next.map(|next| next.to_string());
+        if encoded.is_none() {
+            break;
+        }
+    }
+    results.sort();
+    results.dedup();
+    assert_eq!(results.len(), NUM_CONCURRENT_REQUESTS);
+
+    // A cursor cannot be used with different bound values.
+    let mut statement = session.statement(query);
+    statement.record_bindings().bind(0, "")?;
+    let (_, next) = statement
+        .execute_page::<(String, String)>(None, PAGE_SIZE)
+        .await?;
+    let next = next.expect("more than one page");
+    let mut statement = session.statement(query);
+    statement.record_bindings().bind(0, "1")?;
+    match statement
+        .execute_page::<(String, String)>(Some(&next), PAGE_SIZE)
+        .await
+    {
+        Err(Error(ErrorKind::PagingCursorMismatch, _)) => {}
+        other => panic!("Unexpected result {:?}", other),
+    }
+
+    // Values bound without recording them cannot be checked, so are refused.
+    let mut statement = session.statement(query);
+    statement.bind(0, "")?;
+    match statement
+        .execute_page::<(String, String)>(None, PAGE_SIZE)
+        .await
+    {
+        Err(Error(ErrorKind::BindingsNotRecorded, _)) => {}
+        other => panic!("Unexpected result {:?}", other),
+    }
+
+    // Collections cannot be fingerprinted, so are refused.
+    let mut statement = session.statement("SELECT key, value FROM examples.paging WHERE key IN ?");
+    let mut keys = List::new();
+    keys.append_string("1")?;
+    statement.record_bindings().bind(0, keys)?;
+    match statement
+        .execute_page::<(String, String)>(None, PAGE_SIZE)
+        .await
+    {
+        Err(Error(ErrorKind::PagingCursorUnsupported(_, _), _)) => {}
+        other => panic!("Unexpected result {:?}", other),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,81 @@
+mod help;
+
+use cassandra_cpp::*;
+
+static CREATE_TABLE: &str = "CREATE TABLE IF NOT EXISTS examples.prepared_meta \
+     (tenant text, id int, seq bigint, body text, tags set<text>, owner text static, \
+     PRIMARY KEY ((tenant, id), seq));";
+
+#[tokio::test]
+async fn test_prepared_metadata() -> Result<()> {
+    let session = help::create_test_session().await;
+    help::create_example_keyspace(&session).await;
+    session.execute(CREATE_TABLE).await?;
+
+    let query = "SELECT seq, body AS text, writetime(body) FROM examples.prepared_meta \
+                 WHERE id = ? AND tenant = ?";
+    let select = session.prepare(query).await?;
+    assert_eq!(select.query(), query);
+    assert_eq!(select.parameter_count(), 2);
+    assert_eq!(select.result_column_count(), Some(3));
+    assert_eq!(select.result_column_name(0), Some("seq"));
+    assert_eq!(select.result_column_type(0), Some(ValueType::BIGINT));
+    assert_eq!(select.result_column_name(1), Some("text"));
+    assert_eq!(select.result_column_type(1), Some(ValueType::TEXT));
+    assert_eq!(select.result_column_name(2), Some("writetime(body)"));
+    assert!(select.result_column_name(3).is_none());
+    // Partition key order is (tenant, id).
+    assert_eq!(select.partition_key_indices(), Some(&[1, 0][..]));
+
+    let all = session
+        .prepare("SELECT * FROM examples.prepared_meta")
+        .await?;
+    let names: Vec<_> = (0..all.result_column_count().unwrap())
+        .map(|index| all.result_column_name(index).unwrap())
+        .collect();
+    // Static columns come before regular ones.
+    assert_eq!(names, vec!["tenant", "id", "seq", "owner", "body", "tags"]);
+    assert_eq!(all.result_column_type(5), Some(ValueType::SET));
+    assert_eq!(all.partition_key_indices(), None);
+
+    let count = session
+        .prepare("SELECT count(*), count(body) FROM examples.prepared_meta")
+        .await?;
+    assert_eq!(count.result_column_name(0), Some("count"));
+    assert_eq!(count.result_column_name(1), Some("system.count(body)"));
+    assert_eq!(count.result_column_type(1), Some(ValueType::BIGINT));
+
+    let insert = session
+        .prepare("INSERT INTO examples.prepared_meta (tenant, id, seq, body) VALUES (?, ?, ?, ?)")
+        .await?;
+    assert_eq!(insert.parameter_count(), 4);
+    assert_eq!(insert.result_column_count(), Some(0));
+    assert_eq!(insert.partition_key_indices(), Some(&[0, 1][..]));
+
+    let conditional = session
+        .prepare("UPDATE examples.prepared_meta SET body = ? WHERE tenant = ? AND id = ? AND seq = ? IF EXISTS")
+        .await?;
+    assert_eq!(conditional.result_column_count(), None);
+    assert_eq!(conditional.partition_key_indices(), Some(&[1, 2][..]));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_prepared_metadata_in_session_keyspace() -> Result<()> {
+    let session = help::create_test_session().await;
+    help::create_example_keyspace(&session).await;
+    session.execute(CREATE_TABLE).await?;
+
+    let mut cluster = Cluster::default();
+    cluster.set_contact_points("127.0.0.1")?;
+    cluster.set_load_balance_round_robin();
+    let session = cluster.connect_keyspace("examples").await?;
+
+    let select = session
+        .prepare("SELECT seq, body FROM prepared_meta WHERE tenant = ? AND id = ?")
+        .await?;
+    assert_eq!(select.result_column_count(), Some(2));
+    assert_eq!(select.result_column_type(1), Some(ValueType::TEXT));
+    assert_eq!(select.partition_key_indices(), Some(&[0, 1][..]));
+    Ok(())
+}
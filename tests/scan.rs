@@ -0,0 +1,67 @@
+mod help;
+
+use cassandra_cpp::*;
+use futures::{StreamExt, TryStreamExt};
+use std::collections::BTreeSet;
+
+const NUM_ROWS: i32 = 200;
+
+static CREATE_TABLE: &str =
+    "CREATE TABLE IF NOT EXISTS examples.scan (bucket int, id int, value text, \
+     PRIMARY KEY ((bucket, id)));";
+static INSERT_QUERY: &str = "INSERT INTO examples.scan (bucket, id, value) VALUES (?, ?, ?);";
+
+async fn scanner(session: &Session) -> Result<TableScanner> {
+    let schema = session.get_schema_meta();
+    let keyspace = schema.get_keyspace_by_name("examples");
+    let table = keyspace.table_by_name("scan").expect("table");
+    Ok(TableScanner::new(&table)?
+        .columns(&["bucket", "id"])
+        .splits(16)
+        .concurrency(4)
+        .page_size(10))
+}
+
+#[tokio::test]
+async fn test_table_scan() -> Result<()> {
+    let session = help::create_test_session().await;
+    help::create_example_keyspace(&session).await;
+    session.execute(CREATE_TABLE).await?;
+    session.execute("TRUNCATE examples.scan").await?;
+
+    let insert = session.prepare(INSERT_QUERY).await?;
+    for id in 0..NUM_ROWS {
+        let mut statement = insert.bind();
+        statement.bind(0, id % 7)?;
+        statement.bind(1, id)?;
+        statement.bind(2, id.to_string().as_str())?;
+        statement.execute().await?;
+    }
+    let expected: BTreeSet<(i32, i32)> = (0..NUM_ROWS).map(|id| (id % 7, id)).collect();
+
+    let scanner = scanner(&session).await?;
+    let rows: Vec<(i32, i32)> = scanner.scan::<(i32, i32)>(&session).try_collect().await?;
+    assert_eq!(rows.len(), NUM_ROWS as usize);
+    assert_eq!(rows.into_iter().collect::<BTreeSet<_>>(), expected);
+
+    // Interrupt a scan part way through, then resume it from its checkpoint.
+    let mut stream = scanner.scan::<(i32, i32)>(&session);
+    let mut seen = BTreeSet::new();
+    for _ in 0..(NUM_ROWS / 2) {
+        seen.insert(stream.next().await.expect("row")?);
+    }
+    let checkpoint = stream.checkpoint().encode();
+    drop(stream);
+
+    let checkpoint = ScanCheckpoint::decode(&checkpoint)?;
+    assert!(!checkpoint.is_done());
+    let rest: Vec<(i32, i32)> = scanner
+        .resume::<(i32, i32)>(&session, checkpoint)?
+        .try_collect()
+        .await?;
+    assert!(rest.len() < NUM_ROWS as usize);
+    seen.extend(rest);
+    assert_eq!(seen, expected);
+
+    Ok(())
+}
@@ -0,0 +1,62 @@
+mod help;
+
+use cassandra_cpp::*;
+use std::time::Duration;
+
+const QUERY: &str = "SELECT * FROM system_schema.columns;";
+
+#[tokio::test]
+async fn test_with_deadline() -> Result<()> {
+    let session = help::create_test_session().await;
+    let abandoned = session.abandoned_requests();
+
+    let result = session
+        .statement("SELECT * FROM system.local;")
+        .execute_future()
+        .with_deadline(Duration::from_secs(10))
+        .await?;
+    assert_eq!(result.row_count(), 1);
+    assert_eq!(session.abandoned_requests(), abandoned);
+
+    match session
+        .statement(QUERY)
+        .execute_future()
+        .with_deadline(Duration::from_micros(1))
+        .await
+    {
+        Err(e) => match e.kind() {
+            ErrorKind::Timeout(_) => assert_eq!(session.abandoned_requests(), abandoned + 1),
+            _ => panic!("Unexpected error {}", e),
+        },
+        // The request may complete before the deadline is checked.
+        Ok(_) => assert_eq!(session.abandoned_requests(), abandoned),
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_wait_timeout() -> Result<()> {
+    let session = help::create_test_session().await;
+
+    // Time out many requests, then drop them while the driver may still be
+    // executing them, to check the late callbacks are safe.
+    let mut timed_out = 0;
+    for _ in 0..100 {
+        let future = session.statement(QUERY).execute_future();
+        if let Err(e) = future.wait_timeout(Duration::from_micros(1)) {
+            match e.kind() {
+                ErrorKind::Timeout(timeout) => assert_eq!(*timeout, Duration::from_micros(1)),
+                _ => panic!("Unexpected error {}", e),
+            }
+            timed_out += 1;
+        }
+    }
+    assert_eq!(session.abandoned_requests(), timed_out);
+
+    let result = session
+        .statement("SELECT * FROM system.local;")
+        .execute_future()
+        .wait_timeout(Duration::from_secs(10))?;
+    assert_eq!(result.row_count(), 1);
+    Ok(())
+}
@@ -0,0 +1,67 @@
+mod help;
+
+use cassandra_cpp::*;
+
+static CREATE_TABLE: &str =
+    "CREATE TABLE IF NOT EXISTS examples.token (bucket text, id int, value text, \
+     PRIMARY KEY ((bucket, id)));";
+static CREATE_SIMPLE_TABLE: &str =
+    "CREATE TABLE IF NOT EXISTS examples.token_simple (id bigint PRIMARY KEY, value text);";
+
+#[tokio::test]
+async fn test_partition_token() -> Result<()> {
+    let session = help::create_test_session().await;
+    help::create_example_keyspace(&session).await;
+    session.execute(CREATE_TABLE).await?;
+    session.execute(CREATE_SIMPLE_TABLE).await?;
+
+    for id in 0..20 {
+        let bucket = format!("bucket-{}", id % 3);
+        let expected = partition_token(&[&bucket, &id]);
+        let mut insert =
+            session.statement("INSERT INTO examples.token (bucket, id, value) VALUES (?, ?, ?)");
+        insert.bind(0, bucket.as_str())?;
+        insert.bind(1, id)?;
+        insert.bind(2, "x")?;
+        insert.execute().await?;
+        let mut select = session
+            .statement("SELECT token(bucket, id) FROM examples.token WHERE bucket = ? AND id = ?");
+        select.bind(0, bucket.as_str())?;
+        select.bind(1, id)?;
+        let result = select.execute().await?;
+        let token = result.first_row().expect("row").get_column(0)?.get_i64()?;
+        assert_eq!(token, expected);
+    }
+
+    let mut insert =
+        session.statement("INSERT INTO examples.token_simple (id, value) VALUES (?, ?)");
+    insert.bind(0, 42i64)?;
+    insert.bind(1, "x")?;
+    insert.execute().await?;
+    let result = session
+        .execute("SELECT token(id) FROM examples.token_simple WHERE id = 42")
+        .await?;
+    let token = result.first_row().expect("row").get_column(0)?.get_i64()?;
+    assert_eq!(token, partition_token(&[&42i64]));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_token_ring() -> Result<()> {
+    let session = help::create_test_session().await;
+    help::create_example_keyspace(&session).await;
+
+    let ring = TokenRing::fetch(&session).await?;
+    assert!(!ring.hosts().is_empty());
+
+    let schema = session.get_schema_meta();
+    let keyspace = schema.get_keyspace_by_name("examples");
+    let replication = Replication::from_keyspace(&keyspace)?;
+    let token = partition_token(&[&"some key"]);
+    let replicas = ring.replicas(token, &replication);
+    assert!(!replicas.is_empty());
+    assert_eq!(Some(replicas[0]), ring.primary(token));
+
+    Ok(())
+}
@@ -0,0 +1,53 @@
+mod help;
+
+use cassandra_cpp::*;
+
+static CREATE_TABLE: &str =
+    "CREATE TABLE IF NOT EXISTS examples.traced (key text PRIMARY KEY, value int);";
+static INSERT_QUERY: &str = "INSERT INTO examples.traced (key, value) VALUES (?, ?);";
+
+#[tokio::test]
+async fn test_fetch_trace() -> Result<()> {
+    let session = help::create_test_session().await;
+    help::create_example_keyspace(&session).await;
+    session.execute(CREATE_TABLE).await?;
+
+    let mut statement = session.statement(INSERT_QUERY);
+    statement.bind(0, "traced")?;
+    statement.bind(1, 42)?;
+    let (_result, tracing_id) = statement.execute_traced().await?;
+    let tracing_id = tracing_id.expect("Missing tracing id");
+
+    let trace = session.fetch_trace(tracing_id).await?;
+    assert_eq!(trace.tracing_id, tracing_id);
+    assert!(!trace.events.is_empty());
+    assert!(trace.timeline().contains(&tracing_id.to_string()));
+
+    let mut statement = session.statement("SELECT * FROM examples.traced WHERE key = 'traced'");
+    statement.set_tracing(true)?;
+    let (_result, tracing_id) = session.execute_with_tracing_id(&statement).await?;
+    let tracing_id = tracing_id.expect("Missing tracing id");
+    let trace = session.fetch_trace(tracing_id).await?;
+    assert_eq!(trace.tracing_id, tracing_id);
+
+    // Without tracing, the request still succeeds, but has no tracing id.
+    let statement = session.statement("SELECT * FROM examples.traced WHERE key = 'traced'");
+    let (result, tracing_id) = session.execute_with_tracing_id(&statement).await?;
+    assert_eq!(result.row_count(), 1);
+    assert_eq!(tracing_id, None);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_fetch_missing_trace() -> Result<()> {
+    let session = help::create_test_session().await;
+    let tracing_id = UuidGen::default().gen_time();
+    match session
+        .fetch_trace_with_retries(tracing_id, 2, std::time::Duration::from_millis(1))
+        .await
+    {
+        Err(Error(ErrorKind::TraceUnavailable(id), _)) => assert_eq!(id, tracing_id),
+        other => panic!("Unexpected result {:?}", other),
+    }
+    Ok(())
+}
@@ -0,0 +1,139 @@
+mod help;
+
+use cassandra_cpp::*;
+
+async fn create_table(session: &Session) -> Result<()> {
+    help::create_example_keyspace(session).await;
+    session
+        .execute("CREATE TYPE IF NOT EXISTS examples.point (x int, y int);")
+        .await?;
+    session
+        .execute(
+            "CREATE TABLE IF NOT EXISTS examples.type_check (id int PRIMARY KEY, \
+             scores list<int>, tags map<text, bigint>, location frozen<point>);",
+        )
+        .await?;
+    Ok(())
+}
+
+fn assert_mismatch<T>(result: Result<T>, index: usize, name: &str, expected: &str, provided: &str) {
+    match result {
+        Err(Error(ErrorKind::BindTypeMismatch(i, n, e, p), _)) => {
+            assert_eq!(
+                (i, n.as_str(), e.as_str(), p.as_str()),
+                (index, name, expected, provided)
+            );
+        }
+        Err(err) => panic!("expected a type mismatch, got {}", err),
+        Ok(_) => panic!("expected a type mismatch"),
+    }
+}
+
+#[tokio::test]
+async fn test_scalar_mismatch() -> Result<()> {
+    let session = help::create_test_session().await;
+    create_table(&session).await?;
+    let prepared = session
+        .prepare("INSERT INTO examples.type_check (id, scores) VALUES (?, ?)")
+        .await?;
+
+    let mut statement = prepared.bind();
+    assert_mismatch(statement.bind(0, "one"), 0, "id", "int", "text");
+    assert_mismatch(statement.bind_by_name("id", 1i64), 0, "id", "int", "bigint");
+    statement.bind(0, 1)?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_collection_element_mismatch() -> Result<()> {
+    let session = help::create_test_session().await;
+    create_table(&session).await?;
+    let prepared = session
+        .prepare("INSERT INTO examples.type_check (id, scores, tags) VALUES (?, ?, ?)")
+        .await?;
+    let mut statement = prepared.bind();
+
+    let mut scores = List::new();
+    scores.append_int32(1)?;
+    scores.append_string("two")?;
+    assert_mismatch(
+        statement.bind_list(1, scores),
+        1,
+        "scores",
+        "list<int>",
+        "list<int | text>",
+    );
+
+    let mut tags = Map::new();
+    tags.append_string("a")?;
+    tags.append_int32(1)?;
+    assert_mismatch(
+        statement.bind_map_by_name("tags", tags),
+        2,
+        "tags",
+        "map<text, bigint>",
+        "map<text, int>",
+    );
+
+    let mut scores = List::new();
+    scores.append_int32(1)?;
+    let mut tags = Map::new();
+    tags.append_string("a")?;
+    tags.append_int64(1)?;
+    statement.bind(0, 1)?;
+    statement.bind_list(1, scores)?;
+    statement.bind_map(2, tags)?;
+    statement.execute().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_user_type_field_mismatch() -> Result<()> {
+    let session = help::create_test_session().await;
+    create_table(&session).await?;
+    let prepared = session
+        .prepare("INSERT INTO examples.type_check (id, location) VALUES (?, ?)")
+        .await?;
+    let mut statement = prepared.bind();
+
+    let point_type = DataType::new_udt(2);
+    point_type.add_sub_value_type_by_name::<&str>("x", ValueType::INT)?;
+    point_type.add_sub_value_type_by_name::<&str>("y", ValueType::TEXT)?;
+    let point = point_type.new_user_type();
+    assert_mismatch(
+        statement.bind_user_type(1, &point),
+        1,
+        "location",
+        "point",
+        "udt<x int, y text>",
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_bind_with_collections() -> Result<()> {
+    let session = help::create_test_session().await;
+    create_table(&session).await?;
+    let prepared = session
+        .prepare("INSERT INTO examples.type_check (id, scores, tags) VALUES (?, ?, ?)")
+        .await?;
+
+    let mut scores = List::new();
+    scores.append_int32(1)?;
+    let mut tags = Map::new();
+    tags.append_string("a")?;
+    tags.append_int32(1)?;
+    assert_mismatch(
+        prepared.bind_with(&[&3, &scores, &tags]),
+        2,
+        "tags",
+        "map<text, bigint>",
+        "map<text, int>",
+    );
+
+    let mut tags = Map::new();
+    tags.append_string("a")?;
+    tags.append_int64(1)?;
+    prepared.bind_with(&[&3, &scores, &tags])?.execute().await?;
+    Ok(())
+}
@@ -0,0 +1,46 @@
+mod help;
+
+use cassandra_cpp::*;
+
+#[tokio::test]
+async fn test_connect_from_uri() -> Result<()> {
+    let session = help::create_test_session().await;
+    help::create_example_keyspace(&session).await;
+
+    let mut cluster = Cluster::from_uri(
+        "cassandra://127.0.0.1:9042/examples?consistency=ONE&request_timeout=10s",
+    )?;
+    assert_eq!(cluster.keyspace(), Some("examples"));
+    let session = cluster.connect().await?;
+
+    // The session is bound to the keyspace, so tables need not be qualified.
+    session
+        .execute("CREATE TABLE IF NOT EXISTS uri (key text PRIMARY KEY, value int);")
+        .await?;
+    let mut statement = session.statement("INSERT INTO uri (key, value) VALUES (?, ?);");
+    statement.bind(0, "k")?;
+    statement.bind(1, 1i32)?;
+    statement.execute().await?;
+    let result = session
+        .execute("SELECT value FROM examples.uri WHERE key = 'k';")
+        .await?;
+    let value: i32 = result.first_row().expect("Missing row").get(0)?;
+    assert_eq!(value, 1);
+    Ok(())
+}
+
+#[test]
+fn test_invalid_uri() {
+    for uri in &[
+        "127.0.0.1",
+        "cassandra://",
+        "cassandra://127.0.0.1?consistency=MOST",
+        "cassandra://127.0.0.1?serial_consistency=QUORUM",
+    ] {
+        let err = Cluster::from_uri(uri).expect_err(uri);
+        match err.kind() {
+            ErrorKind::InvalidConfig(_) => (),
+            _ => panic!("Unexpected error {} for {}", err, uri),
+        }
+    }
+}
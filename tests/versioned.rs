@@ -0,0 +1,87 @@
+mod help;
+
+use cassandra_cpp::*;
+use std::time::Duration;
+
+static CREATE_TABLE: &str = "CREATE TABLE IF NOT EXISTS examples.accounts \
+     (id int PRIMARY KEY, balance bigint, version bigint);";
+
+fn bind_key(id: i32) -> impl Fn(&mut Statement) -> Result<()> {
+    move |statement| {
+        statement.bind_by_name("id", id)?;
+        Ok(())
+    }
+}
+
+async fn deposit(accounts: &VersionedTable, id: i32, amount: i64) -> Result<i64> {
+    accounts
+        .update(bind_key(id), |current, update| {
+            let balance = match current {
+                Some(row) => row.get_column_by_name("balance")?.get_i64()?,
+                None => 0,
+            };
+            update.bind_by_name("balance", balance + amount)?;
+            Ok(())
+        })
+        .await
+}
+
+#[tokio::test]
+async fn test_versioned_update() -> Result<()> {
+    let session = help::create_test_session().await;
+    help::create_example_keyspace(&session).await;
+    session.execute(CREATE_TABLE).await?;
+    session.execute("TRUNCATE examples.accounts").await?;
+
+    let accounts = VersionedTable::new(&session, "examples.accounts", &["id"], &["balance"]);
+    assert_eq!(deposit(&accounts, 1, 10).await?, 1);
+    assert_eq!(deposit(&accounts, 1, 5).await?, 2);
+
+    // Concurrent updates all apply, retrying on conflict.
+    let accounts = accounts.max_attempts(50).backoff(Duration::from_millis(1));
+    let deposits = (0..5).map(|_| deposit(&accounts, 1, 1));
+    futures::future::try_join_all(deposits).await?;
+
+    let result = session
+        .execute("SELECT balance, version FROM examples.accounts WHERE id = 1")
+        .await?;
+    let row = result.first_row().unwrap();
+    assert_eq!(row.get_column(0)?.get_i64()?, 20);
+    assert_eq!(row.get_column(1)?.get_i64()?, 7);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_versioned_conflict() -> Result<()> {
+    let session = help::create_test_session().await;
+    help::create_example_keyspace(&session).await;
+    session.execute(CREATE_TABLE).await?;
+
+    let accounts = VersionedTable::new(&session, "examples.accounts", &["id"], &["balance"])
+        .max_attempts(3)
+        .backoff(Duration::from_millis(1));
+    deposit(&accounts, 2, 0).await?;
+
+    // Every attempt is invalidated by a concurrent write to the version.
+    let mut attempts = 0;
+    let result = accounts
+        .update(bind_key(2), |_, update| {
+            attempts += 1;
+            let bump = format!(
+                "UPDATE examples.accounts SET version = {} WHERE id = 2",
+                100 + attempts
+            );
+            futures::executor::block_on(session.execute(bump))?;
+            update.bind_by_name("balance", 0i64)?;
+            Ok(())
+        })
+        .await;
+    match result {
+        Err(Error(ErrorKind::VersionConflict(3), _)) => {}
+        other => panic!("Unexpected result {:?}", other),
+    }
+    assert_eq!(attempts, 3);
+
+    Ok(())
+}